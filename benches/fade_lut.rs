@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+use embedded_graphics_extras::{FadedRectangle, Fading};
+
+fn fade_lut(c: &mut Criterion) {
+    let rect = Rectangle::new(Point::zero(), Size::new(240, 32));
+    let faded = FadedRectangle::new(rect, Rgb565::CSS_ORANGE, Fading::Horizontal { steps: 48 });
+
+    c.bench_function("wide fade with lut", |b| {
+        b.iter(|| {
+            black_box(&faded).into_iter().fold(0u32, |sum, pixel| {
+                sum.wrapping_add(pixel.1.into_storage() as u32)
+            })
+        })
+    });
+    c.bench_function("wide fade per pixel", |b| {
+        b.iter(|| {
+            black_box(&faded)
+                .iter_without_lut()
+                .fold(0u32, |sum, pixel| {
+                    sum.wrapping_add(pixel.1.into_storage() as u32)
+                })
+        })
+    });
+}
+
+criterion_group!(benches, fade_lut);
+criterion_main!(benches);
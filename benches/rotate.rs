@@ -0,0 +1,65 @@
+use core::convert::Infallible;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+use embedded_graphics_extras::{
+    rotate::{RotatedDrawTarget, Rotation},
+    FadedRectangle, Fading,
+};
+
+const SIZE: Size = Size::new(240, 135);
+
+// A plain framebuffer, so the benchmark measures the adapter rather than a simulator.
+struct Framebuffer {
+    pixels: Vec<Rgb565>,
+}
+
+impl Dimensions for Framebuffer {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), SIZE)
+    }
+}
+
+impl DrawTarget for Framebuffer {
+    type Color = Rgb565;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if self.bounding_box().contains(point) {
+                self.pixels[point.y as usize * SIZE.width as usize + point.x as usize] = color;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn rotate(c: &mut Criterion) {
+    let mut framebuffer = Framebuffer {
+        pixels: vec![Rgb565::BLACK; (SIZE.width * SIZE.height) as usize],
+    };
+    let rect = Rectangle::new(Point::new(10, 10), Size::new(60, 100));
+    let faded = FadedRectangle::new(rect, Rgb565::CSS_ORANGE, Fading::AllEdges { steps: 12 });
+    let colors = || (0..6000).map(|value| Rgb565::new(value as u8, 0, 0));
+
+    for rotation in [Rotation::Rotate0, Rotation::Rotate90, Rotation::Rotate180] {
+        c.bench_function(&format!("faded rectangle {rotation:?}"), |b| {
+            b.iter(|| {
+                let mut rotated = RotatedDrawTarget::new(&mut framebuffer, rotation);
+                black_box(&faded).draw(&mut rotated).unwrap();
+            })
+        });
+        c.bench_function(&format!("fill contiguous {rotation:?}"), |b| {
+            b.iter(|| {
+                let mut rotated = RotatedDrawTarget::new(&mut framebuffer, rotation);
+                rotated.fill_contiguous(black_box(&rect), colors()).unwrap();
+            })
+        });
+    }
+}
+
+criterion_group!(benches, rotate);
+criterion_main!(benches);
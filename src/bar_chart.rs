@@ -0,0 +1,272 @@
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+
+use crate::{
+    color::FadeColor,
+    faded_rectangle::{FadedRectangle, Fading},
+    progress_bar::split,
+};
+
+/// A bar chart of signed `values`, one bar per value.
+///
+/// `range` is mapped to the height of `bounds`, values outside of it are clamped. Bars start
+/// at the baseline of zero and grow upwards for positive values, downwards for negative
+/// ones. If `range` doesn't contain zero, the baseline is the end of `range` closest to zero.
+/// The bars are placed next to each other with `gap` pixels between them; the gaps and the
+/// area around the bars aren't drawn.
+///
+/// `bar_color_fn` is called with the index and value of each bar and returns its color.
+#[derive(Copy, Clone, Debug)]
+pub struct BarChart<'a, C = Rgb888, F = fn(usize, i32) -> C> {
+    pub bounds: Rectangle,
+    pub values: &'a [i32],
+    pub range: (i32, i32),
+    pub bar_color_fn: F,
+    pub gap: u32,
+    /// Fades the tips of the bars over this many steps, the top of positive bars and the
+    /// bottom of negative ones. `0` draws solid bars.
+    pub fade_steps: u16,
+    pub background: C,
+}
+
+// The color function can't be formatted.
+#[cfg(feature = "defmt")]
+impl<C, F> defmt::Format for BarChart<'_, C, F>
+where
+    C: defmt::Format,
+{
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "BarChart {{ bounds: {}, values: {}, range: {}, gap: {}, fade_steps: {}, background: {}, .. }}",
+            self.bounds,
+            self.values,
+            self.range,
+            self.gap,
+            self.fade_steps,
+            self.background
+        )
+    }
+}
+
+impl<'a, C, F> BarChart<'a, C, F>
+where
+    C: FadeColor,
+    F: Fn(usize, i32) -> C,
+{
+    pub fn new(bounds: Rectangle, values: &'a [i32], range: (i32, i32), bar_color_fn: F) -> Self {
+        Self {
+            bounds,
+            values,
+            range,
+            bar_color_fn,
+            gap: 1,
+            fade_steps: 0,
+            background: C::DEFAULT_TARGET,
+        }
+    }
+
+    pub fn with_gap(mut self, gap: u32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn with_fade(mut self, steps: u16) -> Self {
+        self.fade_steps = steps;
+        self
+    }
+
+    pub fn with_background(mut self, background: C) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// The row of the pixel edge `value` maps to, from the top of `bounds` for the upper end
+    /// of `range` to the bottom for the lower end.
+    pub fn value_to_y(&self, value: i32) -> i32 {
+        let (low, high) = (
+            self.range.0.min(self.range.1),
+            self.range.0.max(self.range.1),
+        );
+        let top = self.bounds.top_left.y;
+        let height = i64::from(self.bounds.size.height);
+        let span = i64::from(high) - i64::from(low);
+        if span == 0 {
+            return top + height as i32;
+        }
+
+        let from_top = i64::from(high) - i64::from(value.clamp(low, high));
+        top + ((from_top * height + span / 2) / span) as i32
+    }
+
+    /// The row of the baseline bars start from.
+    pub fn baseline(&self) -> i32 {
+        self.value_to_y(0)
+    }
+
+    /// The areas of the bars from left to right, zero sized for values on the baseline.
+    pub fn bar_rects(&self) -> impl Iterator<Item = Rectangle> + '_ {
+        let baseline = self.baseline();
+        let columns = split(self.bounds, self.values.len() as u32, self.gap, true);
+
+        columns.zip(self.values).map(move |(column, &value)| {
+            let y = self.value_to_y(value);
+            let (top, bottom) = (y.min(baseline), y.max(baseline));
+            Rectangle::new(
+                Point::new(column.top_left.x, top),
+                Size::new(column.size.width, (bottom - top) as u32),
+            )
+        })
+    }
+}
+
+impl<C, F> Dimensions for BarChart<'_, C, F> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+impl<C, F> Drawable for BarChart<'_, C, F>
+where
+    C: FadeColor,
+    F: Fn(usize, i32) -> C,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let baseline = self.baseline();
+        for ((index, &value), rect) in self.values.iter().enumerate().zip(self.bar_rects()) {
+            if rect.is_zero_sized() {
+                continue;
+            }
+
+            let steps = self.fade_steps;
+            let fading = match steps {
+                0 => Fading::None,
+                _ if rect.top_left.y < baseline => Fading::Top { steps },
+                _ => Fading::Bottom { steps },
+            };
+            FadedRectangle::new(rect, (self.bar_color_fn)(index, value), fading)
+                .with_fade_target(self.background)
+                .draw(target)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+
+    const BOUNDS: Rectangle = Rectangle::new(Point::new(1, 2), Size::new(19, 20));
+
+    fn sign_color(_: usize, value: i32) -> Rgb888 {
+        if value < 0 {
+            Rgb888::RED
+        } else {
+            Rgb888::GREEN
+        }
+    }
+
+    fn rects(values: &[i32], range: (i32, i32)) -> Vec<(i32, i32, u32, u32)> {
+        let chart = BarChart::new(BOUNDS, values, range, sign_color);
+        let rects = chart.bar_rects().map(|rect| {
+            let Rectangle { top_left, size } = rect;
+            (top_left.x, top_left.y, size.width, size.height)
+        });
+        rects.collect()
+    }
+
+    #[test]
+    fn test_bar_chart_positive() {
+        assert_eq!(
+            rects(&[0, 25, 100, 150], (0, 100)),
+            [(1, 22, 4, 0), (6, 17, 4, 5), (11, 2, 4, 20), (16, 2, 4, 20)]
+        );
+        // The baseline is the bottom if the range starts above zero.
+        assert_eq!(
+            rects(&[40, 50, 100, 150], (150, 50)),
+            [
+                (1, 22, 4, 0),
+                (6, 22, 4, 0),
+                (11, 12, 4, 10),
+                (16, 2, 4, 20)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bar_chart_negative() {
+        assert_eq!(
+            rects(&[-20, -60, -100, -200], (-100, -20)),
+            [(1, 2, 4, 0), (6, 2, 4, 10), (11, 2, 4, 20), (16, 2, 4, 20)]
+        );
+        assert_eq!(
+            rects(&[0, -5, -10], (-10, 0)),
+            [(1, 2, 5, 0), (7, 2, 6, 10), (14, 2, 6, 20)]
+        );
+    }
+
+    #[test]
+    fn test_bar_chart_mixed() {
+        let values = [30, -10, -50, 0, i32::MAX, i32::MIN];
+        let expected_rects = [
+            (1, 6, 2, 6),
+            (4, 12, 2, 2),
+            (7, 12, 3, 10),
+            (11, 12, 2, 0),
+            (14, 2, 2, 10),
+            (17, 12, 3, 10),
+        ];
+        assert_eq!(rects(&values, (-50, 50)), expected_rects);
+        assert!(rects(&values, (7, 7)).iter().all(|rect| rect.3 == 0));
+
+        let mut display = MockDisplay::new();
+        let chart = BarChart::new(BOUNDS, &values, (-50, 50), sign_color);
+        assert_eq!(chart.baseline(), 12);
+        chart.draw(&mut display).unwrap();
+
+        let mut expected = MockDisplay::new();
+        for ((x, y, width, height), value) in expected_rects.into_iter().zip(values) {
+            let rect = Rectangle::new(Point::new(x, y), Size::new(width, height));
+            expected.fill_solid(&rect, sign_color(0, value)).unwrap();
+        }
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_bar_chart_fade() {
+        let values = [50, -50];
+        let background = Rgb888::CSS_DARK_SLATE_GRAY;
+        let chart = BarChart::new(BOUNDS, &values, (-50, 50), sign_color)
+            .with_fade(4)
+            .with_background(background);
+
+        let mut display = MockDisplay::new();
+        chart.draw(&mut display).unwrap();
+
+        // The tips fade, the ends at the baseline keep the bar color.
+        let positive = Rectangle::new(Point::new(1, 2), Size::new(9, 10));
+        let negative = Rectangle::new(Point::new(11, 12), Size::new(9, 10));
+        assert_ne!(display.get_pixel(Point::new(1, 2)), Some(Rgb888::GREEN));
+        assert_eq!(display.get_pixel(Point::new(1, 11)), Some(Rgb888::GREEN));
+        assert_eq!(display.get_pixel(Point::new(11, 12)), Some(Rgb888::RED));
+        assert_ne!(display.get_pixel(Point::new(11, 21)), Some(Rgb888::RED));
+
+        let mut expected = MockDisplay::new();
+        let top = FadedRectangle::new(positive, Rgb888::GREEN, Fading::Top { steps: 4 });
+        top.with_fade_target(background)
+            .draw(&mut expected)
+            .unwrap();
+        let bottom = FadedRectangle::new(negative, Rgb888::RED, Fading::Bottom { steps: 4 });
+        bottom
+            .with_fade_target(background)
+            .draw(&mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+}
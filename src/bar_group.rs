@@ -0,0 +1,228 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::{
+    color::FadeColor,
+    faded_rectangle::{union, FadedRectangle},
+};
+
+/// A row of `N` bars that are updated together, e.g. the bands of a spectrum analyzer.
+///
+/// An update clears the vacated parts of all bars before any bar is redrawn. Adjacent areas
+/// cleared with the same color are merged, so edges shared by neighbouring bars are written
+/// once per frame.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct BarGroup<C, const N: usize> {
+    pub bars: [FadedRectangle<C>; N],
+}
+
+impl<C, const N: usize> BarGroup<C, N>
+where
+    C: FadeColor,
+{
+    /// Creates `N` copies of `first` placed `spacing` pixels apart.
+    ///
+    /// Bars that are resized horizontally, see [`FadedRectangle::resized`], are stacked below
+    /// each other, the others are placed next to each other to the right.
+    pub fn new(first: FadedRectangle<C>, spacing: u32) -> Self {
+        let (horizontal, _) = first.fading.resize_anchor();
+        let size = first.rect.size;
+        let step = if horizontal {
+            Point::new(0, (size.height + spacing) as i32)
+        } else {
+            Point::new((size.width + spacing) as i32, 0)
+        };
+
+        let mut bar = first;
+        let bars = [(); N].map(|_| {
+            let current = bar;
+            bar.rect.top_left += step;
+            current
+        });
+        Self { bars }
+    }
+
+    /// Creates a group from individually configured bars.
+    pub fn from_bars(bars: [FadedRectangle<C>; N]) -> Self {
+        Self { bars }
+    }
+
+    /// Resizes the bars to `lengths` like [`FadedRectangle::resized`] and redraws the
+    /// difference. Bars without a length keep their size.
+    ///
+    /// Returns the bounding box of all written pixels, `None` if nothing changed.
+    pub fn update<D>(
+        &mut self,
+        target: &mut D,
+        lengths: &[u32],
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let previous = self.bars;
+        for (bar, length) in self.bars.iter_mut().zip(lengths) {
+            *bar = bar.resized(*length);
+        }
+
+        let mut dirty = None;
+        let mut pending: Option<(Rectangle, C)> = None;
+        for (bar, old) in self.bars.iter().zip(&previous) {
+            if bar == old {
+                continue;
+            }
+
+            for vacated in bar.vacated(old) {
+                if vacated.is_zero_sized() {
+                    continue;
+                }
+                dirty = union(dirty, &vacated);
+
+                let color = bar.style.target;
+                pending = match pending {
+                    Some((area, pending_color)) => match merged(&area, &vacated) {
+                        Some(area) if pending_color == color => Some((area, color)),
+                        _ => {
+                            target.fill_solid(&area, pending_color)?;
+                            Some((vacated, color))
+                        }
+                    },
+                    None => Some((vacated, color)),
+                };
+            }
+        }
+        if let Some((area, color)) = pending {
+            target.fill_solid(&area, color)?;
+        }
+
+        for (bar, old) in self.bars.iter().zip(&previous) {
+            let color = bar.style.target;
+            let mut clear = |target: &mut D, area: &Rectangle| target.fill_solid(area, color);
+            let redrawn = bar.redraw_changed(target, old, &mut clear)?;
+            dirty = redrawn.iter().fold(dirty, union);
+        }
+
+        Ok(dirty)
+    }
+}
+
+// The union of `a` and `b` if it is a rectangle, i.e. they line up and touch or overlap.
+fn merged(a: &Rectangle, b: &Rectangle) -> Option<Rectangle> {
+    let area = |rect: &Rectangle| rect.size.width as u64 * rect.size.height as u64;
+    let union = union(Some(*a), b)?;
+    let covered = area(a) + area(b) - area(&a.intersection(b));
+
+    (area(&union) == covered).then_some(union)
+}
+
+impl<C, const N: usize> Dimensions for BarGroup<C, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bars
+            .iter()
+            .fold(None, |bounds, bar| union(bounds, &bar.rect))
+            .unwrap_or(Rectangle::zero())
+    }
+}
+
+impl<C, const N: usize> Drawable for BarGroup<C, N>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.bars.iter().try_for_each(|bar| bar.draw(target))
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use crate::faded_rectangle::Fading;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888};
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    #[test]
+    fn test_bar_group_layout() {
+        let first = Rectangle::new(Point::new(2, 10), Size::new(4, 30));
+        let group = BarGroup::<Rgb888, 3>::new(
+            FadedRectangle::new(first, Rgb888::WHITE, Fading::Top { steps: 4 }),
+            1,
+        );
+        let x = group.bars.map(|bar| bar.rect.top_left.x);
+        assert_eq!(x, [2, 7, 12]);
+
+        let first = Rectangle::new(Point::new(2, 10), Size::new(30, 4));
+        let group = BarGroup::<Rgb888, 3>::new(
+            FadedRectangle::new(first, Rgb888::WHITE, Fading::Right { steps: 4 }),
+            2,
+        );
+        let y = group.bars.map(|bar| bar.rect.top_left.y);
+        assert_eq!(y, [10, 16, 22]);
+        assert_eq!(
+            group.bounding_box(),
+            Rectangle::new(Point::new(2, 10), Size::new(30, 16))
+        );
+    }
+
+    #[test]
+    fn test_bar_group_merges_clears() {
+        // Every pixel is written at most once, even where neighbouring bars shrink together.
+        let first = Rectangle::new(Point::new(0, 10), Size::new(8, 40));
+        let mut group = BarGroup::<Rgb888, 4>::new(
+            FadedRectangle::new(first, Rgb888::GREEN, Fading::Top { steps: 3 }),
+            0,
+        );
+
+        let mut display = MockDisplay::new();
+        let dirty = group.update(&mut display, &[20, 20, 20, 30]).unwrap();
+        assert_eq!(
+            dirty,
+            Some(Rectangle::new(Point::new(0, 10), Size::new(32, 23)))
+        );
+    }
+
+    #[test]
+    fn test_bar_group_spectrum() {
+        let size = Size::new(160, 100);
+        let background = Rgb888::CSS_DARK_SLATE_GRAY;
+        let first = FadedRectangle::new(
+            Rectangle::new(Point::new(0, 10), Size::new(9, 80)),
+            Rgb888::CSS_SPRING_GREEN,
+            Fading::Top { steps: 12 },
+        )
+        .with_fade_target(background)
+        .with_corner_radius(2);
+        let mut group = BarGroup::<Rgb888, 16>::new(first, 1);
+
+        let mut display = SimulatorDisplay::<Rgb888>::new(size);
+        display.clear(background).unwrap();
+        group.draw(&mut display).unwrap();
+
+        let mut seed = 0x2545_f491_u32;
+        for frame in 0..40 {
+            let mut levels = [0; 16];
+            for level in &mut levels {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                *level = seed % 81;
+            }
+            group.update(&mut display, &levels).unwrap();
+
+            let mut expected = SimulatorDisplay::<Rgb888>::new(size);
+            expected.clear(background).unwrap();
+            group.draw(&mut expected).unwrap();
+            for point in Rectangle::new(Point::zero(), size).points() {
+                assert_eq!(
+                    display.get_pixel(point),
+                    expected.get_pixel(point),
+                    "{frame} {point:?}"
+                );
+            }
+        }
+    }
+}
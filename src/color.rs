@@ -0,0 +1,333 @@
+use embedded_graphics::pixelcolor::{
+    Bgr555, Bgr565, Bgr666, Bgr888, BinaryColor, Gray2, Gray4, Gray8, GrayColor, PixelColor,
+    Rgb555, Rgb565, Rgb666, Rgb888, RgbColor,
+};
+
+/// Colors that can be blended towards another color by a fade factor.
+///
+/// `factor_256` ranges from `0` (returns `self`) to `256` (returns `target`).
+pub trait FadeColor: PixelColor {
+    /// The color fades go towards unless configured otherwise, black or off.
+    const DEFAULT_TARGET: Self;
+
+    fn fade_towards(self, target: Self, factor_256: u16) -> Self;
+
+    /// Fades only the HSV value towards the value of `target`, keeping hue and saturation.
+    ///
+    /// Colors without a hue fall back to [`FadeColor::fade_towards`].
+    fn fade_value_towards(self, target: Self, factor_256: u16) -> Self {
+        self.fade_towards(target, factor_256)
+    }
+
+    /// Like [`FadeColor::fade_towards`], but adds `threshold` (in 1/256 of a quantization
+    /// level) before truncating each channel, see [`bayer_threshold`].
+    fn fade_towards_dithered(self, target: Self, factor_256: u16, threshold: u8) -> Self {
+        let _ = threshold;
+        self.fade_towards(target, factor_256)
+    }
+}
+
+/// How a fade interpolates between the base color and the fade target.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Interpolation {
+    /// Interpolates every RGB channel independently.
+    #[default]
+    Rgb,
+    /// Interpolates the HSV value only, so the hue doesn't drift.
+    HsvValue,
+}
+
+/// A color in integer HSV space.
+///
+/// `hue` ranges over `0..HUE_MAX` with [`HUE_SECTOR`] steps between the primary and secondary
+/// colors (red at `0`, yellow at `256`, green at `512`, ...).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Hsv {
+    pub hue: u16,
+    pub saturation: u8,
+    pub value: u8,
+}
+
+pub const HUE_SECTOR: u16 = 256;
+pub const HUE_MAX: u16 = 6 * HUE_SECTOR;
+
+pub fn rgb_to_hsv(r: u8, g: u8, b: u8) -> Hsv {
+    let max = r.max(g).max(b) as i32;
+    let min = r.min(g).min(b) as i32;
+    let delta = max - min;
+
+    if delta == 0 {
+        return Hsv {
+            hue: 0,
+            saturation: 0,
+            value: max as u8,
+        };
+    }
+
+    let (r, g, b) = (r as i32, g as i32, b as i32);
+    let sector = HUE_SECTOR as i32;
+    let (start, numerator) = if max == r {
+        (0, g - b)
+    } else if max == g {
+        (2 * sector, b - r)
+    } else {
+        (4 * sector, r - g)
+    };
+
+    // Rounds half up, also for negative numerators.
+    let hue = start + (2 * numerator * sector + delta).div_euclid(2 * delta);
+    let hue = hue.rem_euclid(HUE_MAX as i32);
+
+    Hsv {
+        hue: hue as u16,
+        saturation: ((delta * 255 + max / 2) / max) as u8,
+        value: max as u8,
+    }
+}
+
+pub fn hsv_to_rgb(hsv: Hsv) -> (u8, u8, u8) {
+    let v = hsv.value as u32;
+    let s = hsv.saturation as u32;
+    if s == 0 {
+        return (hsv.value, hsv.value, hsv.value);
+    }
+
+    let hue = (hsv.hue % HUE_MAX) as u32;
+    let sector = HUE_SECTOR as u32;
+    let region = hue / sector;
+    let remainder = hue % sector;
+
+    let scale = 255 * sector;
+    let p = (v * (255 - s) + 127) / 255;
+    let q = (v * (scale - s * remainder) + scale / 2) / scale;
+    let t = (v * (scale - s * (sector - remainder)) + scale / 2) / scale;
+
+    let (r, g, b) = match region {
+        0 => (v, t, p),
+        1 => (q, v, p),
+        2 => (p, v, t),
+        3 => (p, q, v),
+        4 => (t, p, v),
+        _ => (v, p, q),
+    };
+
+    (r as u8, g as u8, b as u8)
+}
+
+pub(crate) fn fade_channel(from: u8, to: u8, factor_256: u16) -> u8 {
+    fade_channel_dithered(from, to, factor_256, 0)
+}
+
+fn fade_channel_dithered(from: u8, to: u8, factor_256: u16, threshold: u8) -> u8 {
+    let factor = factor_256.min(256) as u32;
+    ((from as u32 * (256 - factor) + to as u32 * factor + threshold as u32) / 256) as u8
+}
+
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Ordered dither threshold for a display position, ranging over `8..=248`.
+///
+/// Only depends on the position, so redrawing a pixel always yields the same color.
+pub fn bayer_threshold(x: i32, y: i32) -> u8 {
+    BAYER_4X4[y.rem_euclid(4) as usize][x.rem_euclid(4) as usize] * 16 + 8
+}
+
+// Rescales a channel between `0..=from_max` and `0..=to_max`, rounding to nearest.
+fn rescale(value: u8, from_max: u8, to_max: u8) -> u8 {
+    ((value as u32 * to_max as u32 + from_max as u32 / 2) / from_max as u32) as u8
+}
+
+macro_rules! impl_fade_color_rgb {
+    ($($color:ident),*) => {
+        $(
+            impl FadeColor for $color {
+                const DEFAULT_TARGET: Self = $color::BLACK;
+
+                fn fade_towards(self, target: Self, factor_256: u16) -> Self {
+                    $color::new(
+                        fade_channel(self.r(), target.r(), factor_256),
+                        fade_channel(self.g(), target.g(), factor_256),
+                        fade_channel(self.b(), target.b(), factor_256),
+                    )
+                }
+
+                fn fade_towards_dithered(self, target: Self, factor_256: u16, threshold: u8) -> Self {
+                    $color::new(
+                        fade_channel_dithered(self.r(), target.r(), factor_256, threshold),
+                        fade_channel_dithered(self.g(), target.g(), factor_256, threshold),
+                        fade_channel_dithered(self.b(), target.b(), factor_256, threshold),
+                    )
+                }
+
+                fn fade_value_towards(self, target: Self, factor_256: u16) -> Self {
+                    let to_hsv = |color: Self| {
+                        rgb_to_hsv(
+                            rescale(color.r(), Self::MAX_R, 255),
+                            rescale(color.g(), Self::MAX_G, 255),
+                            rescale(color.b(), Self::MAX_B, 255),
+                        )
+                    };
+
+                    let mut hsv = to_hsv(self);
+                    hsv.value = fade_channel(hsv.value, to_hsv(target).value, factor_256);
+                    let (r, g, b) = hsv_to_rgb(hsv);
+
+                    $color::new(
+                        rescale(r, 255, Self::MAX_R),
+                        rescale(g, 255, Self::MAX_G),
+                        rescale(b, 255, Self::MAX_B),
+                    )
+                }
+            }
+        )*
+    };
+}
+
+impl_fade_color_rgb!(Rgb555, Bgr555, Rgb565, Bgr565, Rgb666, Bgr666, Rgb888, Bgr888);
+
+macro_rules! impl_fade_color_gray {
+    ($($color:ident),*) => {
+        $(
+            /// Luma is rounded to the nearest level, so the fade doesn't darken early on low bit
+            /// depths.
+            impl FadeColor for $color {
+                const DEFAULT_TARGET: Self = $color::BLACK;
+
+                fn fade_towards(self, target: Self, factor_256: u16) -> Self {
+                    self.fade_towards_dithered(target, factor_256, 128)
+                }
+
+                fn fade_towards_dithered(self, target: Self, factor_256: u16, threshold: u8) -> Self {
+                    $color::new(fade_channel_dithered(
+                        self.luma(),
+                        target.luma(),
+                        factor_256,
+                        threshold,
+                    ))
+                }
+            }
+        )*
+    };
+}
+
+impl_fade_color_gray!(Gray2, Gray4, Gray8);
+
+/// Without dithering the color switches to `target` halfway through the fade.
+impl FadeColor for BinaryColor {
+    const DEFAULT_TARGET: Self = BinaryColor::Off;
+
+    fn fade_towards(self, target: Self, factor_256: u16) -> Self {
+        self.fade_towards_dithered(target, factor_256, 128)
+    }
+
+    fn fade_towards_dithered(self, target: Self, factor_256: u16, threshold: u8) -> Self {
+        let on = fade_channel_dithered(
+            self.is_on() as u8,
+            target.is_on() as u8,
+            factor_256,
+            threshold,
+        );
+        BinaryColor::from(on != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsv_round_trip() {
+        for r in (0..=255u8).step_by(3) {
+            for g in 0..=255u8 {
+                for b in (0..=255u8).step_by(5) {
+                    let (r2, g2, b2) = hsv_to_rgb(rgb_to_hsv(r, g, b));
+
+                    assert!(
+                        r.abs_diff(r2) <= 1 && g.abs_diff(g2) <= 1 && b.abs_diff(b2) <= 1,
+                        "({r}, {g}, {b}) -> ({r2}, {g2}, {b2})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hsv_primaries() {
+        let hsv = |hue, saturation, value| Hsv {
+            hue,
+            saturation,
+            value,
+        };
+
+        assert_eq!(rgb_to_hsv(255, 0, 0), hsv(0, 255, 255));
+        assert_eq!(rgb_to_hsv(255, 255, 0), hsv(256, 255, 255));
+        assert_eq!(rgb_to_hsv(0, 255, 0), hsv(512, 255, 255));
+        assert_eq!(rgb_to_hsv(0, 0, 255), hsv(1024, 255, 255));
+        assert_eq!(rgb_to_hsv(255, 0, 255), hsv(1280, 255, 255));
+        assert_eq!(rgb_to_hsv(128, 128, 128), hsv(0, 0, 128));
+        assert_eq!(hsv_to_rgb(hsv(768, 255, 200)), (0, 200, 200));
+    }
+
+    #[test]
+    fn fade_value_keeps_hue() {
+        let orange = Rgb888::new(255, 140, 0);
+        let hue = rgb_to_hsv(255, 140, 0).hue;
+
+        for factor in (0..=224).step_by(16) {
+            let faded = orange.fade_value_towards(Rgb888::BLACK, factor);
+            let faded_hue = rgb_to_hsv(faded.r(), faded.g(), faded.b()).hue;
+
+            assert!(hue.abs_diff(faded_hue) <= 8, "factor {factor}: {faded:?}");
+        }
+        assert_eq!(orange.fade_value_towards(Rgb888::BLACK, 256), Rgb888::BLACK);
+    }
+
+    #[test]
+    fn dither_keeps_end_points() {
+        let from = Rgb565::new(31, 10, 0);
+        let to = Rgb565::new(0, 40, 31);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let threshold = bayer_threshold(x, y);
+                assert_eq!(from.fade_towards_dithered(to, 0, threshold), from);
+                assert_eq!(from.fade_towards_dithered(to, 256, threshold), to);
+            }
+        }
+    }
+
+    #[test]
+    fn gray8_exact_values() {
+        let gray = Gray8::new(200);
+
+        assert_eq!(gray.fade_towards(Gray8::BLACK, 0), gray);
+        assert_eq!(gray.fade_towards(Gray8::BLACK, 64), Gray8::new(150));
+        assert_eq!(gray.fade_towards(Gray8::BLACK, 128), Gray8::new(100));
+        assert_eq!(gray.fade_towards(Gray8::WHITE, 128), Gray8::new(228));
+        assert_eq!(gray.fade_towards(Gray8::BLACK, 256), Gray8::BLACK);
+    }
+
+    #[test]
+    fn gray4_rounds() {
+        let white = Gray4::WHITE;
+
+        // Truncating would already give 14 at the first step.
+        assert_eq!(white.fade_towards(Gray4::BLACK, 8), Gray4::new(15));
+        assert_eq!(white.fade_towards(Gray4::BLACK, 9), Gray4::new(14));
+        assert_eq!(white.fade_towards(Gray4::BLACK, 128), Gray4::new(8));
+        assert_eq!(white.fade_towards(Gray4::BLACK, 247), Gray4::new(1));
+        assert_eq!(white.fade_towards(Gray4::BLACK, 248), Gray4::new(0));
+        assert_eq!(
+            Gray4::new(3).fade_towards(Gray4::new(4), 127),
+            Gray4::new(3)
+        );
+        assert_eq!(
+            Gray4::new(3).fade_towards(Gray4::new(4), 128),
+            Gray4::new(4)
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Circle, Line, PrimitiveStyle, Rectangle},
+};
+
+use crate::{color::FadeColor, faded_rectangle::union, needle::Needle, rotate::rotate_point_deg};
+
+/// A compass dial with a tick every 30° and a needle pointing north.
+///
+/// The top of the dial is the direction of travel, so the needle is turned by `-heading_deg`.
+/// The needle of `previous_heading` stays visible as a ghost, faded towards the background.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Compass {
+    pub center: Point,
+    pub radius: u32,
+    pub heading_deg: i32,
+    pub previous_heading: Option<i32>,
+    pub dial_color: Rgb888,
+    pub needle_color: Rgb888,
+    /// Used to erase needles in [`Compass::update`] and as the fade target of the ghost.
+    pub background: Rgb888,
+}
+
+impl Compass {
+    pub const fn new(center: Point, radius: u32) -> Self {
+        Self {
+            center,
+            radius,
+            heading_deg: 0,
+            previous_heading: None,
+            dial_color: Rgb888::WHITE,
+            needle_color: Rgb888::RED,
+            background: Rgb888::BLACK,
+        }
+    }
+
+    pub fn with_heading(mut self, heading_deg: i32) -> Self {
+        self.heading_deg = heading_deg;
+        self
+    }
+
+    pub fn with_colors(mut self, dial: Rgb888, needle: Rgb888, background: Rgb888) -> Self {
+        self.dial_color = dial;
+        self.needle_color = needle;
+        self.background = background;
+        self
+    }
+
+    fn tick_length(&self) -> u32 {
+        (self.radius / 6).max(2)
+    }
+
+    /// The inner and outer end of the tick at `degrees`, clockwise from the top.
+    pub fn tick(&self, degrees: i32) -> (Point, Point) {
+        let outer = self.radius.saturating_sub(2) as i32;
+        let inner = outer - self.tick_length() as i32;
+        let end = |distance: i32| {
+            rotate_point_deg(self.center - Point::new(0, distance), degrees, self.center)
+        };
+        (end(inner), end(outer))
+    }
+
+    fn needle(&self, heading_deg: i32, color: Rgb888) -> Needle {
+        // Keeps a gap of a few pixels to the ticks, so erasing a needle doesn't touch the dial.
+        let length = self.radius.saturating_sub(self.tick_length() + 5);
+        Needle::new(self.center, length, 3, -heading_deg.rem_euclid(360), color)
+    }
+
+    fn ghost_color(&self) -> Rgb888 {
+        self.needle_color.fade_towards(self.background, 160)
+    }
+
+    // Draws the ghost and the current needle, a ghost of the current heading is skipped.
+    fn draw_needles<D>(&self, target: &mut D) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = Rgb888>,
+    {
+        let mut dirty = None;
+        if let Some(previous) = self.previous_heading.filter(|&p| p != self.heading_deg) {
+            let ghost = self.needle(previous, self.ghost_color());
+            ghost.draw(target)?;
+            dirty = union(dirty, &ghost.bounding_box());
+        }
+        let needle = self.needle(self.heading_deg, self.needle_color);
+        needle.draw(target)?;
+        Ok(union(dirty, &needle.bounding_box()))
+    }
+
+    /// Turns the compass to `heading_deg`, keeping the current heading as the ghost, and
+    /// redraws only the needles over an earlier drawing of this compass.
+    ///
+    /// Returns the bounding box of all written pixels, `None` if nothing changed.
+    pub fn update<D>(
+        &mut self,
+        target: &mut D,
+        heading_deg: i32,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = Rgb888>,
+    {
+        let next = Self {
+            heading_deg,
+            previous_heading: Some(self.heading_deg),
+            ..*self
+        };
+        if next == *self {
+            return Ok(None);
+        }
+
+        let mut dirty = None;
+        for heading in self.previous_heading.into_iter().chain([self.heading_deg]) {
+            let erased = self.needle(heading, self.background);
+            erased.draw(target)?;
+            dirty = union(dirty, &erased.bounding_box());
+        }
+
+        *self = next;
+        let drawn = self.draw_needles(target)?;
+        Ok(drawn.iter().fold(dirty, union))
+    }
+}
+
+impl Dimensions for Compass {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::with_center(self.center, Size::new_equal(self.radius * 2 + 1))
+    }
+}
+
+impl Drawable for Compass {
+    type Color = Rgb888;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let dial = PrimitiveStyle::with_stroke(self.dial_color, 1);
+        Circle::with_center(self.center, self.radius * 2 + 1)
+            .into_styled(dial)
+            .draw(target)?;
+        for degrees in (0..360).step_by(30) {
+            let (inner, outer) = self.tick(degrees);
+            Line::new(inner, outer).into_styled(dial).draw(target)?;
+        }
+
+        self.draw_needles(target)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    #[test]
+    fn test_compass_ticks() {
+        let compass = Compass::new(Point::new(32, 32), 28);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        compass.draw(&mut display).unwrap();
+
+        // Outer ends of the ticks, 26 pixels from the center.
+        let expected = [
+            (0, (32, 6)),
+            (30, (45, 9)),
+            (90, (58, 32)),
+            (180, (32, 58)),
+            (270, (6, 32)),
+            (300, (9, 19)),
+        ];
+        for (degrees, (x, y)) in expected {
+            let outer = Point::new(x, y);
+            assert_eq!(compass.tick(degrees).1, outer, "{degrees}");
+            assert_eq!(display.get_pixel(outer), Some(Rgb888::WHITE), "{degrees}");
+        }
+        assert_eq!(compass.tick(90).0, Point::new(54, 32));
+        // No tick between the marks.
+        assert_eq!(display.get_pixel(Point::new(52, 20)), None);
+    }
+
+    #[test]
+    fn test_compass_update() {
+        let size = Size::new(80, 80);
+        let background = Rgb888::CSS_MIDNIGHT_BLUE;
+        let mut compass = Compass::new(Point::new(40, 40), 36).with_colors(
+            Rgb888::CSS_LIGHT_GRAY,
+            Rgb888::CSS_TOMATO,
+            background,
+        );
+
+        let mut display = SimulatorDisplay::<Rgb888>::new(size);
+        display.clear(background).unwrap();
+        compass.draw(&mut display).unwrap();
+
+        for heading in [10, 95, 95, 200, -45, 359] {
+            let dirty = compass.update(&mut display, heading).unwrap();
+            assert!(dirty.is_some(), "{heading}");
+
+            let mut expected = SimulatorDisplay::<Rgb888>::new(size);
+            expected.clear(background).unwrap();
+            compass.draw(&mut expected).unwrap();
+            for point in Rectangle::new(Point::zero(), size).points() {
+                assert_eq!(
+                    display.get_pixel(point),
+                    expected.get_pixel(point),
+                    "{heading} {point:?}"
+                );
+            }
+        }
+        assert_eq!(compass.previous_heading, Some(-45));
+
+        // The first repeat hides the ghost, the second one has nothing left to do.
+        assert!(compass.update(&mut display, 359).unwrap().is_some());
+        assert_eq!(compass.update(&mut display, 359).unwrap(), None);
+
+        // Extreme headings don't overflow when the needle is turned against them.
+        compass.update(&mut display, i32::MIN).unwrap();
+        compass.update(&mut display, i32::MAX).unwrap();
+        assert_eq!(compass.heading_deg, i32::MAX);
+    }
+}
@@ -0,0 +1,259 @@
+/// Shapes how the fade factor progresses across the fade steps.
+///
+/// The built-in curves map a factor of `0` to `0` and `256` to `256`, so the outermost step
+/// always reaches the fade target. A [`FadeCurve::Profile`] follows its points, which don't have
+/// to start at the fade target or end at the base color.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum FadeCurve {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    Sine,
+    Profile(FadeProfile),
+}
+
+/// A piecewise linear fade made of up to [`FadeProfile::MAX_POINTS`] control points.
+///
+/// Each point is a `(position, brightness)` pair. Positions run across the fade band from `0`
+/// at the faded edge to `255` at the solid region; brightness runs from `0` (the fade target)
+/// to `255` (the base color). Positions before the first or after the last point hold that
+/// point's brightness.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "ProfilePoints", into = "ProfilePoints")
+)]
+pub struct FadeProfile {
+    points: [(u8, u8); FadeProfile::MAX_POINTS],
+    len: u8,
+}
+
+impl FadeProfile {
+    pub const MAX_POINTS: usize = 6;
+
+    /// Creates a profile from 2 to [`FadeProfile::MAX_POINTS`] points with strictly increasing
+    /// positions, returns `None` otherwise.
+    pub fn new(points: &[(u8, u8)]) -> Option<Self> {
+        if points.len() < 2 || points.len() > Self::MAX_POINTS {
+            return None;
+        }
+        if points.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return None;
+        }
+
+        let mut profile = Self {
+            points: [(0, 0); Self::MAX_POINTS],
+            len: points.len() as u8,
+        };
+        profile.points[..points.len()].copy_from_slice(points);
+        Some(profile)
+    }
+
+    /// The two-point profile equivalent to [`FadeCurve::Linear`].
+    pub const fn linear() -> Self {
+        let mut points = [(0, 0); Self::MAX_POINTS];
+        points[1] = (255, 255);
+        Self { points, len: 2 }
+    }
+
+    pub fn points(&self) -> &[(u8, u8)] {
+        &self.points[..self.len as usize]
+    }
+
+    pub fn apply(&self, factor_256: u16) -> u16 {
+        // Scales 0..=255 to 0..=256 so the end points stay exact.
+        let widen = |value: u8| (value as i32 * 256 + 127) / 255;
+
+        let position = 256 - factor_256.min(256) as i32;
+        let points = self.points();
+
+        let (first, last) = (points[0], points[points.len() - 1]);
+        let brightness = if position <= widen(first.0) {
+            widen(first.1)
+        } else if position >= widen(last.0) {
+            widen(last.1)
+        } else {
+            let segment = points
+                .windows(2)
+                .find(|pair| position <= widen(pair[1].0))
+                .unwrap_or(&points[points.len() - 2..]);
+            let (x0, y0) = (widen(segment[0].0), widen(segment[0].1));
+            let (x1, y1) = (widen(segment[1].0), widen(segment[1].1));
+
+            y0 + (y1 - y0) * (position - x0) / (x1 - x0)
+        };
+
+        (256 - brightness) as u16
+    }
+}
+
+// Deserialized profiles are validated by `FadeProfile::new`.
+#[cfg(feature = "serde")]
+#[derive(::serde::Serialize, ::serde::Deserialize)]
+struct ProfilePoints {
+    points: [(u8, u8); FadeProfile::MAX_POINTS],
+    len: u8,
+}
+
+#[cfg(feature = "serde")]
+impl From<FadeProfile> for ProfilePoints {
+    fn from(profile: FadeProfile) -> Self {
+        Self {
+            points: profile.points,
+            len: profile.len,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ProfilePoints> for FadeProfile {
+    type Error = &'static str;
+
+    fn try_from(profile: ProfilePoints) -> Result<Self, Self::Error> {
+        profile
+            .points
+            .get(..profile.len as usize)
+            .and_then(FadeProfile::new)
+            .ok_or("invalid fade profile")
+    }
+}
+
+impl Default for FadeProfile {
+    fn default() -> Self {
+        Self::linear()
+    }
+}
+
+// 256 * (1 - cos(pi * i / 16)) / 2
+const SINE_TABLE: [u32; 17] = [
+    0, 2, 10, 22, 37, 57, 79, 103, 128, 153, 177, 199, 219, 234, 246, 254, 256,
+];
+
+impl FadeCurve {
+    pub fn apply(self, factor_256: u16) -> u16 {
+        let t = factor_256.min(256) as u32;
+
+        let eased = match self {
+            FadeCurve::Linear => t,
+            FadeCurve::EaseIn => t * t / 256,
+            FadeCurve::EaseOut => 256 - (256 - t) * (256 - t) / 256,
+            FadeCurve::EaseInOut => t * t * (768 - 2 * t) / 65536,
+            FadeCurve::Sine => {
+                let index = (t / 16) as usize;
+                let remainder = t % 16;
+                if remainder == 0 {
+                    SINE_TABLE[index]
+                } else {
+                    let (low, high) = (SINE_TABLE[index], SINE_TABLE[index + 1]);
+                    low + (high - low) * remainder / 16
+                }
+            }
+            FadeCurve::Profile(profile) => profile.apply(factor_256) as u32,
+        };
+
+        eased as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVES: [FadeCurve; 5] = [
+        FadeCurve::Linear,
+        FadeCurve::EaseIn,
+        FadeCurve::EaseOut,
+        FadeCurve::EaseInOut,
+        FadeCurve::Sine,
+    ];
+
+    #[test]
+    fn curves_are_monotonic() {
+        for curve in CURVES {
+            for steps in 1..=32u32 {
+                let factors: Vec<u16> = (0..=steps)
+                    .map(|step| curve.apply((step * 256 / steps) as u16))
+                    .collect();
+
+                assert!(
+                    factors.windows(2).all(|pair| pair[0] <= pair[1]),
+                    "{curve:?} with {steps} steps: {factors:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn curves_keep_extremes() {
+        for curve in CURVES {
+            assert_eq!(curve.apply(0), 0, "{curve:?}");
+            assert_eq!(curve.apply(256), 256, "{curve:?}");
+        }
+    }
+
+    #[test]
+    fn linear_is_identity() {
+        for factor in 0..=256 {
+            assert_eq!(FadeCurve::Linear.apply(factor), factor);
+        }
+    }
+
+    #[test]
+    fn eased_curves_bend_in_expected_direction() {
+        assert!(FadeCurve::EaseIn.apply(64) < 64);
+        assert!(FadeCurve::EaseOut.apply(64) > 64);
+        assert!(FadeCurve::EaseInOut.apply(64) < 64);
+        assert!(FadeCurve::EaseInOut.apply(192) > 192);
+        assert_eq!(FadeCurve::Sine.apply(128), 128);
+    }
+
+    #[test]
+    fn linear_profile_matches_linear_curve() {
+        let profile = FadeCurve::Profile(FadeProfile::linear());
+        for factor in 0..=256 {
+            assert_eq!(profile.apply(factor), factor);
+        }
+    }
+
+    #[test]
+    fn profile_interpolates_between_points() {
+        // Drops to half brightness over the outer quarter, holds, then rises to the base color.
+        let profile = FadeProfile::new(&[(0, 0), (64, 128), (191, 128), (255, 255)]).unwrap();
+
+        // At the control points, factors are `256 - position`.
+        assert_eq!(profile.apply(256), 256);
+        assert_eq!(profile.apply(256 - 64), 127);
+        assert_eq!(profile.apply(256 - 192), 127);
+        assert_eq!(profile.apply(0), 0);
+
+        // Between control points.
+        assert_eq!(profile.apply(256 - 32), 192);
+        assert_eq!(profile.apply(128), 127);
+        assert_eq!(profile.apply(32), 64);
+    }
+
+    #[test]
+    fn profile_holds_outside_points() {
+        let profile = FadeProfile::new(&[(64, 64), (128, 192)]).unwrap();
+
+        assert_eq!(profile.apply(256), profile.apply(200));
+        assert_eq!(profile.apply(256), 256 - 64);
+        assert_eq!(profile.apply(0), profile.apply(64));
+        assert_eq!(profile.apply(0), 256 - 193);
+    }
+
+    #[test]
+    fn profile_rejects_invalid_points() {
+        assert_eq!(FadeProfile::new(&[(0, 0)]), None);
+        assert_eq!(FadeProfile::new(&[(10, 0), (10, 255)]), None);
+        assert_eq!(FadeProfile::new(&[(20, 0), (10, 255)]), None);
+        assert_eq!(FadeProfile::new(&[(0, 0); 7]), None);
+        assert!(FadeProfile::new(&[(0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (5, 5)]).is_some());
+    }
+}
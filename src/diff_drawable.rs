@@ -0,0 +1,91 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// A drawable that can redraw only what changed since an earlier state of itself.
+pub trait DiffDrawable: Drawable {
+    /// What needs to be remembered about a drawn value to diff against it.
+    type State;
+
+    /// Returns the state to pass to a later [`DiffDrawable::draw_diff`].
+    fn state(&self) -> Self::State;
+
+    /// Redraws the difference to the value drawn with `previous`. Returns the bounding box of
+    /// all written pixels, `None` if nothing changed.
+    fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Self::State,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>;
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use crate::{
+        faded_rectangle::{FadedRectangle, Fading},
+        faded_rounded_rectangle::FadedRoundedRectangle,
+    };
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::Rgb888, primitives::RoundedRectangle,
+    };
+
+    // Draws `frames` one after another and returns how many of them changed any pixels.
+    fn animate<T, D>(target: &mut D, frames: &[T]) -> Result<usize, D::Error>
+    where
+        T: DiffDrawable,
+        D: DrawTarget<Color = T::Color>,
+    {
+        let Some((first, rest)) = frames.split_first() else {
+            return Ok(0);
+        };
+        first.draw(target)?;
+
+        let mut previous = first.state();
+        let mut changed = 1;
+        for frame in rest {
+            if frame.draw_diff(target, &previous)?.is_some() {
+                changed += 1;
+            }
+            previous = frame.state();
+        }
+        Ok(changed)
+    }
+
+    #[test]
+    fn test_diff_drawable_generic() {
+        let background = Rgb888::BLACK;
+        let bars = [10, 30, 4, 40].map(|x| {
+            let rect = Rectangle::new(Point::new(x, 2), Size::new(20, 12));
+            FadedRectangle::new(rect, Rgb888::CSS_CORAL, Fading::Left { steps: 5 })
+        });
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        assert_eq!(animate(&mut display, &bars).unwrap(), 4);
+
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        expected
+            .fill_solid(
+                &Rectangle::new(Point::new(4, 2), Size::new(56, 12)),
+                background,
+            )
+            .unwrap();
+        bars[3].draw(&mut expected).unwrap();
+        display.assert_eq(&expected);
+
+        let rounded = [20, 2].map(|x| {
+            let rect = Rectangle::new(Point::new(x, 20), Size::new(30, 12));
+            FadedRoundedRectangle::new(
+                RoundedRectangle::with_equal_corners(rect, Size::new(3, 3)),
+                Rgb888::CSS_CORAL,
+                Fading::Left { steps: 5 },
+            )
+        });
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        assert_eq!(animate(&mut display, &rounded).unwrap(), 2);
+        assert_eq!(animate(&mut display, &[rounded[1]; 3]).unwrap(), 1);
+    }
+}
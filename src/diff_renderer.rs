@@ -0,0 +1,100 @@
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+
+use crate::{color::FadeColor, faded_rectangle::FadedRectangle};
+
+/// Redraws a [`FadedRectangle`] incrementally, remembering what was drawn last.
+///
+/// The first update draws the rectangle entirely, later updates only redraw the difference with
+/// [`FadedRectangle::draw_diff_full`].
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct DiffRenderer<C = Rgb888> {
+    previous: Option<FadedRectangle<C>>,
+}
+
+impl<C> DiffRenderer<C>
+where
+    C: FadeColor,
+{
+    pub fn new() -> Self {
+        Self { previous: None }
+    }
+
+    /// The rectangle drawn by the last update.
+    pub fn previous(&self) -> Option<&FadedRectangle<C>> {
+        self.previous.as_ref()
+    }
+
+    /// Draws `new` and returns the area that was written to, `None` if nothing changed.
+    pub fn update<D>(
+        &mut self,
+        target: &mut D,
+        new: FadedRectangle<C>,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let dirty = match &self.previous {
+            Some(previous) => new.draw_diff_full(target, previous)?,
+            None => {
+                new.draw(target)?;
+                (!new.rect.is_zero_sized()).then_some(new.rect)
+            }
+        };
+        self.previous = Some(new);
+
+        Ok(dirty)
+    }
+}
+
+impl<C> Default for DiffRenderer<C>
+where
+    C: FadeColor,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use crate::faded_rectangle::Fading;
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    #[test]
+    fn test_diff_renderer_animation() {
+        let size = Size::new(120, 40);
+        let background = Rgb888::CSS_DARK_SLATE_GRAY;
+        let bar = |frame: i32| {
+            let x = (frame * 37) % 90;
+            let width = 10 + (frame * 13 % 30) as u32;
+            let rect = Rectangle::new(Point::new(x, 6 + frame % 3), Size::new(width, 24));
+            FadedRectangle::new(rect, Rgb888::CSS_GOLD, Fading::Right { steps: 8 })
+                .with_fade_target(background)
+        };
+
+        let mut display = SimulatorDisplay::<Rgb888>::new(size);
+        display.clear(background).unwrap();
+
+        let mut renderer = DiffRenderer::new();
+        let first = renderer.update(&mut display, bar(0)).unwrap();
+        assert_eq!(first, Some(bar(0).rect));
+        for frame in 1..20 {
+            renderer.update(&mut display, bar(frame)).unwrap();
+        }
+        assert_eq!(renderer.update(&mut display, bar(19)).unwrap(), None);
+        assert_eq!(renderer.previous(), Some(&bar(19)));
+
+        let mut expected = SimulatorDisplay::<Rgb888>::new(size);
+        expected.clear(background).unwrap();
+        bar(19).draw(&mut expected).unwrap();
+        for point in Rectangle::new(Point::zero(), size).points() {
+            assert_eq!(
+                display.get_pixel(point),
+                expected.get_pixel(point),
+                "{point:?}"
+            );
+        }
+    }
+}
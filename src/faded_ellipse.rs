@@ -0,0 +1,159 @@
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Ellipse, Rectangle},
+};
+
+use crate::{
+    color::FadeColor,
+    faded_rectangle::{FadeShading, FadedRectangle, Fading},
+    style::FadeStyle,
+};
+
+/// An [`Ellipse`] with a faded edge.
+///
+/// The fade is laid out on the bounding box like for a [`FadedRectangle`] and masked by the
+/// ellipse, so the faded edge follows the curve of the outline.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct FadedEllipse<C = Rgb888> {
+    pub ellipse: Ellipse,
+    pub base_color: C,
+    pub fading: Fading,
+    pub style: FadeStyle<C>,
+}
+
+impl<C> FadedEllipse<C>
+where
+    C: FadeColor,
+{
+    pub fn new(ellipse: Ellipse, base_color: C, fading: Fading) -> Self {
+        Self {
+            ellipse,
+            base_color,
+            fading,
+            style: FadeStyle::new(),
+        }
+    }
+
+    pub fn with_style(mut self, style: FadeStyle<C>) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The fade on the bounding box, before masking.
+    pub fn faded(&self) -> FadedRectangle<C> {
+        FadedRectangle::new(self.ellipse.bounding_box(), self.base_color, self.fading)
+            .with_style(self.style)
+    }
+
+    /// Returns the color drawn at `point`, `None` outside of the ellipse.
+    pub fn color_at(&self, point: Point) -> Option<C> {
+        if !self.ellipse.contains(point) {
+            return None;
+        }
+
+        self.faded().color_at(point)
+    }
+}
+
+impl<C> Dimensions for FadedEllipse<C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.ellipse.bounding_box()
+    }
+}
+
+impl<C> Drawable for FadedEllipse<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let shading = FadeShading::new(&self.faded());
+        let top_left = self.ellipse.top_left;
+
+        let pixels = self
+            .ellipse
+            .points()
+            .map(|point| Pixel(point, shading.color_or_base(point - top_left)));
+        target.draw_iter(pixels)
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    #[test]
+    fn visual_test_faded_ellipse() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let horizon = Ellipse::new(Point::new(10, 60), Size::new(300, 120));
+        FadedEllipse::new(horizon, Rgb888::CSS_SKY_BLUE, Fading::Bottom { steps: 50 })
+            .draw(&mut display)
+            .unwrap();
+
+        let output_path = "visual_test_faded_ellipse.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_faded_ellipse_pixels() {
+        let ellipse = Ellipse::new(Point::new(1, 1), Size::new(7, 5));
+        let faded = FadedEllipse::new(ellipse, Rgb888::WHITE, Fading::Left { steps: 3 });
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        faded.draw(&mut display).unwrap();
+
+        // Columns fade in from the left edge of the bounding box.
+        let pattern = [
+            "        ", "  12WWW ", " K12WWWW", " K12WWWW", " K12WWWW", "  12WWW ",
+        ];
+        let mut expected = MockDisplay::<Rgb888>::new();
+        for (y, row) in pattern.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let color = match c {
+                    'K' => Rgb888::BLACK,
+                    '1' => Rgb888::new(85, 85, 85),
+                    '2' => Rgb888::new(170, 170, 170),
+                    'W' => Rgb888::WHITE,
+                    _ => continue,
+                };
+                expected.set_pixel(Point::new(x as i32, y as i32), Some(color));
+            }
+        }
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_degenerate_ellipses() {
+        let fading = Fading::Top { steps: 4 };
+
+        for size in [
+            Size::new(1, 6),
+            Size::new(6, 1),
+            Size::new(1, 1),
+            Size::zero(),
+        ] {
+            let ellipse = Ellipse::new(Point::new(2, 2), size);
+            let faded = FadedEllipse::new(ellipse, Rgb888::WHITE, fading);
+
+            let mut display = MockDisplay::<Rgb888>::new();
+            faded.draw(&mut display).unwrap();
+
+            for point in ellipse.points() {
+                assert_eq!(display.get_pixel(point), faded.color_at(point), "{size:?}");
+            }
+        }
+    }
+}
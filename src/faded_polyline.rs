@@ -0,0 +1,153 @@
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Line, Rectangle},
+};
+
+use crate::{color::FadeColor, style::FadeStyle};
+
+/// A one pixel wide polyline whose older segments fade towards the style's target, e.g. for
+/// motion trails.
+///
+/// The last segment is drawn in `color`, every segment before it gets darker by `1 / n` of the
+/// fade for `n` segments. Vertices shared by two segments are drawn once, in the color of the
+/// older segment. Fewer than two points draw nothing.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct FadedPolyline<'a, C = Rgb888> {
+    pub points: &'a [Point],
+    pub color: C,
+    pub style: FadeStyle<C>,
+}
+
+impl<'a, C> FadedPolyline<'a, C>
+where
+    C: FadeColor,
+{
+    pub fn new(points: &'a [Point], color: C) -> Self {
+        Self {
+            points,
+            color,
+            style: FadeStyle::new(),
+        }
+    }
+
+    pub fn with_style(mut self, style: FadeStyle<C>) -> Self {
+        self.style = style;
+        self
+    }
+
+    // Fade factor of segment `index`, the newest segment isn't faded.
+    fn segment_factor(&self, index: usize) -> u16 {
+        let segments = self.points.len() - 1;
+        ((segments - 1 - index) * 256 / segments) as u16
+    }
+}
+
+impl<C> Dimensions for FadedPolyline<'_, C> {
+    fn bounding_box(&self) -> Rectangle {
+        let Some((first, rest)) = self.points.split_first() else {
+            return Rectangle::zero();
+        };
+
+        let (min, max) = rest.iter().fold((*first, *first), |(min, max), point| {
+            (min.component_min(*point), max.component_max(*point))
+        });
+        Rectangle::with_corners(min, max)
+    }
+}
+
+impl<C> Drawable for FadedPolyline<'_, C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        for (index, segment) in self.points.windows(2).enumerate() {
+            let factor = self.segment_factor(index);
+            // Dithered colors depend on the position, others are blended once per segment.
+            let color = self.style.blend(self.color, factor, Point::zero());
+
+            let points = Line::new(segment[0], segment[1])
+                .points()
+                .skip(usize::from(index > 0));
+            let pixels = points.map(|point| {
+                let color = if self.style.dithered {
+                    self.style.blend(self.color, factor, point)
+                } else {
+                    color
+                };
+                Pixel(point, color)
+            });
+            target.draw_iter(pixels)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    #[test]
+    fn visual_test_faded_polyline() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let mut trail = [Point::zero(); 200];
+        for (i, point) in trail.iter_mut().enumerate() {
+            let i = i as i32;
+            *point = Point::new(10 + i * 3 / 2, 120 + (i % 40 - 20).abs() * 4 - 40);
+        }
+        FadedPolyline::new(&trail, Rgb888::CSS_YELLOW)
+            .draw(&mut display)
+            .unwrap();
+
+        let output_path = "visual_test_faded_polyline.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_polyline_segments_darken() {
+        let points = [
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 4),
+            Point::new(8, 4),
+        ];
+        let polyline = FadedPolyline::new(&points, Rgb888::WHITE);
+
+        // MockDisplay panics if a shared vertex is drawn twice.
+        let mut display = MockDisplay::new();
+        polyline.draw(&mut display).unwrap();
+
+        let gray = |value| Some(Rgb888::new(value, value, value));
+        assert_eq!(display.get_pixel(Point::new(0, 0)), gray(85));
+        assert_eq!(display.get_pixel(Point::new(4, 0)), gray(85));
+        assert_eq!(display.get_pixel(Point::new(4, 2)), gray(170));
+        assert_eq!(display.get_pixel(Point::new(4, 4)), gray(170));
+        assert_eq!(display.get_pixel(Point::new(8, 4)), gray(255));
+        assert_eq!(display.affected_area(), polyline.bounding_box());
+    }
+
+    #[test]
+    fn test_polyline_without_segments() {
+        for points in [&[][..], &[Point::new(3, 3)][..]] {
+            let polyline = FadedPolyline::new(points, Rgb888::WHITE);
+
+            let mut display = MockDisplay::new();
+            polyline.draw(&mut display).unwrap();
+            assert_eq!(display, MockDisplay::new());
+        }
+    }
+}
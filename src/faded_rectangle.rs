@@ -1,17 +1,97 @@
+use core::iter::FusedIterator;
+
 use embedded_graphics::{
-    pixelcolor::Rgb888,
+    pixelcolor::{BinaryColor, Rgb888},
     prelude::*,
-    primitives::{PrimitiveStyle, Rectangle},
+    primitives::{rectangle, PrimitiveStyleBuilder, Rectangle, StrokeAlignment},
     transform::Transform,
 };
 
+use crate::{
+    color::{FadeColor, Interpolation},
+    curve::FadeCurve,
+    diff_drawable::DiffDrawable,
+    rotate::{rotate_rect_in_place, rotate_rectangle, Rotation},
+    style::FadeStyle,
+};
+
+/// Where and how wide the fade is drawn. Zero steps behave like [`Fading::None`].
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 #[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum Fading {
-    Bottom { steps: u8 },
-    Top { steps: u8 },
-    Left { steps: u8 },
-    Right { steps: u8 },
+    /// No fade, the rectangle is drawn in its solid base color.
+    None,
+    Bottom {
+        steps: u16,
+    },
+    Top {
+        steps: u16,
+    },
+    Left {
+        steps: u16,
+    },
+    Right {
+        steps: u16,
+    },
+    Horizontal {
+        steps: u16,
+    },
+    Vertical {
+        steps: u16,
+    },
+    /// Fades all four edges. In the corners the nearest edge wins, i.e. the stronger fade.
+    AllEdges {
+        steps: u16,
+    },
+    /// Darkens with distance from the center, approximating circular rings scaled to the
+    /// rectangle's aspect ratio. Covers the whole rectangle.
+    Radial {
+        steps: u16,
+    },
+    /// Fades a triangular band towards `corner`, based on the distance `x + y` from it.
+    Diagonal {
+        steps: u16,
+        corner: Corner,
+    },
+    /// The `*Percent` variants fade like their fixed counterparts, with the steps given as a
+    /// percentage of the faded dimension (rounded half up).
+    LeftPercent {
+        percent: u8,
+    },
+    RightPercent {
+        percent: u8,
+    },
+    TopPercent {
+        percent: u8,
+    },
+    BottomPercent {
+        percent: u8,
+    },
+    /// Picks the steps from the rectangle size, see [`Fading::auto`].
+    Auto {
+        edge: Edge,
+    },
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
 }
 
 impl Default for Fading {
@@ -20,442 +100,4791 @@ impl Default for Fading {
     }
 }
 
-impl Fading {
-    fn steps(&self) -> u8 {
-        match self {
-            Fading::Bottom { steps } => *steps,
-            Fading::Top { steps } => *steps,
-            Fading::Left { steps } => *steps,
-            Fading::Right { steps } => *steps,
-        }
-    }
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum ResizeAnchor {
+    Start,
+    Center,
+    End,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
-#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
-pub struct FadedRectangle {
-    pub rect: Rectangle,
-    pub base_color: Rgb888,
-    pub fading: Fading,
+fn zone(x: u32, y: u32, width: u32, height: u32) -> Rectangle {
+    Rectangle::new(Point::new(x as i32, y as i32), Size::new(width, height))
 }
 
-impl FadedRectangle {
-    pub fn new(rect: Rectangle, base_color: Rgb888, fading: Fading) -> Self {
-        Self {
-            rect,
-            base_color,
-            fading,
-        }
+// Position of `edge` of `rect` along its axis, right and bottom edges are exclusive.
+fn edge_position(rect: &Rectangle, edge: Edge) -> i32 {
+    match edge {
+        Edge::Left => rect.top_left.x,
+        Edge::Right => rect.top_left.x + rect.size.width as i32,
+        Edge::Top => rect.top_left.y,
+        Edge::Bottom => rect.top_left.y + rect.size.height as i32,
     }
+}
 
-    // This currently just draws diff with respect to left sided shrinking/expanding
-    pub fn draw_diff<D>(&self, target: &mut D, previous: &Rectangle) -> Result<(), D::Error>
-    where
-        D: DrawTarget<Color = Rgb888>,
-    {
-        if self.rect == *previous {
-            return Ok(());
-        }
+// Bounding box of `dirty` and `area`, zero sized areas are ignored.
+pub(crate) fn union(dirty: Option<Rectangle>, area: &Rectangle) -> Option<Rectangle> {
+    let Some(area_end) = area.bottom_right() else {
+        return dirty;
+    };
+    let Some(dirty) = dirty else {
+        return Some(*area);
+    };
 
-        let x_start_old = previous.top_left.x;
-        let x_start_new = self.rect.top_left.x;
+    let end = dirty
+        .bottom_right()
+        .unwrap_or(area_end)
+        .component_max(area_end);
+    Some(Rectangle::with_corners(
+        dirty.top_left.component_min(area.top_left),
+        end,
+    ))
+}
 
-        let y_diff = previous.size.height;
+// Splits `outer` without `inner` into the strips above, below, left and right of `inner`,
+// which has to lie within `outer`.
+fn difference(outer: &Rectangle, inner: &Rectangle) -> [Rectangle; 4] {
+    if inner.is_zero_sized() {
+        return [
+            *outer,
+            Rectangle::zero(),
+            Rectangle::zero(),
+            Rectangle::zero(),
+        ];
+    }
 
-        if x_start_new > x_start_old {
-            // Left sided shrinking
-            let x_diff = x_start_new - x_start_old;
+    let position = |rect, edge| edge_position(rect, edge);
+    [
+        axis_strip(
+            outer,
+            Edge::Top,
+            position(outer, Edge::Top),
+            position(inner, Edge::Top),
+        ),
+        axis_strip(
+            outer,
+            Edge::Bottom,
+            position(inner, Edge::Bottom),
+            position(outer, Edge::Bottom),
+        ),
+        axis_strip(
+            inner,
+            Edge::Left,
+            position(outer, Edge::Left),
+            position(inner, Edge::Left),
+        ),
+        axis_strip(
+            inner,
+            Edge::Right,
+            position(inner, Edge::Right),
+            position(outer, Edge::Right),
+        ),
+    ]
+}
 
-            let rec_diff = Rectangle::new(
-                Point {
-                    x: x_start_old,
-                    y: 0,
-                },
-                Size {
-                    width: x_diff as u32,
-                    height: y_diff,
-                },
-            );
+// The part of `rect` from `start` to `end` along the axis of `edge`.
+fn axis_strip(rect: &Rectangle, edge: Edge, start: i32, end: i32) -> Rectangle {
+    let length = (end - start).max(0) as u32;
+    match edge {
+        Edge::Left | Edge::Right => Rectangle::new(
+            Point::new(start, rect.top_left.y),
+            Size::new(length, rect.size.height),
+        ),
+        Edge::Top | Edge::Bottom => Rectangle::new(
+            Point::new(rect.top_left.x, start),
+            Size::new(rect.size.width, length),
+        ),
+    }
+}
 
-            rec_diff
-                .into_styled(PrimitiveStyle::with_fill(Rgb888::BLACK))
-                .draw(target)?;
+fn percent_of(dimension: u32, percent: u8) -> u16 {
+    let steps = (dimension as u64 * percent.min(100) as u64 + 50) / 100;
+    steps.min(u16::MAX as u64) as u16
+}
 
-            target.draw_iter(self)?;
-        } else {
-            // Left sided expanding
-            let x_diff = x_start_old - x_start_new + self.fading.steps() as i32;
+impl Fading {
+    /// Fades `edge` over 20% of the rectangle's width or height, clamped to 2..=16 steps and
+    /// to the size of the rectangle itself.
+    pub const fn auto(edge: Edge) -> Self {
+        Fading::Auto { edge }
+    }
+
+    /// The fading of a rectangle after it is rotated clockwise by `rotation`, see
+    /// [`rotate_point`](crate::rotate::rotate_point). A left fade becomes a top fade under
+    /// [`Rotation::Rotate90`], the steps are kept.
+    pub const fn rotated(self, rotation: Rotation) -> Fading {
+        let mut fading = self;
+        let mut quadrants = rotation.quadrants();
+        while quadrants > 0 {
+            fading = fading.rotated_once();
+            quadrants -= 1;
+        }
+        fading
+    }
 
-            let rec_diff = Rectangle::new(
-                Point {
-                    x: x_start_new,
-                    y: 0,
+    const fn rotated_once(self) -> Fading {
+        match self {
+            Fading::Left { steps } => Fading::Top { steps },
+            Fading::Top { steps } => Fading::Right { steps },
+            Fading::Right { steps } => Fading::Bottom { steps },
+            Fading::Bottom { steps } => Fading::Left { steps },
+            Fading::Horizontal { steps } => Fading::Vertical { steps },
+            Fading::Vertical { steps } => Fading::Horizontal { steps },
+            Fading::Diagonal { steps, corner } => Fading::Diagonal {
+                steps,
+                corner: match corner {
+                    Corner::TopLeft => Corner::TopRight,
+                    Corner::TopRight => Corner::BottomRight,
+                    Corner::BottomRight => Corner::BottomLeft,
+                    Corner::BottomLeft => Corner::TopLeft,
                 },
-                Size {
-                    width: x_diff as u32,
-                    height: y_diff,
+            },
+            Fading::LeftPercent { percent } => Fading::TopPercent { percent },
+            Fading::TopPercent { percent } => Fading::RightPercent { percent },
+            Fading::RightPercent { percent } => Fading::BottomPercent { percent },
+            Fading::BottomPercent { percent } => Fading::LeftPercent { percent },
+            Fading::Auto { edge } => Fading::Auto {
+                edge: match edge {
+                    Edge::Left => Edge::Top,
+                    Edge::Top => Edge::Right,
+                    Edge::Right => Edge::Bottom,
+                    Edge::Bottom => Edge::Left,
                 },
-            );
-
-            let rec_faded = FadedRectangle::new(rec_diff, self.base_color, self.fading);
-            rec_faded.draw(target)?;
+            },
+            Fading::None | Fading::AllEdges { .. } | Fading::Radial { .. } => self,
         }
-
-        Ok(())
     }
-}
 
-impl Drawable for FadedRectangle {
-    type Color = Rgb888;
-    type Output = ();
+    /// Returns the number of steps this fading uses on `rect`.
+    pub fn resolved_steps(&self, rect: &Rectangle) -> u32 {
+        self.resolved(rect).steps() as u32
+    }
 
-    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
-    where
-        D: DrawTarget<Color = Self::Color>,
-    {
-        self.rect
-            .into_styled(PrimitiveStyle::with_fill(self.base_color))
-            .draw(target)?;
+    // Converts relative variants into fixed steps for the given rectangle.
+    fn resolved(&self, rect: &Rectangle) -> Fading {
+        let fading = match *self {
+            Fading::LeftPercent { percent } => Fading::Left {
+                steps: percent_of(rect.size.width, percent),
+            },
+            Fading::RightPercent { percent } => Fading::Right {
+                steps: percent_of(rect.size.width, percent),
+            },
+            Fading::TopPercent { percent } => Fading::Top {
+                steps: percent_of(rect.size.height, percent),
+            },
+            Fading::BottomPercent { percent } => Fading::Bottom {
+                steps: percent_of(rect.size.height, percent),
+            },
+            Fading::Auto { edge } => {
+                let dimension = match edge {
+                    Edge::Left | Edge::Right => rect.size.width,
+                    Edge::Top | Edge::Bottom => rect.size.height,
+                };
+                let steps = (percent_of(dimension, 20) as u32)
+                    .clamp(2, 16)
+                    .min(dimension) as u16;
 
-        target.draw_iter(self)?;
+                match edge {
+                    Edge::Left => Fading::Left { steps },
+                    Edge::Right => Fading::Right { steps },
+                    Edge::Top => Fading::Top { steps },
+                    Edge::Bottom => Fading::Bottom { steps },
+                }
+            }
+            fading => fading,
+        };
 
-        Ok(())
+        // More steps than pixels to fade would cut the ramp off before it reaches the base color,
+        // so it is spread over the available pixels instead.
+        let steps = (fading.steps() as u32).min(fading.max_steps(rect));
+        if steps == 0 {
+            Fading::None
+        } else {
+            fading.with_steps(steps as u16)
+        }
     }
-}
 
-impl IntoIterator for FadedRectangle {
-    type IntoIter = FadedRectangleIterator;
-    type Item = Pixel<Rgb888>;
+    // Axis along which `FadedRectangle::resized` changes the length (`true` for the width), and
+    // the side that stays in place.
+    pub(crate) fn resize_anchor(&self) -> (bool, ResizeAnchor) {
+        match self {
+            Fading::Left { .. } | Fading::LeftPercent { .. } => (true, ResizeAnchor::End),
+            Fading::Right { .. } | Fading::RightPercent { .. } => (true, ResizeAnchor::Start),
+            Fading::Top { .. } | Fading::TopPercent { .. } => (false, ResizeAnchor::End),
+            Fading::Bottom { .. } | Fading::BottomPercent { .. } => (false, ResizeAnchor::Start),
+            Fading::Auto { edge } => match edge {
+                Edge::Left => (true, ResizeAnchor::End),
+                Edge::Right => (true, ResizeAnchor::Start),
+                Edge::Top => (false, ResizeAnchor::End),
+                Edge::Bottom => (false, ResizeAnchor::Start),
+            },
+            Fading::Horizontal { .. } | Fading::AllEdges { .. } | Fading::Radial { .. } => {
+                (true, ResizeAnchor::Center)
+            }
+            Fading::Vertical { .. } => (false, ResizeAnchor::Center),
+            Fading::Diagonal { corner, .. } => match corner {
+                Corner::TopLeft | Corner::BottomLeft => (true, ResizeAnchor::End),
+                Corner::TopRight | Corner::BottomRight => (true, ResizeAnchor::Start),
+            },
+            Fading::None => (true, ResizeAnchor::Start),
+        }
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        let steps = match self.fading {
-            Fading::Bottom { steps }
-            | Fading::Top { steps }
-            | Fading::Left { steps }
-            | Fading::Right { steps } => steps,
-        };
+    // Largest number of steps that fits into `rect`.
+    fn max_steps(&self, rect: &Rectangle) -> u32 {
+        let width = rect.size.width;
+        let height = rect.size.height;
 
-        FadedRectangleIterator {
-            rect: self.rect,
-            r: self.base_color.r(),
-            g: self.base_color.g(),
-            b: self.base_color.b(),
-            fading: self.fading,
-            steps,
-            current_x: self.rect.top_left.x,
-            current_y: self.rect.top_left.y,
+        match self {
+            Fading::Left { .. } | Fading::Right { .. } => width,
+            Fading::Top { .. } | Fading::Bottom { .. } => height,
+            Fading::Horizontal { .. } => width - width / 2,
+            Fading::Vertical { .. } => height - height / 2,
+            Fading::AllEdges { .. } => (width - width / 2).min(height - height / 2),
+            // Rings are scaled to the rectangle, any number of steps fits.
+            Fading::Radial { .. } => u32::MAX,
+            Fading::Diagonal { .. } => (width + height).saturating_sub(1),
+            _ => 0,
         }
     }
-}
 
-impl Transform for FadedRectangle {
-    fn translate(&self, by: Point) -> Self {
-        self.rect.translate(by);
-        *self
+    fn with_steps(self, steps: u16) -> Fading {
+        match self {
+            Fading::Bottom { .. } => Fading::Bottom { steps },
+            Fading::Top { .. } => Fading::Top { steps },
+            Fading::Left { .. } => Fading::Left { steps },
+            Fading::Right { .. } => Fading::Right { steps },
+            Fading::Horizontal { .. } => Fading::Horizontal { steps },
+            Fading::Vertical { .. } => Fading::Vertical { steps },
+            Fading::AllEdges { .. } => Fading::AllEdges { steps },
+            Fading::Radial { .. } => Fading::Radial { steps },
+            Fading::Diagonal { corner, .. } => Fading::Diagonal { steps, corner },
+            fading => fading,
+        }
     }
 
-    fn translate_mut(&mut self, by: Point) -> &mut Self {
-        self.rect.translate_mut(by);
-        &mut *self
+    fn steps(&self) -> u16 {
+        match self {
+            // Relative variants are resolved against the rectangle before their steps are used.
+            Fading::None
+            | Fading::LeftPercent { .. }
+            | Fading::RightPercent { .. }
+            | Fading::TopPercent { .. }
+            | Fading::BottomPercent { .. }
+            | Fading::Auto { .. } => 0,
+            Fading::Bottom { steps } => *steps,
+            Fading::Top { steps } => *steps,
+            Fading::Left { steps } => *steps,
+            Fading::Right { steps } => *steps,
+            Fading::Horizontal { steps } => *steps,
+            Fading::Vertical { steps } => *steps,
+            Fading::AllEdges { steps } => *steps,
+            Fading::Radial { steps } => *steps,
+            Fading::Diagonal { steps, .. } => *steps,
+        }
     }
-}
 
-pub struct FadedRectangleIterator {
-    rect: Rectangle,
-    r: u8,
-    g: u8,
-    b: u8,
-    fading: Fading,
-    steps: u8,
-    current_x: i32,
-    current_y: i32,
-}
+    // Width of the fade band including the solid offset in front of the gradient.
+    fn band(&self, offset: u32) -> u32 {
+        match self.steps() as u32 {
+            0 => 0,
+            steps => steps + offset,
+        }
+    }
 
-impl IntoIterator for &FadedRectangle {
-    type IntoIter = FadedRectangleIterator;
-    type Item = Pixel<Rgb888>;
+    // Widths of the bands along the left, right, top and bottom edge of `rect`, zero for
+    // variants that don't fade along edges. Opposite bands never overlap: the first one takes
+    // the middle column/row of odd sizes.
+    fn margins(&self, rect: &Rectangle, offset: u32) -> [u32; 4] {
+        let band = self.band(offset);
+        let width = rect.size.width;
+        let height = rect.size.height;
 
-    fn into_iter(self) -> Self::IntoIter {
-        let steps = match self.fading {
-            Fading::Bottom { steps }
-            | Fading::Top { steps }
-            | Fading::Left { steps }
-            | Fading::Right { steps } => steps,
-        };
+        let left = band.min(width - width / 2);
+        let right = band.min(width / 2);
+        let top = band.min(height - height / 2);
+        let bottom = band.min(height / 2);
 
-        FadedRectangleIterator {
-            rect: self.rect,
-            r: self.base_color.r(),
-            g: self.base_color.g(),
-            b: self.base_color.b(),
-            fading: self.fading,
-            steps,
-            current_x: self.rect.top_left.x,
-            current_y: self.rect.top_left.y,
+        match self {
+            Fading::Left { .. } => [band.min(width), 0, 0, 0],
+            Fading::Right { .. } => [0, band.min(width), 0, 0],
+            Fading::Top { .. } => [0, 0, band.min(height), 0],
+            Fading::Bottom { .. } => [0, 0, 0, band.min(height)],
+            Fading::Horizontal { .. } => [left, right, 0, 0],
+            Fading::Vertical { .. } => [0, 0, top, bottom],
+            Fading::AllEdges { .. } => [left, right, top, bottom],
+            _ => [0; 4],
         }
     }
-}
 
-impl Iterator for FadedRectangleIterator {
-    type Item = Pixel<Rgb888>;
+    // Bounding box of a diagonal band, in rectangle-relative coordinates.
+    fn diagonal_zone(&self, rect: &Rectangle, offset: u32) -> Rectangle {
+        let Fading::Diagonal { corner, .. } = self else {
+            return Rectangle::zero();
+        };
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let steps = self.steps as u32;
-        let total_height = self.rect.size.height;
-        let total_width = self.rect.size.width;
-
-        let (start_row, end_row, start_col, end_col) = match self.fading {
-            Fading::Bottom { .. } => (
-                total_height.saturating_sub(steps),
-                total_height,
-                0,
-                total_width,
-            ),
-            Fading::Top { .. } => (0, steps, 0, total_width),
-            Fading::Right { .. } => (
-                0,
-                total_height,
-                total_width.saturating_sub(steps),
-                total_width,
-            ),
-            Fading::Left { .. } => (0, total_height, 0, steps),
+        let band = self.band(offset);
+        let cols = band.min(rect.size.width);
+        let rows = band.min(rect.size.height);
+        let x = match corner {
+            Corner::TopLeft | Corner::BottomLeft => 0,
+            Corner::TopRight | Corner::BottomRight => rect.size.width - cols,
         };
+        let y = match corner {
+            Corner::TopLeft | Corner::TopRight => 0,
+            Corner::BottomLeft | Corner::BottomRight => rect.size.height - rows,
+        };
+        zone(x, y, cols, rows)
+    }
 
-        // Initialize on first call
-        if self.current_y == self.rect.top_left.y && self.current_x == self.rect.top_left.x {
-            self.current_y = self.rect.top_left.y + start_row as i32;
-            self.current_x = self.rect.top_left.x + start_col as i32;
+    // Regions of `rect` covered by the fade, in rectangle-relative coordinates. Pixels of a
+    // zone outside of the band (see `band_step`) keep the base color.
+    fn zones(&self, rect: &Rectangle, offset: u32) -> [Rectangle; 4] {
+        let width = rect.size.width;
+        let height = rect.size.height;
+
+        match self {
+            Fading::Radial { .. } if self.band(offset) > 0 => [
+                zone(0, 0, width, height),
+                Rectangle::zero(),
+                Rectangle::zero(),
+                Rectangle::zero(),
+            ],
+            Fading::Diagonal { .. } => [
+                self.diagonal_zone(rect, offset),
+                Rectangle::zero(),
+                Rectangle::zero(),
+                Rectangle::zero(),
+            ],
+            _ => {
+                // The side zones only cover the rows between the top and bottom zones.
+                let [left, right, top, bottom] = self.margins(rect, offset);
+                let middle = height - top - bottom;
+                [
+                    zone(0, 0, width, top),
+                    zone(0, top, left, middle),
+                    zone(width - right, top, right, middle),
+                    zone(0, height - bottom, width, bottom),
+                ]
+            }
         }
+    }
 
-        // Check if we're done
-        let row_in_rect = (self.current_y - self.rect.top_left.y) as u32;
-        if row_in_rect >= end_row {
-            return None;
+    // Number of pixels in the zones that are part of the band.
+    fn band_pixels(&self, rect: &Rectangle, offset: u32) -> usize {
+        match self {
+            Fading::Diagonal { .. } => {
+                // Columns of the bounding box shorten by one row per column away from the corner.
+                let zone = self.diagonal_zone(rect, offset);
+                let band = self.band(offset);
+                (0..zone.size.width)
+                    .map(|dx| zone.size.height.min(band - dx) as usize)
+                    .sum()
+            }
+            _ => self
+                .zones(rect, offset)
+                .iter()
+                .map(|zone| zone.size.width as usize * zone.size.height as usize)
+                .sum(),
         }
+    }
 
-        let col_in_rect = (self.current_x - self.rect.top_left.x) as u32;
-        let point = Point::new(self.current_x, self.current_y);
+    // Regions of `rect` that are entirely in the base color, in rectangle-relative coordinates.
+    // Together with the zones they tile `rect` without overlapping.
+    fn solid_zones(&self, rect: &Rectangle, offset: u32) -> [Rectangle; 2] {
+        let width = rect.size.width;
+        let height = rect.size.height;
 
-        // Calculate fade
-        let fade_factor_256 = match self.fading {
-            Fading::Bottom { .. } => {
-                let rows_from_start = row_in_rect - total_height.saturating_sub(steps);
-                ((rows_from_start + 1) * 256 / steps) as u16
+        match self {
+            Fading::Radial { .. } if self.band(offset) > 0 => [Rectangle::zero(); 2],
+            Fading::Diagonal { .. } => {
+                // L-shaped remainder next to the band's bounding box.
+                let band = self.diagonal_zone(rect, offset);
+                let (cols, rows) = (band.size.width, band.size.height);
+                let x = if band.top_left.x == 0 { cols } else { 0 };
+                let y = if band.top_left.y == 0 { rows } else { 0 };
+                [
+                    zone(x, 0, width - cols, height),
+                    zone(band.top_left.x as u32, y, cols, height - rows),
+                ]
             }
-            Fading::Top { .. } => ((steps - row_in_rect) * 256 / steps) as u16,
-            Fading::Right { .. } => {
-                let cols_from_start = col_in_rect - total_width.saturating_sub(steps);
-                ((cols_from_start + 1) * 256 / steps) as u16
+            _ => {
+                let [left, right, top, bottom] = self.margins(rect, offset);
+                [
+                    zone(left, top, width - left - right, height - top - bottom),
+                    Rectangle::zero(),
+                ]
             }
-            Fading::Left { .. } => ((steps - col_in_rect) * 256 / steps) as u16,
-        };
-
-        let new_r = ((self.r as u16 * (256 - fade_factor_256)) / 256) as u8;
-        let new_g = ((self.g as u16 * (256 - fade_factor_256)) / 256) as u8;
-        let new_b = ((self.b as u16 * (256 - fade_factor_256)) / 256) as u8;
-
-        // Advance to next pixel in fade zone
-        self.current_x += 1;
-        if self.current_x >= self.rect.top_left.x + end_col as i32 {
-            self.current_x = self.rect.top_left.x + start_col as i32;
-            self.current_y += 1;
         }
-
-        Some(Pixel(point, Rgb888::new(new_r, new_g, new_b)))
     }
-}
 
-#[cfg(test)]
-mod simulator_tests {
-    use super::*;
-    use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
-    use embedded_graphics_simulator::SimulatorDisplay;
-
-    #[test]
-    fn visual_test_bottom_fade() {
-        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+    // Distance of a rectangle-relative position from the faded edge.
+    fn edge_distance(&self, rect: &Rectangle, col: u32, row: u32) -> u32 {
+        let width = rect.size.width;
+        let height = rect.size.height;
 
-        let rect = Rectangle::new(Point::new(10, 10), Size::new(300, 5));
-        let base_color = Rgb888::new(255, 0, 0);
-        FadedRectangle::new(rect, base_color, Fading::Bottom { steps: 4 })
-            .draw(&mut display)
-            .unwrap();
+        match self {
+            Fading::None
+            | Fading::LeftPercent { .. }
+            | Fading::RightPercent { .. }
+            | Fading::TopPercent { .. }
+            | Fading::BottomPercent { .. }
+            | Fading::Auto { .. } => 0,
+            Fading::Bottom { .. } => height - 1 - row,
+            Fading::Top { .. } => row,
+            Fading::Right { .. } => width - 1 - col,
+            Fading::Left { .. } => col,
+            Fading::Horizontal { .. } => col.min(width - 1 - col),
+            Fading::Vertical { .. } => row.min(height - 1 - row),
+            Fading::AllEdges { .. } => col.min(width - 1 - col).min(row).min(height - 1 - row),
+            Fading::Radial { steps } => {
+                // Offsets from the center in half pixels, normalized so the edges are at 256.
+                let dx = (2 * col + 1).abs_diff(width) * 256 / width;
+                let dy = (2 * row + 1).abs_diff(height) * 256 / height;
 
-        let output_path = "visual_test_bottom_fade.png";
-        display
-            .to_rgb_output_image(&Default::default())
-            .save_png(output_path)
-            .unwrap();
+                // Octagonal approximation of the euclidean distance.
+                let radius = dx.max(dy) + dx.min(dy) * 3 / 8;
+                let ring = radius * *steps as u32 / 256 + 1;
 
-        // std::process::Command::new("open")
-        //     .arg(output_path)
-        //     .spawn()
-        //     .ok();
+                (*steps as u32).saturating_sub(ring)
+            }
+            Fading::Diagonal { corner, .. } => {
+                let dx = match corner {
+                    Corner::TopLeft | Corner::BottomLeft => col,
+                    Corner::TopRight | Corner::BottomRight => width - 1 - col,
+                };
+                let dy = match corner {
+                    Corner::TopLeft | Corner::TopRight => row,
+                    Corner::BottomLeft | Corner::BottomRight => height - 1 - row,
+                };
+                dx + dy
+            }
+        }
     }
 
-    #[test]
-    fn visual_test_left_fade() {
-        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+    // Step of a rectangle-relative position within the fade band, `None` outside of it. Step
+    // `0` is the solid offset in front of the gradient, `distance + 1` the gradient itself.
+    fn band_step(&self, rect: &Rectangle, col: u32, row: u32, offset: u32) -> Option<u32> {
+        let steps = self.steps() as u32;
+        if steps == 0 {
+            return None;
+        }
 
-        let rect = Rectangle::new(Point::new(10, 10), Size::new(100, 32));
-        let base_color = Rgb888::new(0, 255, 132);
-        FadedRectangle::new(rect, base_color, Fading::Left { steps: 5 })
-            .draw(&mut display)
-            .unwrap();
+        let distance = self.edge_distance(rect, col, row);
+        if distance < offset {
+            return Some(0);
+        }
 
-        let output_path = "visual_test_left_fade.png";
-        display
-            .to_rgb_output_image(&Default::default())
-            .save_png(output_path)
-            .unwrap();
+        let distance = distance - offset;
+        if distance >= steps {
+            return None;
+        }
 
-        // std::process::Command::new("open")
-        //     .arg(output_path)
-        //     .spawn()
-        //     .ok();
+        Some(distance + 1)
     }
 
-    #[test]
-    fn visual_test_right_fade() {
-        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
-
-        let rect = Rectangle::new(Point::new(10, 10), Size::new(100, 32));
-        let base_color = Rgb888::new(255, 255, 0);
-        FadedRectangle::new(rect, base_color, Fading::Right { steps: 5 })
-            .draw(&mut display)
-            .unwrap();
-
-        let output_path = "visual_test_right_fade.png";
-        display
-            .to_rgb_output_image(&Default::default())
-            .save_png(output_path)
-            .unwrap();
+    // Fade factor of a step returned by `band_step`. Every variant uses this ramp on the distance
+    // from its faded edge, so mirrored variants produce mirrored pixels.
+    fn step_factor(&self, step: u32, reversed: bool) -> u16 {
+        let Some(distance) = step.checked_sub(1) else {
+            return 0;
+        };
 
-        // std::process::Command::new("open")
-        //     .arg(output_path)
-        //     .spawn()
-        //     .ok();
+        // Reversed fades keep the outermost pixel at the base color and darken inwards.
+        let steps = self.steps() as u32;
+        let ramp = if reversed { distance } else { steps - distance };
+        (ramp * 256 / steps) as u16
     }
+}
 
-    #[test]
-    fn visual_test_top_fade() {
-        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: crate::serde_helpers::RawColor"))]
+pub struct FadedRectangle<C = Rgb888> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::rectangle"))]
+    pub rect: Rectangle,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::color"))]
+    pub base_color: C,
+    pub fading: Fading,
+    pub style: FadeStyle<C>,
+    /// Radius of the rounded corners, pixels outside of a quarter circle in each corner are
+    /// skipped. Clamped to half of the smaller dimension. [`FadedRectangle::runs`] and
+    /// [`FadedRectangle::zone_colors`] still cover the whole rectangle.
+    pub corner_radius: u32,
+}
 
-        let rect = Rectangle::new(Point::new(10, 10), Size::new(300, 20));
-        let base_color = Rgb888::new(255, 49, 73);
-        FadedRectangle::new(rect, base_color, Fading::Top { steps: 4 })
-            .draw(&mut display)
-            .unwrap();
+impl<C> FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    pub fn new(rect: Rectangle, base_color: C, fading: Fading) -> Self {
+        Self {
+            rect,
+            base_color,
+            fading,
+            style: FadeStyle::new(),
+            corner_radius: 0,
+        }
+    }
 
-        let output_path = "visual_test_top_fade.png";
-        display
-            .to_rgb_output_image(&Default::default())
-            .save_png(output_path)
-            .unwrap();
+    pub fn with_style(mut self, style: FadeStyle<C>) -> Self {
+        self.style = style;
+        self
+    }
 
-        // std::process::Command::new("open")
-        //     .arg(output_path)
-        //     .spawn()
-        //     .ok();
+    /// Sets the color the fade interpolates towards, e.g. the UI background.
+    pub fn with_fade_target(mut self, fade_to: C) -> Self {
+        self.style.target = fade_to;
+        self
     }
 
-    #[test]
-    fn test_large_shrink() {
-        let mut display = SimulatorDisplay::new(Size::new(96, 32));
-        let prev_rect = Rectangle::new(Point::new(0, 0), Size::new(96, 32));
-        let prev_faded = FadedRectangle::new(prev_rect, Rgb888::GREEN, Fading::Left { steps: 5 });
-        prev_faded.draw(&mut display).unwrap();
+    pub fn with_curve(mut self, curve: FadeCurve) -> Self {
+        self.style.curve = curve;
+        self
+    }
 
-        let before_output = display.to_rgb_output_image(&Default::default());
-        let before_image = before_output.as_image_buffer();
+    /// See [`FadeStyle::reversed`].
+    pub fn with_reversed(mut self, reversed: bool) -> Self {
+        self.style.reversed = reversed;
+        self
+    }
 
-        let new_rect = Rectangle::new(Point::new(76, 0), Size::new(20, 32));
-        let new_faded = FadedRectangle::new(new_rect, Rgb888::GREEN, Fading::Left { steps: 5 });
+    /// See [`FadeStyle::max_fade`].
+    pub fn with_max_fade(mut self, max_fade: u8) -> Self {
+        self.style.max_fade = max_fade;
+        self
+    }
 
-        new_faded.draw_diff(&mut display, &prev_rect).unwrap();
+    /// See [`FadeStyle::offset`].
+    pub fn with_fade_offset(mut self, offset: u8) -> Self {
+        self.style.offset = offset;
+        self
+    }
 
-        let after_output = display.to_rgb_output_image(&Default::default());
-        let after_image = after_output.as_image_buffer();
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.style.interpolation = interpolation;
+        self
+    }
 
-        let combined_width = 96 + 20;
-        let combined_height = 32 * 2 + 30;
+    /// See [`FadeStyle::dithered`].
+    pub fn with_dithered(mut self, dithered: bool) -> Self {
+        self.style.dithered = dithered;
+        self
+    }
 
-        let mut combined = image::RgbImage::new(combined_width, combined_height);
+    /// See [`FadedRectangle::corner_radius`].
+    pub fn with_corner_radius(mut self, corner_radius: u32) -> Self {
+        self.corner_radius = corner_radius;
+        self
+    }
 
-        for pixel in combined.pixels_mut() {
-            *pixel = image::Rgb([32, 32, 32]);
+    // Width of the fade band including the offset, as drawn on `rect`.
+    pub(crate) fn band_width(&self, rect: &Rectangle) -> u32 {
+        match self.fading.resolved_steps(rect) {
+            0 => 0,
+            steps => steps + self.style.offset as u32,
         }
+    }
 
-        for y in 0..32 {
-            for x in 0..96 {
-                let src_pixel = before_image.get_pixel(x, y);
-                combined.put_pixel(x + 10, y + 10, *src_pixel);
-            }
-        }
+    /// Redraws the difference to the rectangle drawn on `previous`.
+    ///
+    /// Vacated pixels are cleared with the style's target, so with the fade target set to the
+    /// background (see [`FadedRectangle::with_fade_target`]) the fade and the cleared area blend
+    /// into the same color.
+    ///
+    /// Any edges may move. Only the parts of `previous` the rectangle no longer covers are
+    /// cleared, and only the newly covered parts plus the fade bands and corners next to moved
+    /// edges are drawn. Radial fades depend on the whole rectangle and are redrawn entirely.
+    /// If the rectangles don't overlap, `previous` is cleared and the rectangle drawn, the
+    /// pixels between them aren't touched.
+    /// Pixels are only ever written in their final color, so nothing flickers in between.
+    ///
+    /// Assumes `previous` was drawn with the same colors and fade, see
+    /// [`FadedRectangle::draw_diff_full`] otherwise.
+    ///
+    /// Returns the bounding box of all written pixels, `None` if nothing changed.
+    pub fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Rectangle,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let previous = FadedRectangle {
+            rect: *previous,
+            ..*self
+        };
+        self.draw_diff_full(target, &previous)
+    }
 
-        for y in 0..32 {
-            for x in 0..96 {
+    /// Redraws the difference to the `previous` rectangle like [`FadedRectangle::draw_diff`].
+    /// If anything but the position and size changed, e.g. the base color, the whole rectangle
+    /// is redrawn.
+    pub fn draw_diff_full<D>(
+        &self,
+        target: &mut D,
+        previous: &FadedRectangle<C>,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let color = self.style.target;
+        self.draw_diff_with(target, previous, |target, area| {
+            target.fill_solid(area, color)
+        })
+    }
+
+    /// Redraws the difference to the rectangle drawn on `previous` like
+    /// [`FadedRectangle::draw_diff`], but repaints vacated pixels with `background` clipped to
+    /// them instead of clearing them to a solid color.
+    pub fn draw_diff_over<D, B>(
+        &self,
+        target: &mut D,
+        previous: &Rectangle,
+        background: &B,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        B: Drawable<Color = C>,
+    {
+        let previous = FadedRectangle {
+            rect: *previous,
+            ..*self
+        };
+        self.draw_diff_with(target, &previous, |target, area| {
+            background.draw(&mut target.clipped(area)).map(|_| ())
+        })
+    }
+
+    /// Redraws the difference to the `previous` rectangle like
+    /// [`FadedRectangle::draw_diff_full`], calling `clear` to repaint every area the rectangle
+    /// no longer covers.
+    pub fn draw_diff_with<D, F>(
+        &self,
+        target: &mut D,
+        previous: &FadedRectangle<C>,
+        mut clear: F,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        F: FnMut(&mut D, &Rectangle) -> Result<(), D::Error>,
+    {
+        if self == previous {
+            return Ok(None);
+        }
+
+        let mut dirty = None;
+        for vacated in self.vacated(previous) {
+            if !vacated.is_zero_sized() {
+                clear(target, &vacated)?;
+                dirty = union(dirty, &vacated);
+            }
+        }
+
+        let redrawn = self.redraw_changed(target, previous, &mut clear)?;
+        Ok(redrawn.iter().fold(dirty, union))
+    }
+
+    // Parts of `previous` the rectangle no longer covers, some of them may be zero sized.
+    pub(crate) fn vacated(&self, previous: &FadedRectangle<C>) -> [Rectangle; 4] {
+        // Disjoint rectangles share no pixels, the gap between them is left alone.
+        difference(&previous.rect, &previous.rect.intersection(&self.rect))
+    }
+
+    // Redraws the pixels that differ from `previous`, after the vacated areas were cleared.
+    pub(crate) fn redraw_changed<D, F>(
+        &self,
+        target: &mut D,
+        previous: &FadedRectangle<C>,
+        clear: &mut F,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        F: FnMut(&mut D, &Rectangle) -> Result<(), D::Error>,
+    {
+        if self == previous {
+            return Ok(None);
+        }
+
+        let common = previous.rect.intersection(&self.rect);
+        if common.is_zero_sized() {
+            self.redraw_area(target, &self.rect, clear)?;
+            return Ok(union(None, &self.rect));
+        }
+
+        // Relative fades span a different number of steps on the old and the new rectangle.
+        let reach_old = previous.edge_bands();
+        let reach_new = self.edge_bands();
+        let radius_old = CornerMask::new(previous).radius;
+        let radius_new = CornerMask::new(self).radius;
+
+        let restyled = *self
+            != FadedRectangle {
+                rect: self.rect,
+                ..*previous
+            };
+        let radial = matches!(self.fading.resolved(&self.rect), Fading::Radial { .. });
+        if restyled || radial || radius_old != radius_new {
+            self.redraw_area(target, &self.rect, clear)?;
+            return Ok(union(None, &self.rect));
+        }
+
+        let mut dirty = None;
+        for added in difference(&self.rect, &common) {
+            self.redraw_area(target, &added, clear)?;
+            dirty = union(dirty, &added);
+        }
+
+        // Inside the common area the colors only change near moved edges, or near edges whose
+        // band width changed.
+        let edges = [Edge::Left, Edge::Right, Edge::Top, Edge::Bottom];
+        for (index, edge) in edges.into_iter().enumerate() {
+            let moved = edge_position(&previous.rect, edge) != edge_position(&self.rect, edge);
+            if !moved && reach_old[index] == reach_new[index] {
+                continue;
+            }
+
+            let reach = (reach_old[index].max(reach_new[index]) + radius_new) as i32;
+            let position = edge_position(&common, edge);
+            let strip = match edge {
+                Edge::Left | Edge::Top => axis_strip(&common, edge, position, position + reach),
+                Edge::Right | Edge::Bottom => axis_strip(&common, edge, position - reach, position),
+            };
+            let strip = strip.intersection(&common);
+            self.redraw_area(target, &strip, clear)?;
+            dirty = union(dirty, &strip);
+        }
+
+        Ok(dirty)
+    }
+
+    // Widths of the bands along the left, right, top and bottom edge, a diagonal band counts
+    // for both edges at its corner.
+    pub(crate) fn edge_bands(&self) -> [u32; 4] {
+        let fading = self.fading.resolved(&self.rect);
+        let offset = self.style.offset as u32;
+
+        let Fading::Diagonal { corner, .. } = fading else {
+            return fading.margins(&self.rect, offset);
+        };
+        let Size { width, height } = fading.diagonal_zone(&self.rect, offset).size;
+        match corner {
+            Corner::TopLeft => [width, 0, height, 0],
+            Corner::TopRight => [0, width, height, 0],
+            Corner::BottomLeft => [width, 0, 0, height],
+            Corner::BottomRight => [0, width, 0, height],
+        }
+    }
+
+    // Redraws `area` like `draw_partial` and clears the pixels cut off by rounded corners.
+    fn redraw_area<D, F>(
+        &self,
+        target: &mut D,
+        area: &Rectangle,
+        clear: &mut F,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        F: FnMut(&mut D, &Rectangle) -> Result<(), D::Error>,
+    {
+        self.draw_partial(target, area)?;
+
+        let top_left = self.rect.top_left;
+        for span in CornerMask::new(self).spans() {
+            let cut = span.translate(top_left).intersection(area);
+            if !cut.is_zero_sized() {
+                clear(target, &cut)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a copy resized to `new_len` along the fade axis, keeping the edge opposite of
+    /// the fade in place.
+    ///
+    /// Left and right fades change the width, top and bottom fades the height. For a left fade
+    /// the right edge stays fixed, for a bottom fade the top edge, and so on. Fades on both
+    /// sides and radial fades keep their center, diagonal fades keep the vertical edge away
+    /// from their corner, and unfaded rectangles keep their left edge.
+    pub fn resized(&self, new_len: u32) -> Self {
+        let (horizontal, anchor) = self.fading.resize_anchor();
+        let Rectangle { top_left, size } = self.rect;
+
+        let old_len = if horizontal { size.width } else { size.height };
+        let shift = match anchor {
+            ResizeAnchor::Start => 0,
+            ResizeAnchor::Center => (old_len as i32 - new_len as i32) / 2,
+            ResizeAnchor::End => old_len as i32 - new_len as i32,
+        };
+
+        let rect = if horizontal {
+            Rectangle::new(
+                top_left + Point::new(shift, 0),
+                Size::new(new_len, size.height),
+            )
+        } else {
+            Rectangle::new(
+                top_left + Point::new(0, shift),
+                Size::new(size.width, new_len),
+            )
+        };
+        Self { rect, ..*self }
+    }
+
+    /// Returns a copy rotated around `center` with [`Fading::rotated`]. It draws the same
+    /// pixels as [`Rotated`](crate::rotate::Rotated), apart from dither patterns, without
+    /// moving every pixel.
+    pub fn rotated(&self, rotation: Rotation, center: Point) -> Self {
+        Self {
+            rect: rotate_rectangle(&self.rect, rotation, center),
+            fading: self.fading.rotated(rotation),
+            ..*self
+        }
+    }
+
+    /// Returns a copy rotated like [`FadedRectangle::rotated`] that keeps the center of the
+    /// rectangle in place, see [`rotate_rect_in_place`].
+    pub fn rotated_in_place(&self, rotation: Rotation) -> Self {
+        Self {
+            rect: rotate_rect_in_place(&self.rect, rotation),
+            fading: self.fading.rotated(rotation),
+            ..*self
+        }
+    }
+
+    /// Resizes the rectangle like [`FadedRectangle::resized`] and redraws the difference with
+    /// [`FadedRectangle::draw_diff`], returning the same dirty area.
+    pub fn resize_and_diff<D>(
+        &mut self,
+        target: &mut D,
+        new_len: u32,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let previous = self.rect;
+        *self = self.resized(new_len);
+        self.draw_diff(target, &previous)
+    }
+}
+
+/// A [`FadedRectangle`] for monochrome displays, see [`FadedRectangle::binary`].
+pub type BinaryFadedRectangle = FadedRectangle<BinaryColor>;
+
+impl FadedRectangle<BinaryColor> {
+    /// Creates a rectangle of on pixels that fades out as an ordered dither pattern, the
+    /// density of on pixels follows the fade factor.
+    pub fn binary(rect: Rectangle, fading: Fading) -> Self {
+        Self {
+            rect,
+            base_color: BinaryColor::On,
+            fading,
+            style: FadeStyle {
+                target: BinaryColor::Off,
+                dithered: true,
+                ..FadeStyle::default()
+            },
+            corner_radius: 0,
+        }
+    }
+}
+
+impl<C> Drawable for FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.draw_clipped(target, &self.rect)
+    }
+}
+
+impl<C> DiffDrawable for FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    type State = FadedRectangle<C>;
+
+    fn state(&self) -> Self::State {
+        *self
+    }
+
+    fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Self::State,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        self.draw_diff_full(target, previous)
+    }
+}
+
+/// A [`FadedRectangle`] whose fade factors come from a user supplied function.
+///
+/// The function is called for every pixel in the zones of `fading` with the column and row
+/// relative to the zone and the resolved steps, and returns the fade factor (`0` keeps the
+/// base color, `256` reaches the style's target). It replaces the built-in gradient, so only
+/// the target of the [`FadeStyle`] applies.
+#[derive(Copy, Clone, Debug)]
+pub struct FadedRectangleWith<C, F> {
+    pub faded: FadedRectangle<C>,
+    pub fade_fn: F,
+}
+
+// The fade function can't be formatted.
+#[cfg(feature = "defmt")]
+impl<C, F> defmt::Format for FadedRectangleWith<C, F>
+where
+    C: defmt::Format,
+{
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "FadedRectangleWith {{ faded: {}, .. }}", self.faded)
+    }
+}
+
+impl<C> FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    pub fn with_fade_fn<F>(
+        rect: Rectangle,
+        base_color: C,
+        zone: Fading,
+        fade_fn: F,
+    ) -> FadedRectangleWith<C, F>
+    where
+        F: Fn(u32, u32, u32) -> u16,
+    {
+        FadedRectangleWith {
+            faded: FadedRectangle::new(rect, base_color, zone),
+            fade_fn,
+        }
+    }
+}
+
+impl<C, F> Drawable for FadedRectangleWith<C, F>
+where
+    C: FadeColor,
+    F: Fn(u32, u32, u32) -> u16,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let faded = &self.faded;
+        let fading = faded.fading.resolved(&faded.rect);
+        let steps = fading.steps() as u32;
+
+        for solid in fading.solid_zones(&faded.rect, 0) {
+            if !solid.is_zero_sized() {
+                target.fill_solid(&solid.translate(faded.rect.top_left), faded.base_color)?;
+            }
+        }
+
+        for zone in fading.zones(&faded.rect, 0) {
+            target.draw_iter(zone.points().map(|offset| {
+                let relative = offset - zone.top_left;
+                let fade_factor_256 = (self.fade_fn)(relative.x as u32, relative.y as u32, steps);
+                let color = faded
+                    .base_color
+                    .fade_towards(faded.style.target, fade_factor_256);
+
+                Pixel(faded.rect.top_left + offset, color)
+            }))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`FadedRectangle`] with a solid border, see [`FadedRectangle::with_stroke`].
+///
+/// The stroke lies inside the rectangle and the fade is drawn on the area within the stroke, so
+/// the whole gradient stays visible next to the border.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: crate::serde_helpers::RawColor"))]
+pub struct StrokedFadedRectangle<C = Rgb888> {
+    pub faded: FadedRectangle<C>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::color"))]
+    pub stroke_color: C,
+    pub stroke_width: u32,
+}
+
+impl<C> FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    pub fn with_stroke(self, stroke_color: C, stroke_width: u32) -> StrokedFadedRectangle<C> {
+        StrokedFadedRectangle {
+            faded: self,
+            stroke_color,
+            stroke_width,
+        }
+    }
+}
+
+impl<C> StrokedFadedRectangle<C>
+where
+    C: FadeColor,
+{
+    /// The faded area inside the stroke.
+    pub fn inner(&self) -> FadedRectangle<C> {
+        FadedRectangle {
+            rect: self.faded.rect.offset(-(self.stroke_width as i32)),
+            ..self.faded
+        }
+    }
+
+    fn draw_stroke<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let style = PrimitiveStyleBuilder::new()
+            .stroke_color(self.stroke_color)
+            .stroke_width(self.stroke_width)
+            .stroke_alignment(StrokeAlignment::Inside)
+            .build();
+
+        self.faded.rect.into_styled(style).draw(target)
+    }
+
+    /// Redraws the difference to the `previous` rectangle, like [`FadedRectangle::draw_diff`].
+    /// Only the parts of the stroke that moved are repainted, the pixels under the new stroke
+    /// aren't cleared first. Returns the bounding box of all written pixels.
+    pub fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Rectangle,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let rect = self.faded.rect;
+        if rect == *previous {
+            return Ok(None);
+        }
+
+        // Areas outside of the new rectangle are cleared, the rest is covered by the new
+        // stroke or redrawn as part of the inner rectangle.
+        let color = self.faded.style.target;
+        let mut cleared = None;
+        let mut clear = |target: &mut D, area: &Rectangle| {
+            for outside in difference(area, &area.intersection(&rect)) {
+                if !outside.is_zero_sized() {
+                    target.fill_solid(&outside, color)?;
+                    cleared = union(cleared, &outside);
+                }
+            }
+            Ok(())
+        };
+
+        let inset = -(self.stroke_width as i32);
+        let inner_previous = FadedRectangle {
+            rect: previous.offset(inset),
+            ..self.faded
+        };
+        let inner = self.inner();
+        let mut dirty = None;
+        if inner != inner_previous {
+            for vacated in inner.vacated(&inner_previous) {
+                if !vacated.is_zero_sized() {
+                    clear(target, &vacated)?;
+                }
+            }
+            dirty = inner.redraw_changed(target, &inner_previous, &mut clear)?;
+        }
+
+        // The old stroke outside of the new rectangle isn't part of either inner rectangle.
+        let previous_inner = previous.offset(inset);
+        for strip in difference(previous, &previous_inner) {
+            if !strip.is_zero_sized() {
+                clear(target, &strip)?;
+            }
+        }
+        dirty = cleared.iter().fold(dirty, union);
+
+        // Stroke pixels that weren't part of the old stroke, either inside the old inner
+        // rectangle or outside of the old rectangle.
+        for strip in difference(&rect, &rect.offset(inset)) {
+            let inside = strip.intersection(&previous_inner);
+            let outside = difference(&strip, &strip.intersection(previous));
+            for area in outside.iter().chain([&inside]) {
+                if !area.is_zero_sized() {
+                    target.fill_solid(area, self.stroke_color)?;
+                    dirty = union(dirty, area);
+                }
+            }
+        }
+
+        Ok(dirty)
+    }
+}
+
+impl<C> Drawable for StrokedFadedRectangle<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.inner().draw(target)?;
+        self.draw_stroke(target)
+    }
+}
+
+impl<C> DiffDrawable for StrokedFadedRectangle<C>
+where
+    C: FadeColor,
+{
+    type State = Rectangle;
+
+    fn state(&self) -> Self::State {
+        self.faded.rect
+    }
+
+    fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Self::State,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        StrokedFadedRectangle::draw_diff(self, target, previous)
+    }
+}
+
+impl<C> IntoIterator for FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    type IntoIter = FadedRectangleIterator<C>;
+    type Item = Pixel<C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (&self).into_iter()
+    }
+}
+
+impl<C> FadedRectangle<C> {
+    /// The area covered by the rectangle, same as [`Dimensions::bounding_box`].
+    pub fn bounding_box(&self) -> Rectangle {
+        self.rect
+    }
+}
+
+impl<C> FadedRectangle<C> {
+    /// Returns the color drawn at `point`, `None` outside of the rectangle.
+    pub fn color_at(&self, point: Point) -> Option<C>
+    where
+        C: FadeColor,
+    {
+        if !self.rect.contains(point) {
+            return None;
+        }
+
+        let shading = FadeShading::with_lut(self, false);
+        let offset = point - self.rect.top_left;
+        if shading.mask.contains(offset) {
+            return None;
+        }
+        Some(shading.color_or_base(offset))
+    }
+
+    /// Returns whether `point` is part of the fade, rather than the solid region.
+    pub fn contains_fade(&self, point: Point) -> bool {
+        if !self.rect.contains(point) {
+            return false;
+        }
+
+        let fading = self.fading.resolved(&self.rect);
+        let offset = point - self.rect.top_left;
+        fading
+            .band_step(
+                &self.rect,
+                offset.x as u32,
+                offset.y as u32,
+                self.style.offset as u32,
+            )
+            .is_some()
+    }
+
+    /// Returns the points the fade is drawn on, the same ones the pixel iterator yields.
+    pub fn fade_points(&self) -> impl Iterator<Item = Point> {
+        self.zone_points(true)
+    }
+
+    /// Returns the points drawn in the solid base color, all points of the rectangle that
+    /// aren't part of [`FadedRectangle::fade_points`].
+    pub fn solid_points(&self) -> impl Iterator<Item = Point> {
+        let fading = self.fading.resolved(&self.rect);
+        let top_left = self.rect.top_left;
+        let mask = CornerMask::new(self);
+
+        fading
+            .solid_zones(&self.rect, self.style.offset as u32)
+            .into_iter()
+            .flat_map(|solid| solid.points())
+            .filter(move |point| !mask.contains(*point))
+            .map(move |point| top_left + point)
+            .chain(self.zone_points(false))
+    }
+
+    // Points of the fade zones in display coordinates that are (or aren't) part of the band.
+    fn zone_points(&self, in_band: bool) -> impl Iterator<Item = Point> {
+        let fading = self.fading.resolved(&self.rect);
+        let rect = self.rect;
+        let offset = self.style.offset as u32;
+        let mask = CornerMask::new(self);
+
+        fading
+            .zones(&rect, offset)
+            .into_iter()
+            .flat_map(|zone| zone.points())
+            .filter(move |point| {
+                let step = fading.band_step(&rect, point.x as u32, point.y as u32, offset);
+                step.is_some() == in_band && !mask.contains(*point)
+            })
+            .map(move |point| rect.top_left + point)
+    }
+}
+
+impl<C> Dimensions for FadedRectangle<C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.rect
+    }
+}
+
+impl<C> Transform for FadedRectangle<C>
+where
+    C: Copy,
+{
+    fn translate(&self, by: Point) -> Self {
+        Self {
+            rect: self.rect.translate(by),
+            ..*self
+        }
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.rect.translate_mut(by);
+        &mut *self
+    }
+}
+
+// Fades with up to `FADE_LUT_LEN - 1` steps look their colors up instead of blending per pixel.
+const FADE_LUT_LEN: usize = 65;
+
+// A fade resolved against its rectangle, maps rectangle-relative positions to colors.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct FadeShading<C> {
+    rect: Rectangle,
+    base_color: C,
+    fading: Fading,
+    style: FadeStyle<C>,
+    // Colors indexed by band step, only used if `use_lut` is set.
+    lut: [C; FADE_LUT_LEN],
+    use_lut: bool,
+    mask: CornerMask,
+}
+
+impl<C> FadeShading<C>
+where
+    C: FadeColor,
+{
+    pub(crate) fn new(faded: &FadedRectangle<C>) -> Self {
+        Self::with_lut(faded, true)
+    }
+
+    // Single lookups skip building the table, which costs a blend per step.
+    fn with_lut(faded: &FadedRectangle<C>, lut: bool) -> Self {
+        let fading = faded.fading.resolved(&faded.rect);
+
+        // Dithered colors depend on the position, so they can't be precomputed.
+        let steps = fading.steps() as usize;
+        let use_lut = lut && !faded.style.dithered && steps < FADE_LUT_LEN;
+        let mut lut = [faded.base_color; FADE_LUT_LEN];
+        if use_lut {
+            for (step, color) in lut.iter_mut().enumerate().take(steps + 1) {
+                let fade_factor_256 = fading.step_factor(step as u32, faded.style.reversed);
+                *color = faded
+                    .style
+                    .blend(faded.base_color, fade_factor_256, Point::zero());
+            }
+        }
+
+        Self {
+            rect: faded.rect,
+            base_color: faded.base_color,
+            fading,
+            style: faded.style,
+            lut,
+            use_lut,
+            mask: CornerMask::new(faded),
+        }
+    }
+
+    fn zones(&self) -> [Rectangle; 4] {
+        self.fading.zones(&self.rect, self.style.offset as u32)
+    }
+
+    fn solid_zones(&self) -> [Rectangle; 2] {
+        self.fading
+            .solid_zones(&self.rect, self.style.offset as u32)
+    }
+
+    // Color of a rectangle-relative position, `None` outside of the fade band.
+    fn color(&self, offset: Point) -> Option<C> {
+        let (col, row) = (offset.x as u32, offset.y as u32);
+        let step = self
+            .fading
+            .band_step(&self.rect, col, row, self.style.offset as u32)?;
+
+        if self.use_lut {
+            Some(self.lut[step as usize])
+        } else {
+            let fade_factor_256 = self.fading.step_factor(step, self.style.reversed);
+            let point = self.rect.top_left + offset;
+            Some(self.style.blend(self.base_color, fade_factor_256, point))
+        }
+    }
+
+    pub(crate) fn color_or_base(&self, offset: Point) -> C {
+        self.color(offset).unwrap_or(self.base_color)
+    }
+}
+
+// Square root rounded down, `u64::isqrt` needs a newer compiler.
+fn isqrt(value: u64) -> u64 {
+    let mut root = value;
+    let mut next = value.div_ceil(2);
+    while next < root {
+        root = next;
+        next = (root + value / root) / 2;
+    }
+    root
+}
+
+// The pixels cut off by the rounded corners of a rectangle of `size`.
+#[derive(Copy, Clone, Debug)]
+struct CornerMask {
+    size: Size,
+    radius: u32,
+}
+
+impl CornerMask {
+    fn new<C>(faded: &FadedRectangle<C>) -> Self {
+        let size = faded.rect.size;
+        Self {
+            size,
+            radius: faded.corner_radius.min(size.width / 2).min(size.height / 2),
+        }
+    }
+
+    // Pixels skipped at both ends of `row`.
+    fn inset(&self, row: u32) -> u32 {
+        let radius = self.radius;
+        let from_edge = row.min(self.size.height - 1 - row);
+        if from_edge >= radius {
+            return 0;
+        }
+
+        // Distance of the pixel center to the circle center, in half pixels.
+        let (radius, dy) = (u64::from(radius), u64::from(2 * (radius - from_edge) - 1));
+        let dx = isqrt(4 * radius * radius - dy * dy);
+        (radius - dx.div_ceil(2)) as u32
+    }
+
+    fn contains(&self, offset: Point) -> bool {
+        if self.radius == 0 {
+            return false;
+        }
+
+        let inset = self.inset(offset.y as u32);
+        let column = offset.x as u32;
+        column < inset || column >= self.size.width - inset
+    }
+
+    // Rows cut by the corners.
+    fn rows(&self) -> impl Iterator<Item = u32> {
+        let (radius, height) = (self.radius, self.size.height);
+        (0..radius).chain(height - radius..height)
+    }
+
+    // Rectangle-relative spans that are cut off, one at each end of every cut row.
+    fn spans(self) -> impl Iterator<Item = Rectangle> {
+        let width = self.size.width;
+        self.rows().flat_map(move |row| {
+            let inset = self.inset(row);
+            [0, width - inset].map(|column| zone(column, row, inset, 1))
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FadedRectangleIterator<C = Rgb888> {
+    shading: FadeShading<C>,
+    zones: [Rectangle; 4],
+    zone: usize,
+    points: rectangle::Points,
+    remaining: usize,
+}
+
+impl<C> IntoIterator for &FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    type IntoIter = FadedRectangleIterator<C>;
+    type Item = Pixel<C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let shading = FadeShading::new(self);
+        let zones = shading.zones();
+        let offset = self.style.offset as u32;
+        let masked = shading
+            .mask
+            .spans()
+            .flat_map(|span| span.points())
+            .filter(|point| {
+                let (col, row) = (point.x as u32, point.y as u32);
+                shading
+                    .fading
+                    .band_step(&self.rect, col, row, offset)
+                    .is_some()
+            })
+            .count();
+        let remaining = shading.fading.band_pixels(&self.rect, offset) - masked;
+
+        FadedRectangleIterator {
+            shading,
+            zones,
+            zone: 0,
+            points: zones[0].points(),
+            remaining,
+        }
+    }
+}
+
+#[cfg(feature = "bench")]
+impl<C> FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    /// Iterates without the color lookup table, blending every pixel. Only meant for benchmarks.
+    #[doc(hidden)]
+    pub fn iter_without_lut(&self) -> FadedRectangleIterator<C> {
+        let mut iter = self.into_iter();
+        iter.shading.use_lut = false;
+        iter
+    }
+}
+
+impl<C> Iterator for FadedRectangleIterator<C>
+where
+    C: FadeColor,
+{
+    type Item = Pixel<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(offset) = self.points.next() {
+                let Some(color) = self.shading.color(offset) else {
+                    continue;
+                };
+                if self.shading.mask.contains(offset) {
+                    continue;
+                }
+
+                self.remaining -= 1;
+                return Some(Pixel(self.shading.rect.top_left + offset, color));
+            }
+
+            // Stays on the exhausted last zone, so later calls keep returning `None`.
+            if self.zone + 1 >= self.zones.len() {
+                return None;
+            }
+            self.zone += 1;
+            self.points = self.zones[self.zone].points();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<C> ExactSizeIterator for FadedRectangleIterator<C> where C: FadeColor {}
+
+impl<C> FusedIterator for FadedRectangleIterator<C> where C: FadeColor {}
+
+impl<C> FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    /// Returns the fade zones in display coordinates, each with the colors of all its pixels in
+    /// the row-major order `DrawTarget::fill_contiguous` expects.
+    ///
+    /// Pixels of a zone outside of the fade band, like the solid corner of a diagonal fade,
+    /// are yielded in the base color.
+    pub fn zone_colors(&self) -> impl Iterator<Item = (Rectangle, FadedRectangleColors<C>)> {
+        let shading = FadeShading::new(self);
+        let top_left = self.rect.top_left;
+
+        shading
+            .zones()
+            .into_iter()
+            .filter(|zone| !zone.is_zero_sized())
+            .map(move |zone| {
+                let colors = FadedRectangleColors {
+                    shading,
+                    points: zone.points(),
+                };
+                (zone.translate(top_left), colors)
+            })
+    }
+}
+
+impl<C> FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    /// Draws the part of the rectangle inside `area`, e.g. to keep a rectangle that is partially
+    /// off-screen within the bounds of the display. The fade is computed for the whole
+    /// rectangle and then cropped.
+    pub fn draw_clipped<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let shading = FadeShading::new(self);
+        let top_left = self.rect.top_left;
+        let area = area.translate(Point::zero() - top_left);
+        let mask = shading.mask;
+        if mask.radius == 0 {
+            return self.fill_area(target, &shading, &area);
+        }
+
+        // Rows cut by the rounded corners are drawn as single spans, the rows between them as
+        // if there were no corners.
+        let Size { width, height } = self.rect.size;
+        let radius = mask.radius;
+        let middle = zone(0, radius, width, height - 2 * radius).intersection(&area);
+        self.fill_area(target, &shading, &middle)?;
+
+        for row in mask.rows() {
+            let inset = mask.inset(row);
+            let span = zone(inset, row, width - 2 * inset, 1).intersection(&area);
+            if !span.is_zero_sized() {
+                let colors = FadedRectangleColors {
+                    shading,
+                    points: span.points(),
+                };
+                target.fill_contiguous(&span.translate(top_left), colors)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Draws the rectangle-relative `area`, ignoring the rounded corners.
+    fn fill_area<D>(
+        &self,
+        target: &mut D,
+        shading: &FadeShading<C>,
+        area: &Rectangle,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let top_left = self.rect.top_left;
+
+        // Only the solid parts are filled, so every pixel is written once.
+        for solid in shading.solid_zones() {
+            let solid = solid.intersection(area);
+            if !solid.is_zero_sized() {
+                target.fill_solid(&solid.translate(top_left), self.base_color)?;
+            }
+        }
+
+        for zone in shading.zones() {
+            let zone = zone.intersection(area);
+            if !zone.is_zero_sized() {
+                let colors = FadedRectangleColors {
+                    shading: *shading,
+                    points: zone.points(),
+                };
+                target.fill_contiguous(&zone.translate(top_left), colors)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Redraws the pixels inside a damaged `area` in the colors a full draw would produce.
+    pub fn draw_partial<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let damaged = self.rect.intersection(area);
+        if damaged.is_zero_sized() {
+            return Ok(());
+        }
+
+        self.draw_clipped(target, &damaged)
+    }
+}
+
+/// Colors of a fade zone, see [`FadedRectangle::zone_colors`].
+#[derive(Clone, Debug)]
+pub struct FadedRectangleColors<C = Rgb888> {
+    shading: FadeShading<C>,
+    points: rectangle::Points,
+}
+
+impl<C> Iterator for FadedRectangleColors<C>
+where
+    C: FadeColor,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.points.next()?;
+        Some(self.shading.color_or_base(offset))
+    }
+}
+
+impl<C> FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    /// Decomposes the rectangle into uniformly colored strips, e.g. to draw them with
+    /// `DrawTarget::fill_solid` on displays with fast line primitives.
+    ///
+    /// The strips tile the rectangle without overlapping. Fades along the left and right edges
+    /// yield columns, other fades yield horizontal runs of equal colors.
+    pub fn runs(&self) -> FadedRectangleRuns<C> {
+        let shading = FadeShading::new(self);
+        let columns = !shading.style.dithered
+            && matches!(
+                shading.fading,
+                Fading::Left { .. } | Fading::Right { .. } | Fading::Horizontal { .. }
+            );
+
+        FadedRectangleRuns {
+            solid: shading.solid_zones(),
+            zones: shading.zones(),
+            shading,
+            columns,
+            solid_index: 0,
+            zone: 0,
+            cursor: Point::zero(),
+        }
+    }
+}
+
+/// Uniformly colored strips of a [`FadedRectangle`], see [`FadedRectangle::runs`].
+#[derive(Clone, Debug)]
+pub struct FadedRectangleRuns<C = Rgb888> {
+    shading: FadeShading<C>,
+    solid: [Rectangle; 2],
+    zones: [Rectangle; 4],
+    // Yields zone columns instead of row runs, only valid if every column has a single color.
+    columns: bool,
+    solid_index: usize,
+    zone: usize,
+    // Position of the next run, relative to the current zone.
+    cursor: Point,
+}
+
+impl<C> Iterator for FadedRectangleRuns<C>
+where
+    C: FadeColor,
+{
+    type Item = (Rectangle, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let top_left = self.shading.rect.top_left;
+
+        while let Some(solid) = self.solid.get(self.solid_index) {
+            self.solid_index += 1;
+            if !solid.is_zero_sized() {
+                return Some((solid.translate(top_left), self.shading.base_color));
+            }
+        }
+
+        loop {
+            let zone = *self.zones.get(self.zone)?;
+            let size = zone.size;
+            if zone.is_zero_sized() || self.cursor.y >= size.height as i32 {
+                self.zone += 1;
+                self.cursor = Point::zero();
+                continue;
+            }
+
+            // Extends the run while the following pixels share its color.
+            let start = self.cursor;
+            let color = self.shading.color_or_base(zone.top_left + start);
+            let mut end = start.x + 1;
+            while end < size.width as i32
+                && self
+                    .shading
+                    .color_or_base(zone.top_left + Point::new(end, start.y))
+                    == color
+            {
+                end += 1;
+            }
+
+            let height = if self.columns { size.height } else { 1 };
+            self.cursor.x = end;
+            if end >= size.width as i32 {
+                self.cursor = Point::new(0, start.y + height as i32);
+            }
+
+            let run_size = Size::new((end - start.x) as u32, height);
+            let run = Rectangle::new(top_left + zone.top_left + start, run_size);
+            return Some((run, color));
+        }
+    }
+}
+
+/// The border of a [`FadedRectangle`], see [`FadedRectangle::frame`].
+///
+/// Only the outermost `width` pixels are drawn, in the colors the full rectangle has there, so
+/// the fade runs along the border in the direction of `fading`. The interior is left untouched.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: crate::serde_helpers::RawColor"))]
+pub struct FadedFrame<C = Rgb888> {
+    pub faded: FadedRectangle<C>,
+    pub width: u32,
+}
+
+impl<C> FadedRectangle<C>
+where
+    C: FadeColor,
+{
+    pub fn frame(self, width: u32) -> FadedFrame<C> {
+        FadedFrame { faded: self, width }
+    }
+}
+
+impl<C> FadedFrame<C> {
+    // Top, left, right and bottom border strips relative to the rectangle. The side strips only
+    // span the rows between the top and bottom strips, so corners belong to a single strip.
+    fn strips(&self) -> [Rectangle; 4] {
+        let Size { width, height } = self.faded.rect.size;
+
+        let top = self.width.min(height);
+        let bottom = self.width.min(height - top);
+        let left = self.width.min(width);
+        let right = self.width.min(width - left);
+        let middle = height - top - bottom;
+
+        [
+            zone(0, 0, width, top),
+            zone(0, top, left, middle),
+            zone(width - right, top, right, middle),
+            zone(0, height - bottom, width, bottom),
+        ]
+    }
+}
+
+impl<C> Dimensions for FadedFrame<C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.faded.rect
+    }
+}
+
+impl<C> Drawable for FadedFrame<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let shading = FadeShading::new(&self.faded);
+        let top_left = self.faded.rect.top_left;
+
+        for strip in self.strips() {
+            if shading.mask.radius == 0 {
+                let colors = strip.points().map(|offset| shading.color_or_base(offset));
+                target.fill_contiguous(&strip.translate(top_left), colors)?;
+            } else {
+                let pixels = strip
+                    .points()
+                    .filter(|offset| !shading.mask.contains(*offset))
+                    .map(|offset| Pixel(top_left + offset, shading.color_or_base(offset)));
+                target.draw_iter(pixels)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C> IntoIterator for &FadedFrame<C>
+where
+    C: FadeColor,
+{
+    type IntoIter = FadedFrameIterator<C>;
+    type Item = Pixel<C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let strips = self.strips();
+
+        FadedFrameIterator {
+            shading: FadeShading::new(&self.faded),
+            strips,
+            strip: 0,
+            points: strips[0].points(),
+        }
+    }
+}
+
+impl<C> IntoIterator for FadedFrame<C>
+where
+    C: FadeColor,
+{
+    type IntoIter = FadedFrameIterator<C>;
+    type Item = Pixel<C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (&self).into_iter()
+    }
+}
+
+/// Pixels of a [`FadedFrame`].
+#[derive(Clone, Debug)]
+pub struct FadedFrameIterator<C = Rgb888> {
+    shading: FadeShading<C>,
+    strips: [Rectangle; 4],
+    strip: usize,
+    points: rectangle::Points,
+}
+
+impl<C> Iterator for FadedFrameIterator<C>
+where
+    C: FadeColor,
+{
+    type Item = Pixel<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(offset) = self.points.next() {
+                if self.shading.mask.contains(offset) {
+                    continue;
+                }
+                let color = self.shading.color_or_base(offset);
+                return Some(Pixel(self.shading.rect.top_left + offset, color));
+            }
+
+            if self.strip + 1 >= self.strips.len() {
+                return None;
+            }
+            self.strip += 1;
+            self.points = self.strips[self.strip].points();
+        }
+    }
+}
+
+impl<C> FusedIterator for FadedFrameIterator<C> where C: FadeColor {}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use crate::rotate::RotateExt;
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::{BinaryColor, Gray4, Rgb565, Rgb888},
+        primitives::Rectangle,
+    };
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    use crate::gradient::{GradientDirection, GradientRectangle};
+
+    #[test]
+    fn visual_test_bottom_fade() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let rect = Rectangle::new(Point::new(10, 10), Size::new(300, 5));
+        let base_color = Rgb888::new(255, 0, 0);
+        FadedRectangle::new(rect, base_color, Fading::Bottom { steps: 4 })
+            .draw(&mut display)
+            .unwrap();
+
+        let output_path = "visual_test_bottom_fade.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+
+        // std::process::Command::new("open")
+        //     .arg(output_path)
+        //     .spawn()
+        //     .ok();
+    }
+
+    #[test]
+    fn visual_test_left_fade() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let rect = Rectangle::new(Point::new(10, 10), Size::new(100, 32));
+        let base_color = Rgb888::new(0, 255, 132);
+        FadedRectangle::new(rect, base_color, Fading::Left { steps: 5 })
+            .draw(&mut display)
+            .unwrap();
+
+        let output_path = "visual_test_left_fade.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+
+        // std::process::Command::new("open")
+        //     .arg(output_path)
+        //     .spawn()
+        //     .ok();
+    }
+
+    #[test]
+    fn visual_test_right_fade() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let rect = Rectangle::new(Point::new(10, 10), Size::new(100, 32));
+        let base_color = Rgb888::new(255, 255, 0);
+        FadedRectangle::new(rect, base_color, Fading::Right { steps: 5 })
+            .draw(&mut display)
+            .unwrap();
+
+        let output_path = "visual_test_right_fade.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+
+        // std::process::Command::new("open")
+        //     .arg(output_path)
+        //     .spawn()
+        //     .ok();
+    }
+
+    #[test]
+    fn visual_test_top_fade() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let rect = Rectangle::new(Point::new(10, 10), Size::new(300, 20));
+        let base_color = Rgb888::new(255, 49, 73);
+        FadedRectangle::new(rect, base_color, Fading::Top { steps: 4 })
+            .draw(&mut display)
+            .unwrap();
+
+        let output_path = "visual_test_top_fade.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+
+        // std::process::Command::new("open")
+        //     .arg(output_path)
+        //     .spawn()
+        //     .ok();
+    }
+
+    #[test]
+    fn test_large_shrink() {
+        let mut display = SimulatorDisplay::new(Size::new(96, 32));
+        let prev_rect = Rectangle::new(Point::new(0, 0), Size::new(96, 32));
+        let prev_faded = FadedRectangle::new(prev_rect, Rgb888::GREEN, Fading::Left { steps: 5 });
+        prev_faded.draw(&mut display).unwrap();
+
+        let before_output = display.to_rgb_output_image(&Default::default());
+        let before_image = before_output.as_image_buffer();
+
+        let new_rect = Rectangle::new(Point::new(76, 0), Size::new(20, 32));
+        let new_faded = FadedRectangle::new(new_rect, Rgb888::GREEN, Fading::Left { steps: 5 });
+
+        new_faded.draw_diff(&mut display, &prev_rect).unwrap();
+
+        let after_output = display.to_rgb_output_image(&Default::default());
+        let after_image = after_output.as_image_buffer();
+
+        let combined_width = 96 + 20;
+        let combined_height = 32 * 2 + 30;
+
+        let mut combined = image::RgbImage::new(combined_width, combined_height);
+
+        for pixel in combined.pixels_mut() {
+            *pixel = image::Rgb([32, 32, 32]);
+        }
+
+        for y in 0..32 {
+            for x in 0..96 {
+                let src_pixel = before_image.get_pixel(x, y);
+                combined.put_pixel(x + 10, y + 10, *src_pixel);
+            }
+        }
+
+        for y in 0..32 {
+            for x in 0..96 {
+                let src_pixel = after_image.get_pixel(x, y);
+                combined.put_pixel(x + 10, y + 32 + 20, *src_pixel);
+            }
+        }
+
+        let output_path = "test_large_shrink_comparison.png";
+        combined.save(output_path).unwrap();
+
+        // std::process::Command::new("open")
+        //     .arg(output_path)
+        //     .spawn()
+        //     .ok();
+    }
+
+    #[test]
+    fn test_expanding() {
+        let mut display = SimulatorDisplay::new(Size::new(96, 32));
+        let prev_rect = Rectangle::new(Point::new(20, 0), Size::new(76, 32));
+        let prev_faded = FadedRectangle::new(prev_rect, Rgb888::YELLOW, Fading::Left { steps: 5 });
+        prev_faded.draw(&mut display).unwrap();
+
+        let before_output = display.to_rgb_output_image(&Default::default());
+        let before_image = before_output.as_image_buffer();
+
+        let new_rect = Rectangle::new(Point::new(18, 0), Size::new(78, 32));
+        let new_faded = FadedRectangle::new(new_rect, Rgb888::YELLOW, Fading::Left { steps: 5 });
+
+        new_faded.draw_diff(&mut display, &prev_rect).unwrap();
+
+        let after_output = display.to_rgb_output_image(&Default::default());
+        let after_image = after_output.as_image_buffer();
+
+        let combined_width = 96 + 20;
+        let combined_height = 32 * 2 + 30;
+
+        let mut combined = image::RgbImage::new(combined_width, combined_height);
+
+        for pixel in combined.pixels_mut() {
+            *pixel = image::Rgb([32, 32, 32]);
+        }
+
+        for y in 0..32 {
+            for x in 0..96 {
+                let src_pixel = before_image.get_pixel(x, y);
+                combined.put_pixel(x + 10, y + 10, *src_pixel);
+            }
+        }
+
+        for y in 0..32 {
+            for x in 0..96 {
                 let src_pixel = after_image.get_pixel(x, y);
                 combined.put_pixel(x + 10, y + 32 + 20, *src_pixel);
             }
         }
 
-        let output_path = "test_large_shrink_comparison.png";
-        combined.save(output_path).unwrap();
+        let output_path = "test_expanding_comparison.png";
+        combined.save(output_path).unwrap();
+
+        // std::process::Command::new("open")
+        //     .arg(output_path)
+        //     .spawn()
+        //     .ok();
+    }
+
+    #[test]
+    fn test_rgb565_matches_converted_rgb888() {
+        let rect = Rectangle::new(Point::new(4, 4), Size::new(40, 20));
+
+        for fading in [
+            Fading::Bottom { steps: 6 },
+            Fading::Top { steps: 6 },
+            Fading::Left { steps: 9 },
+            Fading::Right { steps: 9 },
+        ] {
+            let mut display_888 = SimulatorDisplay::<Rgb888>::new(Size::new(48, 28));
+            let mut display_565 = SimulatorDisplay::<Rgb565>::new(Size::new(48, 28));
+
+            FadedRectangle::new(rect, Rgb888::new(255, 128, 64), fading)
+                .draw(&mut display_888)
+                .unwrap();
+            FadedRectangle::new(rect, Rgb565::from(Rgb888::new(255, 128, 64)), fading)
+                .draw(&mut display_565)
+                .unwrap();
+
+            for point in display_888.bounding_box().points() {
+                let expected = Rgb565::from(display_888.get_pixel(point));
+                let actual = display_565.get_pixel(point);
+
+                assert!(
+                    expected.r().abs_diff(actual.r()) <= 1,
+                    "{fading:?} at {point:?}"
+                );
+                assert!(
+                    expected.g().abs_diff(actual.g()) <= 1,
+                    "{fading:?} at {point:?}"
+                );
+                assert!(
+                    expected.b().abs_diff(actual.b()) <= 1,
+                    "{fading:?} at {point:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fade_target_endpoints() {
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(10, 2));
+        let base_color = Rgb888::new(200, 100, 50);
+        let fade_to = Rgb888::new(0, 0, 120);
+        FadedRectangle::new(rect, base_color, Fading::Left { steps: 4 })
+            .with_fade_target(fade_to)
+            .draw(&mut display)
+            .unwrap();
+
+        // Outermost step lands exactly on the target, innermost stays close to the base color.
+        assert_eq!(display.get_pixel(Point::new(0, 0)), Some(fade_to));
+        assert_eq!(
+            display.get_pixel(Point::new(3, 1)),
+            Some(Rgb888::new(150, 75, 67))
+        );
+        assert_eq!(display.get_pixel(Point::new(4, 0)), Some(base_color));
+
+        for x in 0..4 {
+            let color = display.get_pixel(Point::new(x, 0)).unwrap();
+            assert!(color.r() <= base_color.r() && color.b() >= base_color.b());
+            assert!(color.b() <= fade_to.b());
+        }
+    }
+
+    #[test]
+    fn test_draw_diff_clears_with_fade_target() {
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+
+        let background = Rgb888::new(0, 0, 64);
+        let prev_rect = Rectangle::new(Point::new(0, 0), Size::new(20, 4));
+        let new_rect = Rectangle::new(Point::new(8, 0), Size::new(12, 4));
+
+        FadedRectangle::new(new_rect, Rgb888::GREEN, Fading::Left { steps: 3 })
+            .with_fade_target(background)
+            .draw_diff(&mut display, &prev_rect)
+            .unwrap();
+
+        for x in 0..8 {
+            assert_eq!(display.get_pixel(Point::new(x, 2)), Some(background));
+        }
+    }
+
+    #[test]
+    fn visual_test_horizontal_fade() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let rect = Rectangle::new(Point::new(10, 10), Size::new(100, 32));
+        let base_color = Rgb888::new(0, 132, 255);
+        FadedRectangle::new(rect, base_color, Fading::Horizontal { steps: 8 })
+            .draw(&mut display)
+            .unwrap();
+
+        for y in 10..42 {
+            for x in 0..50 {
+                assert_eq!(
+                    display.get_pixel(Point::new(10 + x, y)),
+                    display.get_pixel(Point::new(109 - x, y))
+                );
+            }
+        }
+
+        let output_path = "visual_test_horizontal_fade.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_horizontal_fade_narrow_rect() {
+        for width in 1..12 {
+            let rect = Rectangle::new(Point::new(3, 2), Size::new(width, 3));
+            let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Horizontal { steps: 5 });
+
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.draw_iter(&faded).unwrap();
+
+            // Every column is emitted exactly once, mirrored around the center.
+            let expected_columns = width.min(10);
+            assert_eq!(faded.into_iter().count() as u32, expected_columns * 3);
+            for x in 0..width as i32 {
+                assert_eq!(
+                    display.get_pixel(Point::new(3 + x, 2)),
+                    display.get_pixel(Point::new(3 + width as i32 - 1 - x, 2))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_horizontal_draw_diff_expanding() {
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+
+        let prev_rect = Rectangle::new(Point::new(20, 0), Size::new(30, 4));
+        let fading = Fading::Horizontal { steps: 4 };
+        FadedRectangle::new(prev_rect, Rgb888::CYAN, fading)
+            .draw(&mut display)
+            .unwrap();
+
+        let new_rect = Rectangle::new(Point::new(12, 0), Size::new(38, 4));
+        let faded = FadedRectangle::new(new_rect, Rgb888::CYAN, fading);
+        faded.draw_diff(&mut display, &prev_rect).unwrap();
+
+        let mut expected = MockDisplay::<Rgb888>::new();
+        expected.set_allow_overdraw(true);
+        faded.draw(&mut expected).unwrap();
+
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_vertical_fade_rows() {
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(4, 9));
+        FadedRectangle::new(
+            rect,
+            Rgb888::new(240, 120, 60),
+            Fading::Vertical { steps: 3 },
+        )
+        .draw(&mut display)
+        .unwrap();
+
+        let rows = [
+            (0, Rgb888::BLACK),
+            (1, Rgb888::new(80, 40, 20)),
+            (2, Rgb888::new(160, 80, 40)),
+            (4, Rgb888::new(240, 120, 60)),
+            (6, Rgb888::new(160, 80, 40)),
+            (7, Rgb888::new(80, 40, 20)),
+            (8, Rgb888::BLACK),
+        ];
+        for (y, color) in rows {
+            for x in 0..4 {
+                assert_eq!(display.get_pixel(Point::new(x, y)), Some(color), "row {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_vertical_fade_short_rect_is_a_valley() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(2, 5));
+        let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Vertical { steps: 4 });
+
+        // Drawing into a MockDisplay without overdraw panics on double-emitted rows.
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.draw_iter(&faded).unwrap();
+
+        let brightness: Vec<u8> = (0..5)
+            .map(|y| display.get_pixel(Point::new(0, y)).unwrap().r())
+            .collect();
+        // The steps are clamped to the 3 rows each half can fade.
+        assert_eq!(brightness, [0, 85, 170, 85, 0]);
+    }
+
+    #[test]
+    fn visual_test_all_edges_fade() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(320, 240));
+        let base_color = Rgb888::new(64, 96, 255);
+        let faded = FadedRectangle::new(rect, base_color, Fading::AllEdges { steps: 12 });
+        faded.draw(&mut display).unwrap();
+
+        // Only the border band is iterated, not the solid interior.
+        assert_eq!(faded.into_iter().count(), 320 * 240 - 296 * 216);
+
+        let output_path = "visual_test_all_edges_fade.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_all_edges_corners() {
+        let rect = Rectangle::new(Point::new(1, 1), Size::new(9, 7));
+        let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::AllEdges { steps: 3 });
+
+        // Drawing into a MockDisplay without overdraw panics on double-emitted corners.
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.draw_iter(&faded).unwrap();
+
+        let brightness = |x, y| display.get_pixel(Point::new(x, y)).unwrap().r();
+        assert_eq!(brightness(1, 1), 0);
+        assert_eq!(brightness(9, 7), 0);
+        assert_eq!(brightness(2, 2), brightness(2, 4));
+        assert_eq!(brightness(3, 2), brightness(2, 2));
+        assert_eq!(brightness(3, 3), brightness(5, 3));
+        assert_eq!(display.get_pixel(Point::new(4, 4)), None);
+    }
+
+    #[test]
+    fn visual_test_radial_fade() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(128, 64));
+
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(128, 64));
+        let base_color = Rgb888::new(255, 220, 160);
+        FadedRectangle::new(rect, base_color, Fading::Radial { steps: 16 })
+            .draw(&mut display)
+            .unwrap();
+
+        let brightness = |x, y| display.get_pixel(Point::new(x, y)).r();
+        assert_eq!(brightness(0, 0), 0);
+        assert_eq!(brightness(64, 32), brightness(63, 31));
+        assert!(brightness(63, 31) > brightness(32, 31));
+        assert!(brightness(32, 31) > brightness(4, 31));
+        assert_eq!(brightness(10, 20), brightness(117, 43));
+
+        let output_path = "visual_test_radial_fade.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_radial_draws_each_pixel_once() {
+        let rect = Rectangle::new(Point::new(2, 2), Size::new(21, 13));
+        let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Radial { steps: 5 });
+
+        // Drawing into a MockDisplay without overdraw panics if the solid fill isn't skipped.
+        let mut display = MockDisplay::<Rgb888>::new();
+        faded.draw(&mut display).unwrap();
+
+        assert_eq!(display.affected_area(), rect);
+        assert_eq!(
+            display.get_pixel(Point::new(12, 8)),
+            Some(Rgb888::new(204, 204, 204))
+        );
+    }
+
+    #[test]
+    fn test_diagonal_fade_band() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(20, 10));
+        let faded = FadedRectangle::new(
+            rect,
+            Rgb888::WHITE,
+            Fading::Diagonal {
+                steps: 4,
+                corner: Corner::BottomRight,
+            },
+        );
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.draw_iter(&faded).unwrap();
+
+        // Triangular band of 4 + 3 + 2 + 1 pixels at the bottom right corner.
+        assert_eq!(faded.into_iter().count(), 10);
+        assert_eq!(display.get_pixel(Point::new(19, 9)), Some(Rgb888::BLACK));
+        assert_eq!(
+            display.get_pixel(Point::new(16, 9)),
+            Some(Rgb888::new(191, 191, 191))
+        );
+        assert_eq!(
+            display.get_pixel(Point::new(19, 6)),
+            Some(Rgb888::new(191, 191, 191))
+        );
+        assert_eq!(
+            display.get_pixel(Point::new(18, 8)),
+            Some(Rgb888::new(127, 127, 127))
+        );
+        assert_eq!(display.get_pixel(Point::new(16, 8)), None);
+    }
+
+    #[test]
+    fn test_diagonal_fade_large_steps_covers_surface() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(8, 6));
+        let faded = FadedRectangle::new(
+            rect,
+            Rgb888::WHITE,
+            Fading::Diagonal {
+                steps: 255,
+                corner: Corner::TopLeft,
+            },
+        );
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.draw_iter(&faded).unwrap();
+
+        assert_eq!(display.affected_area(), rect);
+        let brightness: Vec<u8> = (0..6)
+            .map(|i| display.get_pixel(Point::new(i, i)).unwrap().r())
+            .collect();
+        assert_eq!(brightness[0], 0);
+        assert!(brightness.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn visual_test_diagonal_fade() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let rect = Rectangle::new(Point::new(10, 10), Size::new(200, 100));
+        let base_color = Rgb888::new(180, 200, 255);
+        let fading = Fading::Diagonal {
+            steps: 120,
+            corner: Corner::BottomRight,
+        };
+        FadedRectangle::new(rect, base_color, fading)
+            .draw(&mut display)
+            .unwrap();
+
+        let output_path = "visual_test_diagonal_fade.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_curve_keeps_outermost_step() {
+        let mut display = MockDisplay::<Rgb888>::new();
+
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(8, 1));
+        let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Left { steps: 8 })
+            .with_curve(FadeCurve::EaseIn);
+        display.draw_iter(&faded).unwrap();
+
+        let brightness: Vec<u8> = (0..8)
+            .map(|x| display.get_pixel(Point::new(x, 0)).unwrap().r())
+            .collect();
+        assert_eq!(brightness[0], 0);
+        assert!(brightness.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(brightness, [0, 59, 111, 155, 191, 219, 239, 251]);
+    }
+
+    #[test]
+    fn test_no_fading_draws_solid_rect() {
+        let rect = Rectangle::new(Point::new(2, 3), Size::new(12, 6));
+        let faded = FadedRectangle::new(rect, Rgb888::MAGENTA, Fading::None);
+
+        assert_eq!(faded.into_iter().count(), 0);
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        faded.draw(&mut display).unwrap();
+
+        let mut expected = MockDisplay::<Rgb888>::new();
+        expected.fill_solid(&rect, Rgb888::MAGENTA).unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_no_fading_draw_diff_expanding() {
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+
+        let prev_rect = Rectangle::new(Point::new(20, 0), Size::new(10, 4));
+        FadedRectangle::new(prev_rect, Rgb888::RED, Fading::None)
+            .draw(&mut display)
+            .unwrap();
+
+        let new_rect = Rectangle::new(Point::new(14, 0), Size::new(16, 4));
+        FadedRectangle::new(new_rect, Rgb888::RED, Fading::None)
+            .draw_diff(&mut display, &prev_rect)
+            .unwrap();
+
+        let mut expected = MockDisplay::<Rgb888>::new();
+        expected.fill_solid(&new_rect, Rgb888::RED).unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_percent_fading_resolves_steps() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(10, 30));
+
+        // 25% of 10 px rounds half up to 3 steps, 25% of 30 px to 8 steps.
+        let cases = [
+            (
+                Fading::LeftPercent { percent: 25 },
+                Fading::Left { steps: 3 },
+            ),
+            (
+                Fading::RightPercent { percent: 25 },
+                Fading::Right { steps: 3 },
+            ),
+            (Fading::TopPercent { percent: 25 }, Fading::Top { steps: 8 }),
+            (
+                Fading::BottomPercent { percent: 25 },
+                Fading::Bottom { steps: 8 },
+            ),
+            (Fading::LeftPercent { percent: 0 }, Fading::None),
+        ];
+        for (percent, fixed) in cases {
+            let faded = FadedRectangle::new(rect, Rgb888::WHITE, percent);
+            let reference = FadedRectangle::new(rect, Rgb888::WHITE, fixed);
+
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.set_allow_overdraw(true);
+            faded.draw(&mut display).unwrap();
+
+            let mut expected = MockDisplay::<Rgb888>::new();
+            expected.set_allow_overdraw(true);
+            reference.draw(&mut expected).unwrap();
+
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_percent_fading_draw_diff() {
+        let fading = Fading::LeftPercent { percent: 20 };
+        let rects = [
+            Rectangle::new(Point::new(10, 0), Size::new(50, 4)),
+            Rectangle::new(Point::new(40, 0), Size::new(20, 4)),
+            Rectangle::new(Point::new(0, 0), Size::new(60, 4)),
+        ];
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        FadedRectangle::new(rects[0], Rgb888::GREEN, fading)
+            .draw(&mut display)
+            .unwrap();
+
+        for pair in rects.windows(2) {
+            let faded = FadedRectangle::new(pair[1], Rgb888::GREEN, fading);
+            faded.draw_diff(&mut display, &pair[0]).unwrap();
+
+            let mut expected = MockDisplay::<Rgb888>::new();
+            expected.set_allow_overdraw(true);
+            faded.draw(&mut expected).unwrap();
+
+            for point in pair[1].points() {
+                assert_eq!(display.get_pixel(point), expected.get_pixel(point));
+            }
+        }
+    }
+
+    #[test]
+    fn test_auto_fading_steps() {
+        let cases = [
+            (0, 0),
+            (1, 1),
+            (2, 2),
+            (3, 2),
+            (10, 2),
+            (50, 10),
+            (64, 13),
+            (200, 16),
+        ];
+
+        for (width, steps) in cases {
+            let rect = Rectangle::new(Point::new(0, 0), Size::new(width, 4));
+            assert_eq!(Fading::auto(Edge::Left).resolved_steps(&rect), steps);
+            assert_eq!(Fading::auto(Edge::Right).resolved_steps(&rect), steps);
+            assert_eq!(Fading::auto(Edge::Top).resolved_steps(&rect), 2);
+        }
+
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(4, 50));
+        assert_eq!(Fading::auto(Edge::Bottom).resolved_steps(&rect), 10);
+        assert_eq!(Fading::Left { steps: 3 }.resolved_steps(&rect), 3);
+        assert_eq!(Fading::Left { steps: 7 }.resolved_steps(&rect), 4);
+    }
+
+    #[test]
+    fn test_auto_fading_tiny_rect() {
+        for width in 1..=3 {
+            let rect = Rectangle::new(Point::new(0, 0), Size::new(width, 2));
+            let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::auto(Edge::Left));
+
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.draw_iter(&faded).unwrap();
+
+            assert_eq!(faded.into_iter().count() as u32, width.min(2) * 2);
+            assert_eq!(display.get_pixel(Point::new(0, 0)), Some(Rgb888::BLACK));
+        }
+    }
+
+    #[test]
+    fn test_reversed_fade() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(10, 1));
+        let base_color = Rgb888::new(200, 100, 40);
+
+        for fading in [Fading::Left { steps: 4 }, Fading::Right { steps: 4 }] {
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.set_allow_overdraw(true);
+            FadedRectangle::new(rect, base_color, fading)
+                .with_reversed(true)
+                .draw(&mut display)
+                .unwrap();
+
+            let (outermost, innermost, solid) = match fading {
+                Fading::Left { .. } => (0, 3, 4),
+                _ => (9, 6, 5),
+            };
+            assert_eq!(
+                display.get_pixel(Point::new(outermost, 0)),
+                Some(base_color)
+            );
+            assert_eq!(
+                display.get_pixel(Point::new(innermost, 0)),
+                Some(Rgb888::new(50, 25, 10))
+            );
+            assert_eq!(display.get_pixel(Point::new(solid, 0)), Some(base_color));
+        }
+    }
+
+    #[test]
+    fn test_max_fade_outermost_pixel() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(8, 1));
+        let base_color = Rgb888::new(200, 100, 50);
+
+        for (max_fade, outermost) in [
+            (255, Rgb888::BLACK),
+            (153, Rgb888::new(80, 40, 20)),
+            (102, Rgb888::new(120, 60, 30)),
+            (0, base_color),
+        ] {
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.set_allow_overdraw(true);
+            FadedRectangle::new(rect, base_color, Fading::Left { steps: 4 })
+                .with_max_fade(max_fade)
+                .draw(&mut display)
+                .unwrap();
+
+            assert_eq!(display.get_pixel(Point::new(0, 0)), Some(outermost));
+        }
+    }
+
+    #[test]
+    fn test_max_fade_draw_diff_shrinking() {
+        let prev_rect = Rectangle::new(Point::new(0, 0), Size::new(40, 3));
+        let new_rect = Rectangle::new(Point::new(12, 0), Size::new(28, 3));
+        let faded = |rect| {
+            FadedRectangle::new(rect, Rgb888::CSS_ORANGE, Fading::Left { steps: 6 })
+                .with_max_fade(128)
+        };
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        faded(prev_rect).draw(&mut display).unwrap();
+        faded(new_rect).draw_diff(&mut display, &prev_rect).unwrap();
+
+        let mut expected = MockDisplay::<Rgb888>::new();
+        expected.set_allow_overdraw(true);
+        expected.fill_solid(&prev_rect, Rgb888::BLACK).unwrap();
+        faded(new_rect).draw(&mut expected).unwrap();
+
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_wide_fade_is_monotonic() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(480, 4));
+
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(480, 4));
+        let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Left { steps: 400 });
+        faded.draw(&mut display).unwrap();
+
+        assert_eq!(faded.into_iter().count(), 400 * 4);
+
+        let brightness: Vec<u8> = (0..480)
+            .map(|x| display.get_pixel(Point::new(x, 2)).r())
+            .collect();
+        assert_eq!(brightness[0], 0);
+        assert_eq!(brightness[200], 127);
+        assert!(brightness.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(brightness[400..].iter().all(|&b| b == 255));
+    }
+
+    #[test]
+    fn test_fade_offset() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(12, 1));
+        let base_color = Rgb888::new(200, 100, 40);
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        FadedRectangle::new(rect, base_color, Fading::Left { steps: 5 })
+            .with_fade_offset(2)
+            .draw(&mut display)
+            .unwrap();
+
+        let brightness: Vec<u8> = (0..12)
+            .map(|x| display.get_pixel(Point::new(x, 0)).unwrap().r())
+            .collect();
+        assert_eq!(
+            brightness,
+            [200, 200, 0, 40, 80, 120, 160, 200, 200, 200, 200, 200]
+        );
+    }
+
+    #[test]
+    fn test_fade_offset_clamps_to_rect() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(4, 2));
+        let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Right { steps: 5 })
+            .with_fade_offset(2);
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.draw_iter(&faded).unwrap();
+
+        assert_eq!(display.affected_area(), rect);
+        assert_eq!(display.get_pixel(Point::new(3, 0)), Some(Rgb888::WHITE));
+        assert_eq!(display.get_pixel(Point::new(1, 0)), Some(Rgb888::BLACK));
+    }
+
+    #[test]
+    fn test_fade_offset_draw_diff_expanding() {
+        let prev_rect = Rectangle::new(Point::new(30, 0), Size::new(20, 3));
+        let new_rect = Rectangle::new(Point::new(24, 0), Size::new(26, 3));
+        let faded = |rect| {
+            FadedRectangle::new(rect, Rgb888::GREEN, Fading::Left { steps: 5 }).with_fade_offset(3)
+        };
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        faded(prev_rect).draw(&mut display).unwrap();
+        faded(new_rect).draw_diff(&mut display, &prev_rect).unwrap();
+
+        let mut expected = MockDisplay::<Rgb888>::new();
+        expected.set_allow_overdraw(true);
+        faded(new_rect).draw(&mut expected).unwrap();
+
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_fade_fn_sawtooth() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(16, 2));
+        let sawtooth = |col: u32, _row: u32, _steps: u32| ((col % 4) * 64) as u16;
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        FadedRectangle::with_fade_fn(rect, Rgb888::WHITE, Fading::Right { steps: 8 }, sawtooth)
+            .draw(&mut display)
+            .unwrap();
+
+        let brightness: Vec<u8> = (0..16)
+            .map(|x| display.get_pixel(Point::new(x, 1)).unwrap().r())
+            .collect();
+        assert_eq!(
+            brightness,
+            [255, 255, 255, 255, 255, 255, 255, 255, 255, 191, 127, 63, 255, 191, 127, 63]
+        );
+    }
+
+    #[test]
+    fn test_fade_fn_receives_steps() {
+        fn half(_col: u32, _row: u32, steps: u32) -> u16 {
+            assert_eq!(steps, 3);
+            128
+        }
+
+        let rect = Rectangle::new(Point::new(2, 2), Size::new(6, 6));
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        FadedRectangle::with_fade_fn(rect, Rgb888::WHITE, Fading::Top { steps: 3 }, half)
+            .draw(&mut display)
+            .unwrap();
+
+        assert_eq!(
+            display.get_pixel(Point::new(4, 4)),
+            Some(Rgb888::new(127, 127, 127))
+        );
+        assert_eq!(display.get_pixel(Point::new(4, 5)), Some(Rgb888::WHITE));
+    }
+
+    #[test]
+    fn test_hsv_interpolation_keeps_channel_ratio() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(16, 1));
+        let orange = Rgb888::new(255, 128, 0);
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        FadedRectangle::new(rect, orange, Fading::Left { steps: 8 })
+            .with_interpolation(Interpolation::HsvValue)
+            .draw(&mut display)
+            .unwrap();
+
+        assert_eq!(display.get_pixel(Point::new(0, 0)), Some(Rgb888::BLACK));
+        for x in 1..8 {
+            let color = display.get_pixel(Point::new(x, 0)).unwrap();
+            assert_eq!(color.b(), 0);
+            assert!(color.g().abs_diff(color.r() / 2) <= 1, "{color:?}");
+        }
+    }
+
+    #[test]
+    fn visual_test_fade_targets() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+        display.clear(Rgb888::new(0, 40, 80)).unwrap();
+
+        let white = FadeStyle::with_target(Rgb888::WHITE);
+        let accent = FadeStyle {
+            curve: FadeCurve::EaseOut,
+            ..FadeStyle::with_target(Rgb888::new(0, 40, 80))
+        };
+
+        let top = Rectangle::new(Point::new(10, 10), Size::new(300, 40));
+        let bottom = Rectangle::new(Point::new(10, 60), Size::new(300, 40));
+        FadedRectangle::new(top, Rgb888::CSS_ORANGE, Fading::Horizontal { steps: 40 })
+            .with_style(white)
+            .draw(&mut display)
+            .unwrap();
+        FadedRectangle::new(bottom, Rgb888::CSS_ORANGE, Fading::Horizontal { steps: 40 })
+            .with_style(accent)
+            .draw(&mut display)
+            .unwrap();
+
+        assert_eq!(display.get_pixel(Point::new(10, 20)), Rgb888::WHITE);
+        assert_eq!(
+            display.get_pixel(Point::new(309, 70)),
+            Rgb888::new(0, 40, 80)
+        );
+
+        let output_path = "visual_test_fade_targets.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_default_style_fades_to_black() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(12, 3));
+        let faded = FadedRectangle::new(rect, Rgb888::CSS_TEAL, Fading::Left { steps: 6 });
+        let styled = faded.with_style(FadeStyle::new());
+
+        assert_eq!(faded, styled);
+        assert_eq!(faded.style, FadeStyle::default());
+        assert!(faded.into_iter().eq(styled.into_iter()));
+    }
+
+    #[test]
+    fn test_style_target_draw_diff_shrinking() {
+        let prev_rect = Rectangle::new(Point::new(0, 0), Size::new(30, 3));
+        let new_rect = Rectangle::new(Point::new(10, 0), Size::new(20, 3));
+        let style = FadeStyle::with_target(Rgb888::WHITE);
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        FadedRectangle::new(new_rect, Rgb888::BLUE, Fading::Left { steps: 4 })
+            .with_style(style)
+            .draw_diff(&mut display, &prev_rect)
+            .unwrap();
+
+        assert_eq!(display.get_pixel(Point::new(5, 1)), Some(Rgb888::WHITE));
+        assert_eq!(display.get_pixel(Point::new(10, 1)), Some(Rgb888::WHITE));
+    }
+
+    #[test]
+    fn test_dithered_rgb565_fade() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(128, 8));
+        let faded = FadedRectangle::new(rect, Rgb565::new(31, 63, 31), Fading::Left { steps: 128 })
+            .with_dithered(true);
+
+        let mut display = SimulatorDisplay::<Rgb565>::new(rect.size);
+        faded.draw(&mut display).unwrap();
+
+        // Round trip through Rgb888, the way the panel would show it.
+        let level = |point| Rgb565::from(Rgb888::from(display.get_pixel(point))).g() as i32;
+        // Every column is one fade step, its dithered level summed over the 8 rows.
+        let band = |x| (0..8).map(|y| level(Point::new(x, y))).sum::<i32>();
+        for x in 1..128 {
+            let (left, right) = (band(x - 1), band(x));
+            assert!((left - right).abs() <= 8, "column {x}: {left} -> {right}");
+        }
+
+        // Each 4x4 block averages to the exact fade level.
+        for block in 0..32 {
+            let sum: i32 = (0..16)
+                .map(|i| level(Point::new(block * 4 + i % 4, i / 4)))
+                .sum();
+            let exact = 63 * (block * 4 + 2) * 16 / 128;
+            assert!((sum - exact).abs() <= 16, "block {block}: {sum} vs {exact}");
+        }
+
+        // Deterministic, repeated draws produce the same pixels.
+        assert!(faded.into_iter().eq(faded.into_iter()));
+    }
+
+    #[test]
+    fn test_undithered_matches_default() {
+        let rect = Rectangle::new(Point::new(3, 2), Size::new(40, 4));
+        let faded = FadedRectangle::new(rect, Rgb565::CSS_CORAL, Fading::Horizontal { steps: 12 });
+
+        assert!(faded.into_iter().eq(faded.with_dithered(false).into_iter()));
+        assert!(faded.into_iter().ne(faded.with_dithered(true).into_iter()));
+    }
+
+    #[test]
+    fn test_binary_fade_density() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(32, 16));
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+        BinaryFadedRectangle::binary(rect, Fading::Left { steps: 16 })
+            .draw(&mut display)
+            .unwrap();
+
+        let on_pixels = |columns: core::ops::Range<i32>| {
+            columns
+                .flat_map(|x| (0..16).map(move |y| Point::new(x, y)))
+                .filter(|&point| display.get_pixel(point) == Some(BinaryColor::On))
+                .count() as i32
+        };
+
+        // The outermost step is off, the solid region fully on.
+        assert_eq!(on_pixels(0..1), 0);
+        assert_eq!(on_pixels(16..32), 16 * 16);
+
+        // Densities follow the fade, every column `x` ideally has `x` of 16 pixels on. A single
+        // column only sees 4 of the 16 dither thresholds, so compare 4 column blocks.
+        let mut previous = 0;
+        for block in (0..16).step_by(4) {
+            let on = on_pixels(block..block + 4);
+            let expected = (block..block + 4).sum::<i32>();
+            assert!(
+                (on - expected).abs() <= 8,
+                "columns {block}..: {on} vs {expected}"
+            );
+            assert!(on > previous, "columns {block}..: {on} after {previous}");
+            previous = on;
+        }
+    }
+
+    #[test]
+    fn test_binary_fade_variants() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(24, 24));
+        let variants = [
+            Fading::Bottom { steps: 6 },
+            Fading::Top { steps: 6 },
+            Fading::Right { steps: 6 },
+            Fading::Horizontal { steps: 6 },
+            Fading::Vertical { steps: 6 },
+            Fading::AllEdges { steps: 6 },
+            Fading::LeftPercent { percent: 25 },
+            Fading::auto(Edge::Top),
+        ];
+
+        for fading in variants {
+            let faded = BinaryFadedRectangle::binary(rect, fading);
+            let mut display = MockDisplay::<BinaryColor>::new();
+            display.set_allow_overdraw(true);
+            faded.draw(&mut display).unwrap();
+
+            assert_eq!(
+                display.get_pixel(Point::new(12, 12)),
+                Some(BinaryColor::On),
+                "{fading:?}"
+            );
+            let pixels: Vec<_> = faded.into_iter().collect();
+            assert!(pixels.iter().any(|pixel| pixel.1.is_off()), "{fading:?}");
+            assert!(pixels.iter().any(|pixel| pixel.1.is_on()), "{fading:?}");
+        }
+    }
+
+    #[test]
+    fn test_gray4_fade() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(8, 1));
+        let mut display = MockDisplay::<Gray4>::new();
+        display.set_allow_overdraw(true);
+        FadedRectangle::new(rect, Gray4::WHITE, Fading::Left { steps: 4 })
+            .draw(&mut display)
+            .unwrap();
+
+        let expected = [0, 4, 8, 11, 15, 15, 15, 15];
+        for (x, luma) in expected.into_iter().enumerate() {
+            assert_eq!(
+                display.get_pixel(Point::new(x as i32, 0)),
+                Some(Gray4::new(luma)),
+                "column {x}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_lut_matches_per_pixel_blending() {
+        let rect = Rectangle::new(Point::new(-3, 5), Size::new(70, 41));
+        let variants = [
+            Fading::None,
+            Fading::Bottom { steps: 7 },
+            Fading::Top { steps: 64 },
+            Fading::Left { steps: 1 },
+            Fading::Right { steps: 30 },
+            Fading::Horizontal { steps: 9 },
+            Fading::Vertical { steps: 64 },
+            Fading::AllEdges { steps: 12 },
+            Fading::Radial { steps: 20 },
+            Fading::Diagonal {
+                steps: 50,
+                corner: Corner::BottomRight,
+            },
+            Fading::LeftPercent { percent: 40 },
+            Fading::BottomPercent { percent: 100 },
+            Fading::auto(Edge::Right),
+        ];
+        let styles = [
+            FadeStyle::new(),
+            FadeStyle {
+                curve: FadeCurve::EaseInOut,
+                reversed: true,
+                offset: 3,
+                ..FadeStyle::with_target(Rgb565::CSS_LIGHT_BLUE)
+            },
+            FadeStyle {
+                curve: FadeCurve::Sine,
+                max_fade: 100,
+                interpolation: Interpolation::HsvValue,
+                ..FadeStyle::new()
+            },
+        ];
+
+        for fading in variants {
+            for style in styles {
+                let faded = FadedRectangle::new(rect, Rgb565::CSS_GOLD, fading).with_style(style);
+                let lut = faded.into_iter();
+                assert!(lut.shading.use_lut, "{fading:?}");
+
+                let mut per_pixel = faded.into_iter();
+                per_pixel.shading.use_lut = false;
+                assert!(lut.eq(per_pixel), "{fading:?} {style:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_contiguous_draw_matches_pixel_draw() {
+        let rect = Rectangle::new(Point::new(2, 1), Size::new(40, 23));
+        let variants = [
+            Fading::None,
+            Fading::Bottom { steps: 5 },
+            Fading::Top { steps: 30 },
+            Fading::Left { steps: 8 },
+            Fading::Right { steps: 1 },
+            Fading::Horizontal { steps: 25 },
+            Fading::Vertical { steps: 6 },
+            Fading::AllEdges { steps: 4 },
+            Fading::Radial { steps: 9 },
+            Fading::Diagonal {
+                steps: 14,
+                corner: Corner::TopRight,
+            },
+            Fading::RightPercent { percent: 30 },
+            Fading::auto(Edge::Bottom),
+        ];
+
+        for fading in variants {
+            for offset in [0, 3] {
+                let faded = FadedRectangle::new(rect, Rgb888::CSS_SALMON, fading)
+                    .with_fade_offset(offset)
+                    .with_dithered(offset > 0);
+
+                let mut expected = MockDisplay::<Rgb888>::new();
+                expected.set_allow_overdraw(true);
+                if !matches!(fading, Fading::Radial { .. }) {
+                    expected.fill_solid(&rect, faded.base_color).unwrap();
+                }
+                expected.draw_iter(faded).unwrap();
+
+                let mut display = MockDisplay::<Rgb888>::new();
+                display.set_allow_overdraw(true);
+                faded.draw(&mut display).unwrap();
+
+                display.assert_eq(&expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_runs_tile_rectangle() {
+        let sizes = [
+            Size::new(1, 1),
+            Size::new(7, 3),
+            Size::new(30, 21),
+            Size::new(4, 40),
+        ];
+        let variants = [
+            Fading::None,
+            Fading::Bottom { steps: 5 },
+            Fading::Top { steps: 50 },
+            Fading::Left { steps: 8 },
+            Fading::Right { steps: 3 },
+            Fading::Horizontal { steps: 6 },
+            Fading::Vertical { steps: 40 },
+            Fading::AllEdges { steps: 4 },
+            Fading::Radial { steps: 7 },
+            Fading::Diagonal {
+                steps: 9,
+                corner: Corner::BottomLeft,
+            },
+            Fading::Diagonal {
+                steps: 9,
+                corner: Corner::TopRight,
+            },
+            Fading::TopPercent { percent: 50 },
+        ];
+
+        for size in sizes {
+            for fading in variants {
+                for offset in [0, 2] {
+                    let rect = Rectangle::new(Point::new(3, 4), size);
+                    let faded = FadedRectangle::new(rect, Rgb888::CSS_PLUM, fading)
+                        .with_fade_offset(offset)
+                        .with_fade_target(Rgb888::CSS_NAVY);
+
+                    let mut expected = MockDisplay::<Rgb888>::new();
+                    expected.set_allow_overdraw(true);
+                    faded.draw(&mut expected).unwrap();
+
+                    // Every pixel is covered exactly once, in the color `draw` produces.
+                    let mut display = MockDisplay::<Rgb888>::new();
+                    for (run, color) in faded.runs() {
+                        assert!(!run.is_zero_sized(), "{fading:?} {size:?}");
+                        display.fill_solid(&run, color).unwrap();
+                    }
+                    display.assert_eq(&expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_runs_are_strips() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(40, 10));
+
+        let left = FadedRectangle::new(rect, Rgb888::RED, Fading::Left { steps: 8 });
+        let runs: Vec<_> = left.runs().collect();
+        assert_eq!(runs.len(), 9);
+        assert_eq!(runs[0], (zone(8, 0, 32, 10), Rgb888::RED));
+        assert!(runs[1..]
+            .iter()
+            .all(|(run, _)| run.size == Size::new(1, 10)));
+
+        let bottom = FadedRectangle::new(rect, Rgb888::RED, Fading::Bottom { steps: 4 });
+        let runs: Vec<_> = bottom.runs().collect();
+        assert_eq!(runs.len(), 5);
+        assert!(runs[1..]
+            .iter()
+            .all(|(run, _)| run.size == Size::new(40, 1)));
+    }
+
+    // Counts how often each pixel is written.
+    struct RecordingDisplay {
+        writes: std::collections::HashMap<Point, u32>,
+    }
+
+    impl Dimensions for RecordingDisplay {
+        fn bounding_box(&self) -> Rectangle {
+            Rectangle::new(Point::new(-100, -100), Size::new(400, 400))
+        }
+    }
+
+    impl DrawTarget for RecordingDisplay {
+        type Color = Rgb888;
+        type Error = core::convert::Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            for Pixel(point, _) in pixels {
+                *self.writes.entry(point).or_default() += 1;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_draw_writes_each_pixel_once() {
+        let sizes = [
+            Size::zero(),
+            Size::new(1, 1),
+            Size::new(5, 2),
+            Size::new(33, 17),
+        ];
+        let variants = [
+            Fading::None,
+            Fading::Bottom { steps: 4 },
+            Fading::Top { steps: 200 },
+            Fading::Left { steps: 3 },
+            Fading::Right { steps: 33 },
+            Fading::Horizontal { steps: 20 },
+            Fading::Vertical { steps: 5 },
+            Fading::AllEdges { steps: 6 },
+            Fading::AllEdges { steps: 100 },
+            Fading::Radial { steps: 5 },
+            Fading::Diagonal {
+                steps: 8,
+                corner: Corner::BottomRight,
+            },
+            Fading::Diagonal {
+                steps: 80,
+                corner: Corner::TopLeft,
+            },
+            Fading::LeftPercent { percent: 100 },
+        ];
+
+        for size in sizes {
+            for fading in variants {
+                for offset in [0, 3] {
+                    let rect = Rectangle::new(Point::new(-2, 7), size);
+                    let faded = FadedRectangle::new(rect, Rgb888::CSS_KHAKI, fading)
+                        .with_fade_offset(offset);
+
+                    let mut display = RecordingDisplay {
+                        writes: Default::default(),
+                    };
+                    faded.draw(&mut display).unwrap();
+
+                    assert_eq!(
+                        display.writes.len(),
+                        size.width as usize * size.height as usize
+                    );
+                    assert!(
+                        display
+                            .writes
+                            .iter()
+                            .all(|(point, &count)| count == 1 && rect.contains(*point)),
+                        "{fading:?} {size:?} offset {offset}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_iterator_len() {
+        let sizes = [
+            Size::zero(),
+            Size::new(0, 5),
+            Size::new(1, 1),
+            Size::new(6, 3),
+            Size::new(19, 40),
+        ];
+        let variants = [
+            Fading::None,
+            Fading::Bottom { steps: 2 },
+            Fading::Top { steps: 41 },
+            Fading::Left { steps: 5 },
+            Fading::Right { steps: 300 },
+            Fading::Horizontal { steps: 4 },
+            Fading::Vertical { steps: 30 },
+            Fading::AllEdges { steps: 3 },
+            Fading::AllEdges { steps: 25 },
+            Fading::Radial { steps: 6 },
+            Fading::Diagonal {
+                steps: 4,
+                corner: Corner::TopLeft,
+            },
+            Fading::Diagonal {
+                steps: 30,
+                corner: Corner::BottomRight,
+            },
+            Fading::Diagonal {
+                steps: 500,
+                corner: Corner::TopRight,
+            },
+            Fading::BottomPercent { percent: 60 },
+            Fading::auto(Edge::Left),
+        ];
+
+        for size in sizes {
+            for fading in variants {
+                for offset in [0, 4] {
+                    let rect = Rectangle::new(Point::new(1, 1), size);
+                    let faded =
+                        FadedRectangle::new(rect, Rgb888::CSS_TAN, fading).with_fade_offset(offset);
+
+                    let mut iter = faded.into_iter();
+                    let len = iter.len();
+                    assert_eq!(iter.size_hint(), (len, Some(len)));
+                    assert_eq!(
+                        faded.into_iter().count(),
+                        len,
+                        "{fading:?} {size:?} offset {offset}"
+                    );
+
+                    iter.nth(len / 2);
+                    assert_eq!(iter.len(), len - (len / 2 + 1).min(len));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let rect = Rectangle::new(Point::new(4, -2), Size::new(20, 6));
+        let mut faded = FadedRectangle::new(rect, Rgb888::CSS_PERU, Fading::Right { steps: 3 });
+        assert_eq!(faded.bounding_box(), rect);
+        assert_eq!(Dimensions::bounding_box(&faded), rect);
+
+        faded.translate_mut(Point::new(-10, 5));
+        let moved = Rectangle::new(Point::new(-6, 3), Size::new(20, 6));
+        assert_eq!(faded.bounding_box(), moved);
+        assert_eq!(Dimensions::bounding_box(&faded), moved);
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        faded
+            .translate_mut(Point::new(6, 0))
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(display.affected_area(), moved.translate(Point::new(6, 0)));
+
+        let empty = FadedRectangle::new(
+            Rectangle::new(Point::new(3, 3), Size::zero()),
+            Rgb888::CSS_PERU,
+            Fading::Left { steps: 2 },
+        );
+        assert_eq!(empty.bounding_box().size, Size::zero());
+        assert_eq!(empty.bounding_box().top_left, Point::new(3, 3));
+    }
+
+    #[test]
+    fn test_fade_and_solid_points_partition_rect() {
+        let rect = Rectangle::new(Point::new(-5, 2), Size::new(17, 12));
+        let variants = [
+            Fading::None,
+            Fading::Bottom { steps: 3 },
+            Fading::Top { steps: 12 },
+            Fading::Left { steps: 1 },
+            Fading::Right { steps: 40 },
+            Fading::Horizontal { steps: 5 },
+            Fading::Vertical { steps: 7 },
+            Fading::AllEdges { steps: 2 },
+            Fading::AllEdges { steps: 9 },
+            Fading::Radial { steps: 4 },
+            Fading::Diagonal {
+                steps: 6,
+                corner: Corner::TopRight,
+            },
+            Fading::Diagonal {
+                steps: 25,
+                corner: Corner::BottomLeft,
+            },
+            Fading::LeftPercent { percent: 30 },
+        ];
+
+        for fading in variants {
+            for offset in [0, 2] {
+                let faded =
+                    FadedRectangle::new(rect, Rgb888::CSS_WHEAT, fading).with_fade_offset(offset);
+
+                let fade: Vec<Point> = faded.fade_points().collect();
+                let solid: Vec<Point> = faded.solid_points().collect();
+                assert!(
+                    faded
+                        .into_iter()
+                        .map(|pixel| pixel.0)
+                        .eq(fade.iter().copied()),
+                    "{fading:?}"
+                );
+
+                let mut all: Vec<Point> = fade.iter().chain(&solid).copied().collect();
+                all.sort_by_key(|point| (point.y, point.x));
+                assert!(
+                    all.iter().copied().eq(rect.points()),
+                    "{fading:?} offset {offset}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_color_at_matches_drawn_pixels() {
+        let rect = Rectangle::new(Point::new(2, 3), Size::new(23, 14));
+        let variants = [
+            Fading::None,
+            Fading::Bottom { steps: 4 },
+            Fading::Left { steps: 30 },
+            Fading::Horizontal { steps: 6 },
+            Fading::AllEdges { steps: 5 },
+            Fading::Radial { steps: 8 },
+            Fading::Diagonal {
+                steps: 10,
+                corner: Corner::BottomLeft,
+            },
+            Fading::auto(Edge::Top),
+        ];
+        let styles = [
+            FadeStyle::new(),
+            FadeStyle {
+                curve: FadeCurve::EaseIn,
+                reversed: true,
+                offset: 2,
+                dithered: true,
+                ..FadeStyle::with_target(Rgb888::CSS_DARK_SLATE_GRAY)
+            },
+        ];
+
+        for fading in variants {
+            for style in styles {
+                let faded = FadedRectangle::new(rect, Rgb888::CSS_LIME, fading).with_style(style);
+
+                let mut display = MockDisplay::<Rgb888>::new();
+                faded.draw(&mut display).unwrap();
+
+                let fade: Vec<Point> = faded.fade_points().collect();
+                for point in Rectangle::new(Point::zero(), Size::new(30, 20)).points() {
+                    assert_eq!(faded.color_at(point), display.get_pixel(point), "{point:?}");
+                    assert_eq!(faded.contains_fade(point), fade.contains(&point));
+                }
+                for Pixel(point, color) in faded {
+                    assert_eq!(faded.color_at(point), Some(color));
+                }
+            }
+        }
+
+        let faded = FadedRectangle::new(rect, Rgb888::CSS_LIME, Fading::Left { steps: 4 });
+        assert_eq!(faded.color_at(Point::new(24, 16)), Some(Rgb888::CSS_LIME));
+        assert_eq!(faded.color_at(Point::new(2, 3)), Some(Rgb888::BLACK));
+        assert_eq!(faded.color_at(Point::new(25, 3)), None);
+        assert!(!faded.contains_fade(Point::new(1, 3)));
+    }
+
+    #[test]
+    fn test_iterator_clone_and_fused() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(9, 7));
+        let faded = FadedRectangle::new(rect, Rgb888::CSS_ORCHID, Fading::AllEdges { steps: 2 });
+
+        let mut iter = faded.into_iter();
+        iter.nth(5);
+        let clone = iter.clone();
+        assert_eq!(clone.len(), iter.len());
+        assert!(clone.eq(iter.by_ref()));
+        assert!(format!("{iter:?}").starts_with("FadedRectangleIterator"));
+
+        for _ in 0..10 {
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.len(), 0);
+        }
+
+        let mut empty = FadedRectangle::new(rect, Rgb888::CSS_ORCHID, Fading::None).into_iter();
+        for _ in 0..10 {
+            assert_eq!(empty.next(), None);
+        }
+    }
+
+    #[test]
+    fn test_zero_steps() {
+        let rect = Rectangle::new(Point::new(1, 2), Size::new(12, 5));
+        let variants = [
+            Fading::Left { steps: 0 },
+            Fading::Right { steps: 0 },
+            Fading::Top { steps: 0 },
+            Fading::Bottom { steps: 0 },
+        ];
+
+        let mut expected = MockDisplay::<Rgb888>::new();
+        expected.fill_solid(&rect, Rgb888::CSS_CORNSILK).unwrap();
+
+        for fading in variants {
+            let faded = FadedRectangle::new(rect, Rgb888::CSS_CORNSILK, fading);
+            assert_eq!(faded.into_iter().next(), None, "{fading:?}");
+            assert_eq!(faded.into_iter().len(), 0);
+
+            let mut display = MockDisplay::<Rgb888>::new();
+            faded.draw(&mut display).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_zero_steps_draw_diff() {
+        let previous = Rectangle::new(Point::new(10, 0), Size::new(20, 3));
+        let fading = Fading::Left { steps: 0 };
+
+        // Expanding repaints the uncovered strip in the base color.
+        let expanded = Rectangle::new(Point::new(4, 0), Size::new(26, 3));
+        let mut display = MockDisplay::<Rgb888>::new();
+        FadedRectangle::new(expanded, Rgb888::CSS_CORNSILK, fading)
+            .draw_diff(&mut display, &previous)
+            .unwrap();
+        let mut expected = MockDisplay::<Rgb888>::new();
+        expected
+            .fill_solid(
+                &Rectangle::new(Point::new(4, 0), Size::new(6, 3)),
+                Rgb888::CSS_CORNSILK,
+            )
+            .unwrap();
+        display.assert_eq(&expected);
+
+        // Shrinking clears the strip that is no longer covered.
+        let shrunk = Rectangle::new(Point::new(15, 0), Size::new(15, 3));
+        let mut display = MockDisplay::<Rgb888>::new();
+        FadedRectangle::new(shrunk, Rgb888::CSS_CORNSILK, fading)
+            .draw_diff(&mut display, &previous)
+            .unwrap();
+        let mut expected = MockDisplay::<Rgb888>::new();
+        expected
+            .fill_solid(
+                &Rectangle::new(Point::new(10, 0), Size::new(5, 3)),
+                Rgb888::BLACK,
+            )
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_steps_clamped_to_dimension() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(10, 6));
+        let pixels = |fading| {
+            FadedRectangle::new(rect, Rgb888::WHITE, fading)
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+
+        // The full ramp is spread over the 10 columns, down to a light last column.
+        let left = pixels(Fading::Left { steps: 10 });
+        assert_eq!(left.len(), 60);
+        assert_eq!(left[0].1, Rgb888::BLACK);
+        assert_eq!(left[9].1, Rgb888::new(230, 230, 230));
+        for steps in [11, 20, 1000] {
+            assert_eq!(pixels(Fading::Left { steps }), left, "{steps} steps");
+        }
+
+        let bottom = pixels(Fading::Bottom { steps: 6 });
+        assert_eq!(bottom[0].1, Rgb888::new(213, 213, 213));
+        assert_eq!(bottom[59].1, Rgb888::BLACK);
+        for steps in [7, 60] {
+            assert_eq!(pixels(Fading::Bottom { steps }), bottom, "{steps} steps");
+        }
+
+        let all_edges = pixels(Fading::AllEdges { steps: 3 });
+        assert_eq!(pixels(Fading::AllEdges { steps: 4 }), all_edges);
+        assert_eq!(
+            pixels(Fading::Horizontal { steps: 6 }),
+            pixels(Fading::Horizontal { steps: 5 })
+        );
+        assert_eq!(
+            FadedRectangle::new(rect, Rgb888::WHITE, Fading::Right { steps: 99 })
+                .fading
+                .resolved_steps(&rect),
+            10
+        );
+    }
+
+    #[test]
+    fn test_draw_diff_expand_with_clamped_steps() {
+        let previous = Rectangle::new(Point::new(20, 0), Size::new(8, 3));
+        let rect = Rectangle::new(Point::new(12, 0), Size::new(16, 3));
+        let faded = FadedRectangle::new(rect, Rgb888::CSS_TOMATO, Fading::Left { steps: 40 });
+
+        let mut old = MockDisplay::<Rgb888>::new();
+        old.set_allow_overdraw(true);
+        FadedRectangle::new(previous, Rgb888::CSS_TOMATO, Fading::Left { steps: 40 })
+            .draw(&mut old)
+            .unwrap();
+        faded.draw_diff(&mut old, &previous).unwrap();
+
+        let mut expected = MockDisplay::<Rgb888>::new();
+        faded.draw(&mut expected).unwrap();
+        old.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_mirrored_fades_are_symmetric() {
+        let column = |display: &MockDisplay<Rgb888>, x: i32, height: u32| {
+            (0..height as i32)
+                .map(|y| display.get_pixel(Point::new(x, y)))
+                .collect::<Vec<_>>()
+        };
+
+        for width in [1, 2, 5, 16, 33] {
+            for steps in [1, 3, 8, 16, 40] {
+                let rect = Rectangle::new(Point::new(0, 0), Size::new(width, 3));
+                let draw = |fading| {
+                    let mut display = MockDisplay::<Rgb888>::new();
+                    FadedRectangle::new(rect, Rgb888::CSS_AQUAMARINE, fading)
+                        .draw(&mut display)
+                        .unwrap();
+                    display
+                };
+                let left = draw(Fading::Left { steps });
+                let right = draw(Fading::Right { steps });
+
+                for x in 0..width as i32 {
+                    assert_eq!(
+                        column(&left, x, 3),
+                        column(&right, width as i32 - 1 - x, 3),
+                        "width {width}, {steps} steps, column {x}"
+                    );
+                }
+
+                let rect = Rectangle::new(Point::new(0, 0), Size::new(3, width));
+                let top: Vec<_> =
+                    FadedRectangle::new(rect, Rgb888::CSS_AQUAMARINE, Fading::Top { steps })
+                        .into_iter()
+                        .map(|Pixel(point, color)| (point, color))
+                        .collect();
+                let bottom =
+                    FadedRectangle::new(rect, Rgb888::CSS_AQUAMARINE, Fading::Bottom { steps });
+                for (point, color) in top {
+                    let mirrored = Point::new(point.x, width as i32 - 1 - point.y);
+                    assert_eq!(bottom.color_at(mirrored), Some(color));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_draw_clipped() {
+        let screen = Rectangle::new(Point::zero(), Size::new(64, 64));
+        let size = Size::new(24, 10);
+        let positions = [
+            Point::new(-9, 20),
+            Point::new(50, 20),
+            Point::new(20, -4),
+            Point::new(20, 60),
+            Point::new(-10, -7),
+            Point::new(20, 20),
+        ];
+        let variants = [
+            Fading::Left { steps: 12 },
+            Fading::Bottom { steps: 4 },
+            Fading::AllEdges { steps: 3 },
+            Fading::Radial { steps: 6 },
+            Fading::Diagonal {
+                steps: 9,
+                corner: Corner::TopRight,
+            },
+        ];
+
+        for top_left in positions {
+            for fading in variants {
+                let faded =
+                    FadedRectangle::new(Rectangle::new(top_left, size), Rgb888::CSS_OLIVE, fading);
+
+                // MockDisplay panics on pixels outside of the screen.
+                let mut display = MockDisplay::<Rgb888>::new();
+                faded.draw_clipped(&mut display, &screen).unwrap();
+
+                for point in screen.points() {
+                    assert_eq!(
+                        display.get_pixel(point),
+                        faded.color_at(point),
+                        "{top_left:?} {fading:?}"
+                    );
+                }
+            }
+        }
+
+        // Entirely off-screen rectangles draw nothing.
+        for top_left in [
+            Point::new(-30, 0),
+            Point::new(64, 10),
+            Point::new(5, -10),
+            Point::new(0, 70),
+        ] {
+            let faded = FadedRectangle::new(
+                Rectangle::new(top_left, size),
+                Rgb888::CSS_OLIVE,
+                Fading::Left { steps: 5 },
+            );
+            let mut display = MockDisplay::<Rgb888>::new();
+            faded.draw_clipped(&mut display, &screen).unwrap();
+            assert_eq!(display.affected_area(), Rectangle::zero());
+        }
+    }
+
+    #[test]
+    fn test_draw_partial() {
+        let rect = Rectangle::new(Point::new(4, 6), Size::new(40, 30));
+        let areas = [
+            Rectangle::new(Point::new(0, 0), Size::new(10, 10)),
+            Rectangle::new(Point::new(20, 15), Size::new(5, 5)),
+            Rectangle::new(Point::new(30, 30), Size::new(30, 30)),
+            Rectangle::new(Point::new(4, 6), Size::new(40, 30)),
+            Rectangle::new(Point::new(50, 0), Size::new(10, 10)),
+        ];
+
+        for fading in [Fading::AllEdges { steps: 7 }, Fading::Radial { steps: 12 }] {
+            let faded = FadedRectangle::new(rect, Rgb888::CSS_SIENNA, fading)
+                .with_fade_target(Rgb888::CSS_IVORY);
+            let mut full = MockDisplay::<Rgb888>::new();
+            faded.draw(&mut full).unwrap();
+
+            for area in areas {
+                let mut partial = MockDisplay::<Rgb888>::new();
+                faded.draw_partial(&mut partial, &area).unwrap();
+
+                let overlap = rect.intersection(&area);
+                assert_eq!(partial.affected_area(), overlap);
+                for point in overlap.points() {
+                    assert_eq!(partial.get_pixel(point), full.get_pixel(point), "{point:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate() {
+        let rect = Rectangle::new(Point::new(3, 2), Size::new(14, 9));
+        let by = Point::new(11, 7);
+
+        for fading in [
+            Fading::Right { steps: 4 },
+            Fading::AllEdges { steps: 3 },
+            Fading::Diagonal {
+                steps: 6,
+                corner: Corner::BottomLeft,
+            },
+        ] {
+            let faded = FadedRectangle::new(rect, Rgb888::CSS_STEEL_BLUE, fading);
+            let moved = faded.translate(by);
+            assert_eq!(moved.rect, rect.translate(by));
+            assert_eq!(faded.rect, rect);
+
+            let mut moved_mut = faded;
+            moved_mut.translate_mut(by);
+            assert_eq!(moved_mut, moved);
+
+            let mut original = MockDisplay::<Rgb888>::new();
+            faded.draw(&mut original).unwrap();
+            let mut translated = MockDisplay::<Rgb888>::new();
+            moved.draw(&mut translated).unwrap();
+
+            assert_eq!(
+                translated.affected_area(),
+                original.affected_area().translate(by)
+            );
+            for Pixel(point, color) in moved {
+                assert!(moved.rect.contains(point));
+                assert_eq!(original.get_pixel(point - by), Some(color));
+                assert_eq!(translated.get_pixel(point), Some(color));
+            }
+        }
+    }
+
+    #[test]
+    fn test_resized_keeps_opposite_edge() {
+        let rect = Rectangle::new(Point::new(10, 20), Size::new(30, 16));
+        let resized = |fading, new_len| {
+            FadedRectangle::new(rect, Rgb888::CSS_GREEN, fading)
+                .resized(new_len)
+                .rect
+        };
 
-        // std::process::Command::new("open")
-        //     .arg(output_path)
-        //     .spawn()
-        //     .ok();
+        let left = resized(Fading::Left { steps: 4 }, 12);
+        assert_eq!(left, Rectangle::new(Point::new(28, 20), Size::new(12, 16)));
+        assert_eq!(left.bottom_right(), rect.bottom_right());
+
+        let right = resized(Fading::RightPercent { percent: 10 }, 45);
+        assert_eq!(right, Rectangle::new(Point::new(10, 20), Size::new(45, 16)));
+
+        let top = resized(Fading::auto(Edge::Top), 6);
+        assert_eq!(top, Rectangle::new(Point::new(10, 30), Size::new(30, 6)));
+
+        let bottom = resized(Fading::Bottom { steps: 2 }, 20);
+        assert_eq!(
+            bottom,
+            Rectangle::new(Point::new(10, 20), Size::new(30, 20))
+        );
+
+        let horizontal = resized(Fading::Horizontal { steps: 2 }, 20);
+        assert_eq!(
+            horizontal,
+            Rectangle::new(Point::new(15, 20), Size::new(20, 16))
+        );
+        assert_eq!(horizontal.center(), rect.center());
+
+        let vertical = resized(Fading::Vertical { steps: 2 }, 8);
+        assert_eq!(
+            vertical,
+            Rectangle::new(Point::new(10, 24), Size::new(30, 8))
+        );
+
+        let diagonal = Fading::Diagonal {
+            steps: 3,
+            corner: Corner::TopRight,
+        };
+        assert_eq!(
+            resized(diagonal, 5),
+            Rectangle::new(Point::new(10, 20), Size::new(5, 16))
+        );
+
+        // Shrinking to nothing collapses onto the anchored edge.
+        let empty = resized(Fading::Left { steps: 4 }, 0);
+        assert_eq!(empty, Rectangle::new(Point::new(40, 20), Size::new(0, 16)));
+        let empty = resized(Fading::Top { steps: 4 }, 0);
+        assert_eq!(empty, Rectangle::new(Point::new(10, 36), Size::new(30, 0)));
+        let empty = resized(Fading::None, 0);
+        assert_eq!(empty, Rectangle::new(Point::new(10, 20), Size::new(0, 16)));
     }
 
     #[test]
-    fn test_expanding() {
+    fn test_resize_and_diff() {
+        let mut faded = FadedRectangle::new(
+            Rectangle::new(Point::new(0, 0), Size::new(40, 4)),
+            Rgb888::CSS_GREEN,
+            Fading::Left { steps: 5 },
+        );
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        faded.draw(&mut display).unwrap();
+
+        for new_len in [25, 38, 10, 0, 40] {
+            faded.resize_and_diff(&mut display, new_len).unwrap();
+            assert_eq!(faded.rect.top_left.x + new_len as i32, 40);
+
+            let mut expected = MockDisplay::<Rgb888>::new();
+            expected
+                .fill_solid(
+                    &Rectangle::new(Point::zero(), Size::new(40, 4)),
+                    Rgb888::BLACK,
+                )
+                .unwrap();
+            expected.set_allow_overdraw(true);
+            faded.draw(&mut expected).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_rotated_matches_pixel_rotation() {
+        let rect = Rectangle::new(Point::new(22, 25), Size::new(18, 10));
+        let center = Point::new(30, 31);
+        let fadings = [
+            Fading::Left { steps: 6 },
+            Fading::Right { steps: 6 },
+            Fading::Top { steps: 4 },
+            Fading::Bottom { steps: 4 },
+        ];
+        let rotations = [
+            Rotation::Rotate0,
+            Rotation::Rotate90,
+            Rotation::Rotate180,
+            Rotation::Rotate270,
+        ];
+        for fading in fadings {
+            let faded = FadedRectangle::new(rect, Rgb888::CSS_ORCHID, fading).with_corner_radius(3);
+            for rotation in rotations {
+                let mut display = MockDisplay::new();
+                faded.rotated(rotation, center).draw(&mut display).unwrap();
+                let mut expected = MockDisplay::new();
+                crate::rotate::Rotated::new(faded, rotation, center)
+                    .draw(&mut expected)
+                    .unwrap();
+                display.assert_eq(&expected);
+
+                let back = fading.rotated(rotation).rotated(rotation.inverse());
+                assert_eq!(back, fading);
+            }
+        }
+        assert_eq!(
+            Fading::Left { steps: 3 }.rotated(Rotation::Rotate90),
+            Fading::Top { steps: 3 }
+        );
+    }
+
+    #[test]
+    fn test_rotated_in_place() {
+        let rect = Rectangle::new(Point::new(20, 24), Size::new(21, 12));
+        let faded = FadedRectangle::new(rect, Rgb888::CSS_KHAKI, Fading::Right { steps: 7 });
+        let mut original = MockDisplay::new();
+        faded.draw(&mut original).unwrap();
+        let pixels = || {
+            rect.points()
+                .filter_map(|point| Some(Pixel(point, original.get_pixel(point)?)))
+        };
+
+        for quadrants in 0..4 {
+            let rotation = Rotation::from_quadrants(quadrants);
+            let center = Point::new(25, 33);
+            let mut display = MockDisplay::new();
+            faded.rotated(rotation, center).draw(&mut display).unwrap();
+            let mut expected = MockDisplay::new();
+            expected
+                .draw_iter(pixels().rotated(rotation, center))
+                .unwrap();
+            display.assert_eq(&expected);
+
+            let in_place = faded.rotated_in_place(rotation);
+            assert_eq!(in_place.rect.center(), rect.center());
+            let offset =
+                in_place.rect.top_left - rotate_rectangle(&rect, rotation, center).top_left;
+            let mut display = MockDisplay::new();
+            in_place.draw(&mut display).unwrap();
+            let mut expected = MockDisplay::new();
+            expected
+                .draw_iter(
+                    pixels()
+                        .rotated(rotation, center)
+                        .map(|Pixel(point, color)| Pixel(point + offset, color)),
+                )
+                .unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_stroke_surrounds_fade() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(20, 8));
+        let stroked = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Left { steps: 4 })
+            .with_stroke(Rgb888::RED, 2);
+        assert_eq!(
+            stroked.inner().rect,
+            Rectangle::new(Point::new(2, 2), Size::new(16, 4))
+        );
+
+        // Every pixel is written once, the stroke doesn't overlap the fade.
+        let mut display = MockDisplay::<Rgb888>::new();
+        stroked.draw(&mut display).unwrap();
+        assert_eq!(display.affected_area(), rect);
+
+        for point in rect.points() {
+            let inner = stroked.inner().rect;
+            let expected = if inner.contains(point) {
+                stroked.inner().color_at(point).unwrap()
+            } else {
+                Rgb888::RED
+            };
+            assert_eq!(display.get_pixel(point), Some(expected), "{point:?}");
+        }
+
+        // The fade starts right inside the stroke.
+        assert_eq!(display.get_pixel(Point::new(2, 3)), Some(Rgb888::BLACK));
+        assert_eq!(display.get_pixel(Point::new(6, 3)), Some(Rgb888::WHITE));
+    }
+
+    #[test]
+    fn test_stroke_draw_diff() {
+        let full = Rectangle::new(Point::new(0, 0), Size::new(40, 12));
+        for width in [1, 2] {
+            let stroked = |rect| {
+                FadedRectangle::new(rect, Rgb888::CSS_GOLD, Fading::Left { steps: 3 })
+                    .with_stroke(Rgb888::CSS_NAVY, width)
+            };
+            let mut previous = Rectangle::new(Point::new(10, 0), Size::new(30, 6));
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.set_allow_overdraw(true);
+            display.fill_solid(&full, Rgb888::BLACK).unwrap();
+            stroked(previous).draw(&mut display).unwrap();
+
+            for (x, y, height) in [(16, 0, 6), (4, 2, 8), (30, 2, 10), (0, 5, 7), (20, 0, 6)] {
+                let rect = Rectangle::new(Point::new(x, y), Size::new(40 - x as u32, height));
+                stroked(rect).draw_diff(&mut display, &previous).unwrap();
+                previous = rect;
+
+                let mut expected = MockDisplay::<Rgb888>::new();
+                expected.set_allow_overdraw(true);
+                expected.fill_solid(&full, Rgb888::BLACK).unwrap();
+                stroked(rect).draw(&mut expected).unwrap();
+                display.assert_eq(&expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stroke_draw_diff_moving_edge() {
+        let previous = Rectangle::new(Point::new(10, 0), Size::new(30, 6));
+        let rect = Rectangle::new(Point::new(16, 0), Size::new(24, 6));
+        let stroked = FadedRectangle::new(rect, Rgb888::CSS_GOLD, Fading::Left { steps: 3 })
+            .with_stroke(Rgb888::CSS_NAVY, 1);
+
+        // MockDisplay panics if a pixel is written twice, the new left edge of the stroke
+        // isn't cleared before it is painted.
+        let mut display = MockDisplay::<Rgb888>::new();
+        let dirty = stroked.draw_diff(&mut display, &previous).unwrap();
+        assert_eq!(dirty, Some(display.affected_area()));
+        assert_eq!(
+            dirty,
+            Some(Rectangle::new(Point::new(10, 0), Size::new(10, 6)))
+        );
+        // The stroke is only painted on the moving edge.
+        assert_eq!(display.get_pixel(Point::new(16, 3)), Some(Rgb888::CSS_NAVY));
+        assert_eq!(display.get_pixel(Point::new(10, 3)), Some(Rgb888::BLACK));
+        assert_eq!(display.get_pixel(Point::new(30, 0)), None);
+        assert_eq!(display.get_pixel(Point::new(39, 3)), None);
+    }
+
+    #[test]
+    fn test_frame_leaves_interior_untouched() {
+        let rect = Rectangle::new(Point::new(1, 2), Size::new(12, 9));
+        let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Left { steps: 5 });
+        let frame = faded.frame(2);
+        let interior = rect.offset(-2);
+
+        // MockDisplay panics if a corner is written twice.
+        let mut display = MockDisplay::<Rgb888>::new();
+        frame.draw(&mut display).unwrap();
+
+        let mut iterated = MockDisplay::<Rgb888>::new();
+        iterated.draw_iter(frame).unwrap();
+        iterated.assert_eq(&display);
+
+        for point in rect.points() {
+            if interior.contains(point) {
+                assert_eq!(display.get_pixel(point), None, "{point:?}");
+            } else {
+                assert_eq!(display.get_pixel(point), faded.color_at(point), "{point:?}");
+            }
+        }
+        assert_eq!(frame.into_iter().count(), 12 * 9 - 8 * 5);
+    }
+
+    #[test]
+    fn test_frame_rounded_corners() {
+        let rect = Rectangle::new(Point::new(2, 2), Size::new(14, 10));
+        let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Top { steps: 4 })
+            .with_corner_radius(3);
+        let frame = faded.frame(2);
+        let interior = rect.offset(-2);
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        frame.draw(&mut display).unwrap();
+        let mut iterated = MockDisplay::<Rgb888>::new();
+        iterated.draw_iter(frame).unwrap();
+        iterated.assert_eq(&display);
+
+        // The frame draws the same pixels as the rounded rectangle, corners included.
+        for point in rect.points() {
+            let expected = if interior.contains(point) {
+                None
+            } else {
+                faded.color_at(point)
+            };
+            assert_eq!(display.get_pixel(point), expected, "{point:?}");
+        }
+        for corner in [(2, 2), (15, 2), (2, 11), (15, 11)] {
+            assert_eq!(display.get_pixel(Point::new(corner.0, corner.1)), None);
+        }
+    }
+
+    #[test]
+    fn test_frame_wider_than_rectangle() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(5, 3));
+        let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Top { steps: 2 });
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        faded.frame(4).draw(&mut display).unwrap();
+
+        let mut expected = MockDisplay::<Rgb888>::new();
+        faded.draw(&mut expected).unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_corner_radius_skips_corners() {
+        let rect = Rectangle::new(Point::new(2, 3), Size::new(12, 8));
+        let faded = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Left { steps: 4 })
+            .with_corner_radius(3);
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        faded.draw(&mut display).unwrap();
+
+        let corners = [
+            Point::new(2, 3),
+            Point::new(13, 3),
+            Point::new(2, 10),
+            Point::new(13, 10),
+        ];
+        for corner in corners {
+            assert_eq!(display.get_pixel(corner), None, "{corner:?}");
+            assert_eq!(faded.color_at(corner), None, "{corner:?}");
+        }
+        assert_eq!(
+            display.get_pixel(Point::new(3, 3)),
+            faded.color_at(Point::new(3, 3))
+        );
+
+        // The pixel iterator and the point sets agree with the drawn pixels.
+        let drawn = rect
+            .points()
+            .filter(|point| display.get_pixel(*point).is_some())
+            .count();
+        assert_eq!(
+            faded.fade_points().count() + faded.solid_points().count(),
+            drawn
+        );
+        assert_eq!(faded.into_iter().len(), faded.fade_points().count());
+        assert_eq!(faded.into_iter().count(), faded.fade_points().count());
+        for Pixel(point, color) in faded.into_iter() {
+            assert_eq!(display.get_pixel(point), Some(color));
+        }
+        for point in faded.solid_points() {
+            assert_eq!(display.get_pixel(point), Some(Rgb888::WHITE));
+        }
+    }
+
+    #[test]
+    fn test_corner_radius_clamps() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(10, 4));
+        let pill = FadedRectangle::new(rect, Rgb888::WHITE, Fading::Right { steps: 3 });
+
+        let mut clamped = MockDisplay::<Rgb888>::new();
+        pill.with_corner_radius(100).draw(&mut clamped).unwrap();
+        let mut half = MockDisplay::<Rgb888>::new();
+        pill.with_corner_radius(2).draw(&mut half).unwrap();
+        clamped.assert_eq(&half);
+
+        let mut square = MockDisplay::<Rgb888>::new();
+        pill.draw(&mut square).unwrap();
+        assert_eq!(square.get_pixel(Point::new(0, 0)), Some(Rgb888::WHITE));
+    }
+
+    #[test]
+    fn test_corner_radius_large() {
+        for value in [
+            0,
+            1,
+            2,
+            3,
+            4,
+            15,
+            16,
+            17,
+            1 << 40,
+            u64::from(u32::MAX).pow(2),
+        ] {
+            let (root, value) = (u128::from(isqrt(value)), u128::from(value));
+            assert!(
+                root * root <= value && (root + 1) * (root + 1) > value,
+                "{value}"
+            );
+        }
+
+        // 4 * radius² doesn't fit into a u32 any more.
+        let faded = FadedRectangle::new(
+            Rectangle::new(Point::zero(), Size::new_equal(100_000)),
+            Rgb888::WHITE,
+            Fading::None,
+        )
+        .with_corner_radius(50_000);
+        let mask = CornerMask::new(&faded);
+        assert_eq!(mask.inset(0), 49_776);
+        assert_eq!(mask.inset(49_999), 0);
+    }
+
+    #[test]
+    fn test_corner_radius_draw_diff() {
+        let full = Rectangle::new(Point::new(0, 0), Size::new(40, 10));
+        let background = Rgb888::CSS_DARK_SLATE_GRAY;
+        let bar = |rect| {
+            FadedRectangle::new(rect, Rgb888::CSS_CORAL, Fading::Left { steps: 4 })
+                .with_fade_target(background)
+                .with_corner_radius(4)
+        };
+
+        let mut previous = Rectangle::new(Point::new(12, 0), Size::new(28, 10));
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        display.fill_solid(&full, background).unwrap();
+        bar(previous).draw(&mut display).unwrap();
+
+        for x in [14, 2, 30, 29, 0, 20] {
+            let rect = Rectangle::new(Point::new(x, 0), Size::new(40 - x as u32, 10));
+            bar(rect).draw_diff(&mut display, &previous).unwrap();
+            previous = rect;
+
+            let mut expected = MockDisplay::<Rgb888>::new();
+            expected.set_allow_overdraw(true);
+            expected.fill_solid(&full, background).unwrap();
+            bar(rect).draw(&mut expected).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_draw_diff_cycles_leave_no_residue() {
+        let size = Size::new(64, 12);
+        let background = Rgb888::new(40, 40, 40);
+        let bar = |x: i32| {
+            let rect = Rectangle::new(Point::new(x, 0), Size::new(64 - x as u32, 12));
+            FadedRectangle::new(rect, Rgb888::CSS_ORANGE, Fading::Left { steps: 6 })
+                .with_fade_target(background)
+        };
+
+        let mut display = SimulatorDisplay::<Rgb888>::new(size);
+        display.clear(background).unwrap();
+        bar(32).draw(&mut display).unwrap();
+
+        let mut previous = bar(32).rect;
+        for x in [40, 12, 60, 0, 3, 50, 20, 63, 32] {
+            bar(x).draw_diff(&mut display, &previous).unwrap();
+            previous = bar(x).rect;
+
+            let mut expected = SimulatorDisplay::<Rgb888>::new(size);
+            expected.clear(background).unwrap();
+            bar(x).draw(&mut expected).unwrap();
+            for point in Rectangle::new(Point::zero(), size).points() {
+                assert_eq!(
+                    display.get_pixel(point),
+                    expected.get_pixel(point),
+                    "{x}: {point:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_right_large_shrink() {
         let mut display = SimulatorDisplay::new(Size::new(96, 32));
-        let prev_rect = Rectangle::new(Point::new(20, 0), Size::new(76, 32));
-        let prev_faded = FadedRectangle::new(prev_rect, Rgb888::YELLOW, Fading::Left { steps: 5 });
-        prev_faded.draw(&mut display).unwrap();
+        let prev_rect = Rectangle::new(Point::new(0, 0), Size::new(96, 32));
+        let faded = |rect| FadedRectangle::new(rect, Rgb888::GREEN, Fading::Right { steps: 5 });
+        faded(prev_rect).draw(&mut display).unwrap();
 
-        let before_output = display.to_rgb_output_image(&Default::default());
-        let before_image = before_output.as_image_buffer();
+        let new_rect = Rectangle::new(Point::new(0, 0), Size::new(20, 32));
+        faded(new_rect).draw_diff(&mut display, &prev_rect).unwrap();
 
-        let new_rect = Rectangle::new(Point::new(18, 0), Size::new(78, 32));
-        let new_faded = FadedRectangle::new(new_rect, Rgb888::YELLOW, Fading::Left { steps: 5 });
+        let mut expected = SimulatorDisplay::new(Size::new(96, 32));
+        faded(new_rect).draw(&mut expected).unwrap();
+        for point in prev_rect.points() {
+            assert_eq!(
+                display.get_pixel(point),
+                expected.get_pixel(point),
+                "{point:?}"
+            );
+        }
+    }
 
-        new_faded.draw_diff(&mut display, &prev_rect).unwrap();
+    #[test]
+    fn test_right_expanding() {
+        let mut display = SimulatorDisplay::new(Size::new(96, 32));
+        let prev_rect = Rectangle::new(Point::new(0, 0), Size::new(76, 32));
+        let faded = |rect| FadedRectangle::new(rect, Rgb888::YELLOW, Fading::Right { steps: 5 });
+        faded(prev_rect).draw(&mut display).unwrap();
 
-        let after_output = display.to_rgb_output_image(&Default::default());
-        let after_image = after_output.as_image_buffer();
+        let new_rect = Rectangle::new(Point::new(0, 0), Size::new(78, 32));
+        faded(new_rect).draw_diff(&mut display, &prev_rect).unwrap();
 
-        let combined_width = 96 + 20;
-        let combined_height = 32 * 2 + 30;
+        let mut expected = SimulatorDisplay::new(Size::new(96, 32));
+        faded(new_rect).draw(&mut expected).unwrap();
+        for point in expected.bounding_box().points() {
+            assert_eq!(
+                display.get_pixel(point),
+                expected.get_pixel(point),
+                "{point:?}"
+            );
+        }
+    }
 
-        let mut combined = image::RgbImage::new(combined_width, combined_height);
+    #[test]
+    fn test_right_draw_diff_frames() {
+        let full = Rectangle::new(Point::new(0, 6), Size::new(64, 10));
+        let background = Rgb888::CSS_MIDNIGHT_BLUE;
+        let bar = |width, fading| {
+            let rect = Rectangle::new(full.top_left, Size::new(width, 10));
+            FadedRectangle::new(rect, Rgb888::CSS_AQUA, fading)
+                .with_fade_target(background)
+                .with_corner_radius(3)
+        };
 
-        for pixel in combined.pixels_mut() {
-            *pixel = image::Rgb([32, 32, 32]);
+        for fading in [
+            Fading::Right { steps: 6 },
+            Fading::Left { steps: 4 },
+            Fading::RightPercent { percent: 25 },
+        ] {
+            let mut previous = bar(30, fading).rect;
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.set_allow_overdraw(true);
+            display.fill_solid(&full, background).unwrap();
+            bar(30, fading).draw(&mut display).unwrap();
+
+            for width in [34, 12, 64, 63, 8, 40] {
+                bar(width, fading)
+                    .draw_diff(&mut display, &previous)
+                    .unwrap();
+                previous = bar(width, fading).rect;
+
+                let mut expected = MockDisplay::<Rgb888>::new();
+                expected.set_allow_overdraw(true);
+                expected.fill_solid(&full, background).unwrap();
+                bar(width, fading).draw(&mut expected).unwrap();
+                display.assert_eq(&expected);
+            }
         }
+    }
 
-        for y in 0..32 {
-            for x in 0..96 {
-                let src_pixel = before_image.get_pixel(x, y);
-                combined.put_pixel(x + 10, y + 10, *src_pixel);
+    #[test]
+    fn test_vertical_draw_diff_frames() {
+        let full = Rectangle::new(Point::new(20, 0), Size::new(8, 64));
+        let background = Rgb888::CSS_DARK_GREEN;
+        let fadings = [
+            (Edge::Top, Fading::Top { steps: 10 }),
+            (Edge::Bottom, Fading::Bottom { steps: 10 }),
+            (Edge::Top, Fading::TopPercent { percent: 30 }),
+        ];
+
+        for (anchor, fading) in fadings {
+            // The edge opposite of `anchor` stays in place.
+            let bar = |height: u32| {
+                let y = if anchor == Edge::Top {
+                    64 - height as i32
+                } else {
+                    0
+                };
+                let rect = Rectangle::new(Point::new(20, y), Size::new(8, height));
+                FadedRectangle::new(rect, Rgb888::CSS_LAWN_GREEN, fading)
+                    .with_fade_target(background)
+                    .with_corner_radius(2)
+            };
+
+            let mut previous = bar(32).rect;
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.set_allow_overdraw(true);
+            display.fill_solid(&full, background).unwrap();
+            bar(32).draw(&mut display).unwrap();
+
+            for height in [40, 38, 12, 64, 5, 63, 0, 20] {
+                bar(height).draw_diff(&mut display, &previous).unwrap();
+                previous = bar(height).rect;
+
+                let mut expected = MockDisplay::<Rgb888>::new();
+                expected.set_allow_overdraw(true);
+                expected.fill_solid(&full, background).unwrap();
+                bar(height).draw(&mut expected).unwrap();
+                display.assert_eq(&expected);
             }
         }
+    }
 
-        for y in 0..32 {
-            for x in 0..96 {
-                let src_pixel = after_image.get_pixel(x, y);
-                combined.put_pixel(x + 10, y + 32 + 20, *src_pixel);
+    #[test]
+    fn test_draw_diff_keeps_stacked_bars() {
+        let size = Size::new(64, 110);
+        let bar = |y: i32, x: i32| {
+            let rect = Rectangle::new(Point::new(x, y), Size::new(64 - x as u32, 24));
+            FadedRectangle::new(rect, Rgb888::CSS_TOMATO, Fading::Left { steps: 8 })
+        };
+
+        for y in [40, 80] {
+            let mut display = SimulatorDisplay::<Rgb888>::new(size);
+            let others = [0, 40, 80].map(|other| bar(other, 10));
+            for other in others {
+                other.draw(&mut display).unwrap();
+            }
+
+            let mut previous = bar(y, 10).rect;
+            for x in [30, 4, 50, 0, 20] {
+                bar(y, x).draw_diff(&mut display, &previous).unwrap();
+                previous = bar(y, x).rect;
+
+                let mut expected = SimulatorDisplay::<Rgb888>::new(size);
+                for other in others.iter().filter(|other| other.rect.top_left.y != y) {
+                    other.draw(&mut expected).unwrap();
+                }
+                bar(y, x).draw(&mut expected).unwrap();
+                for point in Rectangle::new(Point::zero(), size).points() {
+                    assert_eq!(
+                        display.get_pixel(point),
+                        expected.get_pixel(point),
+                        "{y} {x}: {point:?}"
+                    );
+                }
             }
         }
+    }
 
-        let output_path = "test_expanding_comparison.png";
-        combined.save(output_path).unwrap();
+    #[test]
+    fn test_draw_diff_height_changes() {
+        let full = Rectangle::new(Point::new(0, 0), Size::new(64, 64));
+        let background = Rgb888::CSS_SLATE_GRAY;
+        let bar = |rect, fading| {
+            FadedRectangle::new(rect, Rgb888::CSS_GOLD, fading)
+                .with_fade_target(background)
+                .with_corner_radius(3)
+        };
+        let frames = [
+            // Taller to shorter, shorter to taller.
+            Rectangle::new(Point::new(8, 10), Size::new(40, 30)),
+            Rectangle::new(Point::new(8, 16), Size::new(40, 18)),
+            Rectangle::new(Point::new(8, 4), Size::new(40, 50)),
+            // Width and height at once.
+            Rectangle::new(Point::new(20, 12), Size::new(30, 20)),
+            Rectangle::new(Point::new(2, 20), Size::new(60, 40)),
+            Rectangle::new(Point::new(30, 0), Size::new(10, 64)),
+        ];
 
-        // std::process::Command::new("open")
-        //     .arg(output_path)
-        //     .spawn()
-        //     .ok();
+        for fading in [
+            Fading::Left { steps: 8 },
+            Fading::Bottom { steps: 6 },
+            Fading::AllEdges { steps: 4 },
+            Fading::RightPercent { percent: 30 },
+        ] {
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.set_allow_overdraw(true);
+            display.fill_solid(&full, background).unwrap();
+            bar(frames[0], fading).draw(&mut display).unwrap();
+
+            for pair in frames.windows(2) {
+                bar(pair[1], fading)
+                    .draw_diff(&mut display, &pair[0])
+                    .unwrap();
+
+                let mut expected = MockDisplay::<Rgb888>::new();
+                expected.set_allow_overdraw(true);
+                expected.fill_solid(&full, background).unwrap();
+                bar(pair[1], fading).draw(&mut expected).unwrap();
+                display.assert_eq(&expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_draw_diff_left_anchored_width_change() {
+        // Only the right edge moves, as if the rectangle was resized with a fixed left edge.
+        let full = Rectangle::new(Point::new(0, 0), Size::new(64, 64));
+        let background = Rgb888::BLACK;
+        let widths = [40, 24, 56, 3, 30, 31];
+
+        for fading in [
+            Fading::Left { steps: 6 },
+            Fading::Right { steps: 6 },
+            Fading::Horizontal { steps: 5 },
+            Fading::LeftPercent { percent: 25 },
+        ] {
+            let bar = |width| {
+                let rect = Rectangle::new(Point::new(4, 8), Size::new(width, 16));
+                FadedRectangle::new(rect, Rgb888::CSS_TOMATO, fading).with_fade_target(background)
+            };
+
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.set_allow_overdraw(true);
+            display.fill_solid(&full, background).unwrap();
+            bar(widths[0]).draw(&mut display).unwrap();
+
+            for pair in widths.windows(2) {
+                bar(pair[1])
+                    .draw_diff(&mut display, &bar(pair[0]).rect)
+                    .unwrap();
+
+                let mut expected = MockDisplay::<Rgb888>::new();
+                expected.set_allow_overdraw(true);
+                expected.fill_solid(&full, background).unwrap();
+                bar(pair[1]).draw(&mut expected).unwrap();
+                display.assert_eq(&expected);
+            }
+        }
+    }
+
+    // Small deterministic generator for randomized tests.
+    struct XorShift(u32);
+
+    impl XorShift {
+        fn below(&mut self, bound: u32) -> u32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            self.0 % bound
+        }
+
+        fn rect(&mut self) -> Rectangle {
+            let (x, y) = (self.below(60), self.below(60));
+            let size = Size::new(1 + self.below(64 - x), 1 + self.below(64 - y));
+            Rectangle::new(Point::new(x as i32, y as i32), size)
+        }
+    }
+
+    #[test]
+    fn test_draw_diff_random_rects() {
+        let full = Rectangle::new(Point::zero(), Size::new(64, 64));
+        let background = Rgb888::CSS_NAVY;
+        let fadings = [
+            Fading::None,
+            Fading::Left { steps: 6 },
+            Fading::Right { steps: 9 },
+            Fading::Top { steps: 3 },
+            Fading::Bottom { steps: 12 },
+            Fading::Horizontal { steps: 5 },
+            Fading::Vertical { steps: 7 },
+            Fading::AllEdges { steps: 4 },
+            Fading::Radial { steps: 8 },
+            Fading::Diagonal {
+                steps: 10,
+                corner: Corner::BottomRight,
+            },
+            Fading::TopPercent { percent: 40 },
+        ];
+
+        let mut random = XorShift(0x2545_f491);
+        for round in 0..300 {
+            let fading = fadings[round % fadings.len()];
+            let radius = random.below(5);
+            let faded = |rect| {
+                FadedRectangle::new(rect, Rgb888::CSS_ORCHID, fading)
+                    .with_fade_target(background)
+                    .with_fade_offset(round as u8 % 3)
+                    .with_corner_radius(radius)
+            };
+            let previous = random.rect();
+            // Every other pair moves and resizes slightly, the others are unrelated.
+            let rect = if round % 2 == 0 {
+                let mut delta = || random.below(9) as i32 - 4;
+                let top_left = previous.top_left + Point::new(delta(), delta());
+                let bottom_right = previous.bottom_right().unwrap() + Point::new(delta(), delta());
+                Rectangle::with_corners(top_left, bottom_right).intersection(&full)
+            } else {
+                random.rect()
+            };
+
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.set_allow_overdraw(true);
+            display.fill_solid(&full, background).unwrap();
+            faded(previous).draw(&mut display).unwrap();
+            faded(rect).draw_diff(&mut display, &previous).unwrap();
+
+            let mut expected = MockDisplay::<Rgb888>::new();
+            expected.set_allow_overdraw(true);
+            expected.fill_solid(&full, background).unwrap();
+            faded(rect).draw(&mut expected).unwrap();
+            for point in full.points() {
+                assert_eq!(
+                    display.get_pixel(point),
+                    expected.get_pixel(point),
+                    "{fading:?} {previous:?} -> {rect:?} at {point:?}"
+                );
+            }
+        }
+    }
+
+    // Fails if a pixel is written twice in different colors.
+    struct SingleColorWrites(MockDisplay<Rgb888>);
+
+    impl OriginDimensions for SingleColorWrites {
+        fn size(&self) -> Size {
+            self.0.size()
+        }
+    }
+
+    impl DrawTarget for SingleColorWrites {
+        type Color = Rgb888;
+        type Error = core::convert::Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            for Pixel(point, color) in pixels {
+                let written = self.0.get_pixel(point);
+                assert!(
+                    written.is_none() || written == Some(color),
+                    "{point:?} written as {written:?} and {color:?}"
+                );
+                self.0.set_pixel(point, Some(color));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_draw_diff_writes_final_colors_only() {
+        let full = Rectangle::new(Point::zero(), Size::new(64, 64));
+        let fadings = [
+            Fading::Left { steps: 6 },
+            Fading::Right { steps: 9 },
+            Fading::Bottom { steps: 12 },
+            Fading::AllEdges { steps: 4 },
+            Fading::Radial { steps: 8 },
+            Fading::Diagonal {
+                steps: 10,
+                corner: Corner::TopLeft,
+            },
+        ];
+
+        let mut random = XorShift(0x1234_5678);
+        for round in 0..200 {
+            let fading = fadings[round % fadings.len()];
+            let radius = random.below(4);
+            let faded = |rect| {
+                FadedRectangle::new(rect, Rgb888::CSS_ORCHID, fading)
+                    .with_fade_target(Rgb888::CSS_NAVY)
+                    .with_corner_radius(radius)
+            };
+            let previous = random.rect();
+            let rect = if round % 2 == 0 {
+                // Expanding in every direction.
+                let mut delta = || random.below(5) as i32;
+                let top_left = previous.top_left - Point::new(delta(), delta());
+                let bottom_right = previous.bottom_right().unwrap() + Point::new(delta(), delta());
+                Rectangle::with_corners(top_left, bottom_right).intersection(&full)
+            } else {
+                random.rect()
+            };
+
+            let mut target = SingleColorWrites(MockDisplay::new());
+            faded(rect).draw_diff(&mut target, &previous).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_draw_diff_full_color_change() {
+        let rect = Rectangle::new(Point::new(4, 4), Size::new(30, 10));
+        let green = FadedRectangle::new(rect, Rgb888::GREEN, Fading::Left { steps: 5 });
+        let red = FadedRectangle {
+            base_color: Rgb888::RED,
+            ..green
+        };
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        green.draw(&mut display).unwrap();
+
+        // The same rectangle is unchanged for `draw_diff`.
+        red.draw_diff(&mut display, &rect).unwrap();
+        assert_eq!(display.get_pixel(Point::new(20, 8)), Some(Rgb888::GREEN));
+
+        red.draw_diff_full(&mut display, &green).unwrap();
+        let mut expected = MockDisplay::<Rgb888>::new();
+        red.draw(&mut expected).unwrap();
+        display.assert_eq(&expected);
+
+        // Geometry and color at once.
+        let shorter = FadedRectangle::new(
+            Rectangle::new(Point::new(14, 4), Size::new(20, 10)),
+            Rgb888::BLUE,
+            red.fading,
+        );
+        shorter.draw_diff_full(&mut display, &red).unwrap();
+        let mut expected = MockDisplay::<Rgb888>::new();
+        expected.fill_solid(&rect, Rgb888::BLACK).unwrap();
+        expected.set_allow_overdraw(true);
+        shorter.draw(&mut expected).unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_draw_diff_dirty_region() {
+        let size = Size::new(64, 40);
+        let cases = [
+            (
+                Rectangle::new(Point::new(10, 8), Size::new(54, 20)),
+                Point::new(30, 8),
+            ),
+            (
+                Rectangle::new(Point::new(30, 8), Size::new(34, 20)),
+                Point::new(27, 8),
+            ),
+            (
+                Rectangle::new(Point::new(30, 8), Size::new(34, 20)),
+                Point::new(4, 8),
+            ),
+            (
+                Rectangle::new(Point::new(4, 20), Size::new(40, 12)),
+                Point::new(4, 14),
+            ),
+        ];
+
+        for fading in [Fading::Left { steps: 5 }, Fading::Top { steps: 4 }] {
+            for (previous, top_left) in cases {
+                let end = previous.bottom_right().unwrap();
+                let rect = Rectangle::with_corners(top_left, end);
+                // The faded edge stays distinct from the background, so every write is visible.
+                let faded =
+                    |rect| FadedRectangle::new(rect, Rgb888::GREEN, fading).with_max_fade(200);
+
+                let mut display = SimulatorDisplay::<Rgb888>::new(size);
+                faded(previous).draw(&mut display).unwrap();
+                let before = display.clone();
+
+                let dirty = faded(rect).draw_diff(&mut display, &previous).unwrap();
+
+                let changed = Rectangle::new(Point::zero(), size)
+                    .points()
+                    .filter(|point| display.get_pixel(*point) != before.get_pixel(*point))
+                    .fold(None, |changed, point| {
+                        union(changed, &Rectangle::new(point, Size::new(1, 1)))
+                    });
+                assert_eq!(dirty, changed, "{fading:?} {previous:?} -> {rect:?}");
+            }
+        }
+
+        let faded = FadedRectangle::new(cases[0].0, Rgb888::GREEN, Fading::None);
+        let mut display = SimulatorDisplay::<Rgb888>::new(size);
+        assert_eq!(faded.draw_diff(&mut display, &cases[0].0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_draw_diff_disjoint() {
+        let previous = Rectangle::new(Point::new(2, 4), Size::new(12, 8));
+        let size = previous.size;
+        let moves = [
+            Point::new(40, 4),
+            Point::new(2, 44),
+            Point::new(36, 40),
+            // Touching but not overlapping.
+            Point::new(14, 4),
+        ];
+        let background = Rgb888::CSS_NAVY;
+
+        for fading in [Fading::Right { steps: 4 }, Fading::Radial { steps: 3 }] {
+            for top_left in moves {
+                let rect = Rectangle::new(top_left, size);
+                let faded = |rect| {
+                    FadedRectangle::new(rect, Rgb888::YELLOW, fading).with_fade_target(background)
+                };
+
+                // Every pixel is written at most once and nothing outside both rectangles.
+                let mut display = MockDisplay::<Rgb888>::new();
+                let dirty = faded(rect).draw_diff(&mut display, &previous).unwrap();
+
+                let mut expected = MockDisplay::<Rgb888>::new();
+                expected.fill_solid(&previous, background).unwrap();
+                faded(rect).draw(&mut expected).unwrap();
+                display.assert_eq(&expected);
+
+                let bounds =
+                    Rectangle::with_corners(previous.top_left, rect.bottom_right().unwrap());
+                assert_eq!(dirty, Some(bounds), "{fading:?} {rect:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_draw_diff_over_background() {
+        let size = Size::new(96, 40);
+        let background = GradientRectangle::new(
+            Rectangle::new(Point::zero(), size),
+            Rgb888::CSS_MIDNIGHT_BLUE,
+            Rgb888::CSS_DARK_MAGENTA,
+            GradientDirection::Vertical,
+        );
+        let bar = |x: i32, width: u32| {
+            let rect = Rectangle::new(Point::new(x, 8), Size::new(width, 20));
+            FadedRectangle::new(rect, Rgb888::CSS_ORANGE, Fading::Right { steps: 6 })
+                .with_corner_radius(4)
+        };
+
+        let mut display = SimulatorDisplay::<Rgb888>::new(size);
+        background.draw(&mut display).unwrap();
+        let mut previous = bar(4, 50);
+        previous.draw(&mut display).unwrap();
+
+        for (x, width) in [(4, 30), (10, 70), (60, 30), (2, 20), (2, 90)] {
+            let faded = bar(x, width);
+            faded
+                .draw_diff_over(&mut display, &previous.rect, &background)
+                .unwrap();
+            previous = faded;
+
+            let mut expected = SimulatorDisplay::<Rgb888>::new(size);
+            background.draw(&mut expected).unwrap();
+            faded.draw(&mut expected).unwrap();
+            for point in background.rect.points() {
+                assert_eq!(
+                    display.get_pixel(point),
+                    expected.get_pixel(point),
+                    "{x} {width} {point:?}"
+                );
+            }
+        }
     }
 }
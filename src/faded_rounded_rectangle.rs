@@ -0,0 +1,360 @@
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Rectangle, RoundedRectangle},
+};
+
+use crate::{
+    color::FadeColor,
+    diff_drawable::DiffDrawable,
+    faded_rectangle::{FadeShading, FadedRectangle, Fading},
+    style::FadeStyle,
+};
+
+/// A [`RoundedRectangle`] with a faded edge.
+///
+/// The fade is laid out on the base rectangle like for a [`FadedRectangle`] and masked by the
+/// rounded outline, so corner pixels on the faded edge get the color of their column (or row).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct FadedRoundedRectangle<C = Rgb888> {
+    pub rounded: RoundedRectangle,
+    pub base_color: C,
+    pub fading: Fading,
+    pub style: FadeStyle<C>,
+}
+
+impl<C> FadedRoundedRectangle<C>
+where
+    C: FadeColor,
+{
+    pub fn new(rounded: RoundedRectangle, base_color: C, fading: Fading) -> Self {
+        Self {
+            rounded,
+            base_color,
+            fading,
+            style: FadeStyle::new(),
+        }
+    }
+
+    pub fn with_style(mut self, style: FadeStyle<C>) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The fade on the base rectangle, before masking.
+    pub fn faded(&self) -> FadedRectangle<C> {
+        self.faded_on(self.rounded.rectangle)
+    }
+
+    fn faded_on(&self, rect: Rectangle) -> FadedRectangle<C> {
+        FadedRectangle::new(rect, self.base_color, self.fading).with_style(self.style)
+    }
+
+    /// Returns the color drawn at `point`, `None` outside of the rounded outline.
+    pub fn color_at(&self, point: Point) -> Option<C> {
+        if !self.rounded.contains(point) {
+            return None;
+        }
+
+        self.faded().color_at(point)
+    }
+
+    /// Redraws the difference to the rounded rectangle drawn on `previous`, with the same corner
+    /// radii, like [`FadedRectangle::draw_diff`]. Pixels that are no longer covered are cleared
+    /// with the style's target color. Only a moving left edge is redrawn incrementally, and only
+    /// if the fade bands along the other edges stay the same; otherwise, and for radial fades,
+    /// the whole rectangle is redrawn. Returns the area that was redrawn, `None` if nothing
+    /// changed.
+    pub fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Rectangle,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let rect = self.rounded.rectangle;
+        if rect == *previous {
+            return Ok(None);
+        }
+
+        let old = RoundedRectangle::new(*previous, self.rounded.corners);
+        let damaged = self.damaged_area(&old);
+
+        for point in old.points() {
+            if damaged.contains(point) && !self.rounded.contains(point) {
+                Pixel(point, self.style.target).draw(target)?;
+            }
+        }
+
+        let shading = FadeShading::new(&self.faded());
+        let pixels = self
+            .rounded
+            .points()
+            .filter(|point| damaged.contains(*point))
+            .map(|point| Pixel(point, shading.color_or_base(point - rect.top_left)));
+        target.draw_iter(pixels)?;
+
+        Ok(Some(damaged))
+    }
+
+    // Area that differs between `old` and the current rounded rectangle.
+    fn damaged_area(&self, old: &RoundedRectangle) -> Rectangle {
+        let (new_rect, old_rect) = (self.rounded.rectangle, old.rectangle);
+        let union = match (new_rect.bottom_right(), old_rect.bottom_right()) {
+            (Some(new_end), Some(old_end)) => Rectangle::with_corners(
+                new_rect.top_left.component_min(old_rect.top_left),
+                new_end.component_max(old_end),
+            ),
+            (Some(_), None) => new_rect,
+            (None, _) => old_rect,
+        };
+
+        // Only a moving left edge is handled incrementally, otherwise everything is redrawn.
+        // The bands along the kept edges must match, radial fades change everywhere.
+        let (new_radii, old_radii) = (self.rounded.confine_radii(), old.confine_radii());
+        let (new_faded, old_faded) = (self.faded(), self.faded_on(old_rect));
+        let (new_bands, old_bands) = (new_faded.edge_bands(), old_faded.edge_bands());
+        let right_kept = !matches!(self.fading, Fading::Radial { .. })
+            && new_bands[1..] == old_bands[1..]
+            && new_rect.bottom_right().map(|p| p.x) == old_rect.bottom_right().map(|p| p.x)
+            && new_rect.top_left.y == old_rect.top_left.y
+            && new_rect.size.height == old_rect.size.height
+            && new_radii.corners.top_right == old_radii.corners.top_right
+            && new_radii.corners.bottom_right == old_radii.corners.bottom_right;
+        if !right_kept {
+            return union;
+        }
+
+        let left_end = |rounded: &RoundedRectangle, band: u32| {
+            let corners = rounded.corners;
+            let width = band
+                .max(corners.top_left.width)
+                .max(corners.bottom_left.width);
+            rounded.rectangle.top_left.x + width as i32
+        };
+        let end = left_end(
+            &new_radii,
+            new_faded.band_width(&new_rect).max(new_bands[0]),
+        )
+        .max(left_end(
+            &old_radii,
+            old_faded.band_width(&old_rect).max(old_bands[0]),
+        ));
+
+        let start = union.top_left.x;
+        let width = (end - start).clamp(0, union.size.width as i32) as u32;
+        Rectangle::new(union.top_left, Size::new(width, union.size.height))
+    }
+}
+
+impl<C> Dimensions for FadedRoundedRectangle<C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.rounded.rectangle
+    }
+}
+
+impl<C> Drawable for FadedRoundedRectangle<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let shading = FadeShading::new(&self.faded());
+        let top_left = self.rounded.rectangle.top_left;
+
+        let pixels = self
+            .rounded
+            .points()
+            .map(|point| Pixel(point, shading.color_or_base(point - top_left)));
+        target.draw_iter(pixels)
+    }
+}
+
+impl<C> DiffDrawable for FadedRoundedRectangle<C>
+where
+    C: FadeColor,
+{
+    type State = Rectangle;
+
+    fn state(&self) -> Self::State {
+        self.rounded.rectangle
+    }
+
+    fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Self::State,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        FadedRoundedRectangle::draw_diff(self, target, previous)
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use crate::faded_rectangle::{Corner, Edge};
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    #[test]
+    fn visual_test_rounded_fade() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let card = RoundedRectangle::with_equal_corners(
+            Rectangle::new(Point::new(20, 20), Size::new(280, 90)),
+            Size::new(16, 16),
+        );
+        FadedRoundedRectangle::new(card, Rgb888::CSS_STEEL_BLUE, Fading::Left { steps: 40 })
+            .draw(&mut display)
+            .unwrap();
+
+        let pill = RoundedRectangle::with_equal_corners(
+            Rectangle::new(Point::new(20, 140), Size::new(280, 40)),
+            Size::new(20, 20),
+        );
+        FadedRoundedRectangle::new(pill, Rgb888::CSS_ORANGE, Fading::Right { steps: 60 })
+            .draw(&mut display)
+            .unwrap();
+
+        let output_path = "visual_test_rounded_fade.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_rounded_fade_masks_rectangle() {
+        let rounded = RoundedRectangle::with_equal_corners(
+            Rectangle::new(Point::new(3, 2), Size::new(20, 12)),
+            Size::new(5, 5),
+        );
+        let faded = FadedRoundedRectangle::new(rounded, Rgb888::WHITE, Fading::Left { steps: 6 });
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        faded.draw(&mut display).unwrap();
+
+        let mut reference = MockDisplay::<Rgb888>::new();
+        faded.faded().draw(&mut reference).unwrap();
+
+        for point in rounded.rectangle.points() {
+            let expected = if rounded.contains(point) {
+                reference.get_pixel(point)
+            } else {
+                None
+            };
+            assert_eq!(display.get_pixel(point), expected, "{point:?}");
+            assert_eq!(faded.color_at(point), expected, "{point:?}");
+        }
+
+        // The rounded corner on the faded edge still starts with the first fade column.
+        let corner = Point::new(4, 4);
+        assert!(rounded.contains(corner));
+        assert_eq!(
+            display.get_pixel(corner),
+            reference.get_pixel(Point::new(4, 8))
+        );
+    }
+
+    #[test]
+    fn test_rounded_draw_diff() {
+        let full = Rectangle::new(Point::new(0, 10), Size::new(48, 14));
+        let radii = Size::new(4, 4);
+        let background = Rgb888::CSS_DIM_GRAY;
+        let bar = |rect| {
+            FadedRoundedRectangle::new(
+                RoundedRectangle::with_equal_corners(rect, radii),
+                Rgb888::CSS_LIME,
+                Fading::Left { steps: 5 },
+            )
+            .with_style(FadeStyle::with_target(background))
+        };
+
+        let mut previous = Rectangle::new(Point::new(20, 10), Size::new(28, 14));
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        display.fill_solid(&full, background).unwrap();
+        bar(previous).draw(&mut display).unwrap();
+
+        for (x, height) in [(26, 14), (2, 14), (40, 14), (0, 14), (10, 8), (30, 14)] {
+            let rect = Rectangle::new(Point::new(x, 10), Size::new(48 - x as u32, height));
+            bar(rect).draw_diff(&mut display, &previous).unwrap();
+            previous = rect;
+
+            let mut expected = MockDisplay::<Rgb888>::new();
+            expected.set_allow_overdraw(true);
+            expected.fill_solid(&full, background).unwrap();
+            bar(rect).draw(&mut expected).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_rounded_draw_diff_every_fading() {
+        let full = Rectangle::new(Point::new(0, 0), Size::new(50, 20));
+        let background = Rgb888::CSS_DIM_GRAY;
+        let fadings = [
+            Fading::None,
+            Fading::Left { steps: 6 },
+            Fading::Right { steps: 6 },
+            Fading::Top { steps: 6 },
+            Fading::Bottom { steps: 6 },
+            Fading::Horizontal { steps: 25 },
+            Fading::Vertical { steps: 4 },
+            Fading::AllEdges { steps: 8 },
+            Fading::Radial { steps: 8 },
+            Fading::Diagonal {
+                steps: 12,
+                corner: Corner::TopLeft,
+            },
+            Fading::Diagonal {
+                steps: 45,
+                corner: Corner::BottomRight,
+            },
+            Fading::LeftPercent { percent: 30 },
+            Fading::RightPercent { percent: 30 },
+            Fading::TopPercent { percent: 30 },
+            Fading::BottomPercent { percent: 30 },
+            Fading::Auto { edge: Edge::Left },
+            Fading::Auto { edge: Edge::Right },
+        ];
+
+        for fading in fadings {
+            let card = |x| {
+                let rect = Rectangle::new(Point::new(x, 0), Size::new(50 - x as u32, 20));
+                FadedRoundedRectangle::new(
+                    RoundedRectangle::with_equal_corners(rect, Size::new(4, 4)),
+                    Rgb888::CSS_LIME,
+                    fading,
+                )
+                .with_style(FadeStyle::with_target(background))
+            };
+
+            // Moves the left edge of a 40x20 card by 10 pixels in both directions.
+            for (from, to) in [(10, 0), (0, 10)] {
+                let mut display = MockDisplay::<Rgb888>::new();
+                display.set_allow_overdraw(true);
+                display.fill_solid(&full, background).unwrap();
+                card(from).draw(&mut display).unwrap();
+                let previous = card(from).rounded.rectangle;
+                card(to).draw_diff(&mut display, &previous).unwrap();
+
+                let mut expected = MockDisplay::<Rgb888>::new();
+                expected.set_allow_overdraw(true);
+                expected.fill_solid(&full, background).unwrap();
+                card(to).draw(&mut expected).unwrap();
+                assert_eq!(display, expected, "{fading:?} {from} -> {to}");
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Rectangle, Triangle},
+};
+
+use crate::{
+    color::FadeColor,
+    faded_rectangle::{FadeShading, FadedRectangle, Fading},
+    style::FadeStyle,
+};
+
+/// A [`Triangle`] with a faded edge.
+///
+/// The fade is laid out on the bounding box like for a [`FadedRectangle`] and applied to the
+/// spans of the triangle's scanline fill.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct FadedTriangle<C = Rgb888> {
+    pub triangle: Triangle,
+    pub base_color: C,
+    pub fading: Fading,
+    pub style: FadeStyle<C>,
+}
+
+impl<C> FadedTriangle<C>
+where
+    C: FadeColor,
+{
+    pub fn new(triangle: Triangle, base_color: C, fading: Fading) -> Self {
+        Self {
+            triangle,
+            base_color,
+            fading,
+            style: FadeStyle::new(),
+        }
+    }
+
+    pub fn with_style(mut self, style: FadeStyle<C>) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The fade on the bounding box, before masking.
+    pub fn faded(&self) -> FadedRectangle<C> {
+        FadedRectangle::new(self.triangle.bounding_box(), self.base_color, self.fading)
+            .with_style(self.style)
+    }
+}
+
+impl<C> Dimensions for FadedTriangle<C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.triangle.bounding_box()
+    }
+}
+
+impl<C> Drawable for FadedTriangle<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let faded = self.faded();
+        let shading = FadeShading::new(&faded);
+        let top_left = faded.rect.top_left;
+
+        let pixels = self
+            .triangle
+            .points()
+            .map(|point| Pixel(point, shading.color_or_base(point - top_left)));
+        target.draw_iter(pixels)
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, primitives::PrimitiveStyle};
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    // Fills the triangle and replaces every filled pixel by the color of the unmasked fade.
+    fn masked_reference(faded: &FadedTriangle<Rgb888>) -> MockDisplay<Rgb888> {
+        let mut filled = MockDisplay::new();
+        filled.set_allow_out_of_bounds_drawing(true);
+        faded
+            .triangle
+            .into_styled(PrimitiveStyle::with_fill(Rgb888::RED))
+            .draw(&mut filled)
+            .unwrap();
+
+        let mut reference = MockDisplay::new();
+        let area = faded.faded().rect.intersection(&filled.bounding_box());
+        for point in area.points() {
+            if filled.get_pixel(point).is_some() {
+                reference.set_pixel(point, faded.faded().color_at(point));
+            }
+        }
+        reference
+    }
+
+    #[test]
+    fn visual_test_faded_triangle() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let play = Triangle::new(
+            Point::new(110, 40),
+            Point::new(110, 200),
+            Point::new(250, 120),
+        );
+        FadedTriangle::new(play, Rgb888::CSS_LIME_GREEN, Fading::Right { steps: 60 })
+            .draw(&mut display)
+            .unwrap();
+
+        let output_path = "visual_test_faded_triangle.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_faded_triangle_matches_masked_fill() {
+        let triangles = [
+            Triangle::new(Point::new(2, 2), Point::new(2, 30), Point::new(40, 16)),
+            Triangle::new(Point::new(50, 3), Point::new(10, 40), Point::new(60, 60)),
+            // Sliver with nearly collinear vertices.
+            Triangle::new(Point::new(1, 1), Point::new(60, 5), Point::new(30, 3)),
+            // Collinear vertices.
+            Triangle::new(Point::new(4, 4), Point::new(20, 20), Point::new(12, 12)),
+        ];
+        let fadings = [
+            Fading::Left { steps: 6 },
+            Fading::Right { steps: 9 },
+            Fading::Top { steps: 3 },
+            Fading::Bottom { steps: 12 },
+        ];
+
+        for triangle in triangles {
+            for fading in fadings {
+                let faded = FadedTriangle::new(triangle, Rgb888::WHITE, fading);
+
+                let mut display = MockDisplay::new();
+                faded.draw(&mut display).unwrap();
+                display.assert_eq(&masked_reference(&faded));
+            }
+        }
+    }
+
+    #[test]
+    fn test_faded_triangle_partially_outside() {
+        let triangle = Triangle::new(Point::new(-20, 10), Point::new(30, -5), Point::new(20, 40));
+        let faded = FadedTriangle::new(triangle, Rgb888::CSS_CYAN, Fading::Left { steps: 30 });
+
+        let mut display = MockDisplay::new();
+        display.set_allow_out_of_bounds_drawing(true);
+        faded.draw(&mut display).unwrap();
+        display.assert_eq(&masked_reference(&faded));
+
+        // The visible part still uses the fade of the whole triangle.
+        let inside = Point::new(0, 12);
+        assert_eq!(display.get_pixel(inside), faded.faded().color_at(inside));
+    }
+}
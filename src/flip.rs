@@ -0,0 +1,303 @@
+use embedded_graphics::{pixelcolor::PixelColor, prelude::*, primitives::Rectangle};
+
+use crate::rotate::{rotate_point, Rotation};
+
+/// Mirroring along the horizontal and/or vertical axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Flip {
+    #[default]
+    None,
+    /// Mirrors left and right.
+    Horizontal,
+    /// Mirrors top and bottom.
+    Vertical,
+    /// Mirrors both axes, the same as a rotation by 180°.
+    Both,
+}
+
+impl Flip {
+    pub(crate) const fn flips_x(self) -> bool {
+        matches!(self, Flip::Horizontal | Flip::Both)
+    }
+
+    pub(crate) const fn flips_y(self) -> bool {
+        matches!(self, Flip::Vertical | Flip::Both)
+    }
+}
+
+// Mirrors `point` at the axes through `sum / 2`, which may lie between two pixels.
+fn mirror(point: Point, flip: Flip, sum: Point) -> Point {
+    let x = if flip.flips_x() {
+        sum.x - point.x
+    } else {
+        point.x
+    };
+    let y = if flip.flips_y() {
+        sum.y - point.y
+    } else {
+        point.y
+    };
+    Point::new(x, y)
+}
+
+/// Mirrors `point` at the axes through `axis_center`.
+pub fn flip_point(point: Point, flip: Flip, axis_center: Point) -> Point {
+    mirror(point, flip, axis_center * 2)
+}
+
+/// A flip followed by a rotation, which covers all eight ways to place a display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Orientation {
+    pub rotation: Rotation,
+    pub flip: Flip,
+}
+
+impl Orientation {
+    pub const fn new(rotation: Rotation, flip: Flip) -> Self {
+        Self { rotation, flip }
+    }
+
+    /// Flips `point` at the axes through `center` and then rotates it around `center`.
+    pub fn transform_point(self, point: Point, center: Point) -> Point {
+        rotate_point(flip_point(point, self.flip, center), self.rotation, center)
+    }
+}
+
+/// A [`DrawTarget`] that mirrors everything drawn through it within the bounds of `parent`,
+/// like [`RotatedDrawTarget`](crate::rotate::RotatedDrawTarget) does for rotations.
+#[derive(Debug)]
+pub struct FlippedDrawTarget<'a, D> {
+    parent: &'a mut D,
+    flip: Flip,
+    sum: Point,
+}
+
+/// A mirrored panel, e.g. behind a teleprompter glass, is a [`FlippedDrawTarget`] with
+/// [`Flip::Horizontal`] or [`Flip::Vertical`].
+pub type Mirrored<'a, D> = FlippedDrawTarget<'a, D>;
+
+impl<'a, D> FlippedDrawTarget<'a, D>
+where
+    D: DrawTarget,
+{
+    pub fn new(parent: &'a mut D, flip: Flip) -> Self {
+        let bounds = parent.bounding_box();
+        let size = Point::new(bounds.size.width as i32, bounds.size.height as i32);
+
+        Self {
+            parent,
+            flip,
+            sum: bounds.top_left * 2 + size - Point::new(1, 1),
+        }
+    }
+
+    /// Position of the logical `point` on the parent.
+    pub fn to_physical(&self, point: Point) -> Point {
+        mirror(point, self.flip, self.sum)
+    }
+}
+
+impl<D> Dimensions for FlippedDrawTarget<'_, D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.parent.bounding_box()
+    }
+}
+
+impl<D, C> DrawTarget for FlippedDrawTarget<'_, D>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (flip, sum) = (self.flip, self.sum);
+        self.parent.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(mirror(point, flip, sum), color)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        // Mirrored rows run backwards, so only unflipped areas keep their order.
+        if self.flip == Flip::None {
+            return self.parent.fill_contiguous(area, colors);
+        }
+
+        let pixels = area
+            .points()
+            .zip(colors)
+            .map(|(point, color)| Pixel(point, color));
+        self.draw_iter(pixels)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        let area = Rectangle::with_corners(
+            self.to_physical(area.top_left),
+            self.to_physical(bottom_right),
+        );
+        self.parent.fill_solid(&area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.parent.clear(color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const ROTATIONS: [Rotation; 4] = [
+        Rotation::Rotate0,
+        Rotation::Rotate90,
+        Rotation::Rotate180,
+        Rotation::Rotate270,
+    ];
+
+    // An "L" with its corner at the origin, a long arm up and a short arm to the right.
+    const L_SHAPE: [Point; 7] = [
+        Point::new(0, -4),
+        Point::new(0, -3),
+        Point::new(0, -2),
+        Point::new(0, -1),
+        Point::new(0, 0),
+        Point::new(1, 0),
+        Point::new(2, 0),
+    ];
+
+    #[test]
+    fn test_orientations() {
+        // Ends of the long and the short arm.
+        let expected = [
+            (Rotation::Rotate0, Flip::None, (0, -4), (2, 0)),
+            (Rotation::Rotate90, Flip::None, (4, 0), (0, 2)),
+            (Rotation::Rotate180, Flip::None, (0, 4), (-2, 0)),
+            (Rotation::Rotate270, Flip::None, (-4, 0), (0, -2)),
+            (Rotation::Rotate0, Flip::Horizontal, (0, -4), (-2, 0)),
+            (Rotation::Rotate90, Flip::Horizontal, (4, 0), (0, -2)),
+            (Rotation::Rotate180, Flip::Horizontal, (0, 4), (2, 0)),
+            (Rotation::Rotate270, Flip::Horizontal, (-4, 0), (0, 2)),
+        ];
+        let center = Point::new(20, 30);
+        let ends = |orientation: Orientation| {
+            let end = |point: Point| orientation.transform_point(point + center, center) - center;
+            (end(L_SHAPE[0]), end(L_SHAPE[6]))
+        };
+        for (rotation, flip, long, short) in expected {
+            let orientation = Orientation::new(rotation, flip);
+            let long = Point::new(long.0, long.1);
+            let short = Point::new(short.0, short.1);
+            assert_eq!(ends(orientation), (long, short), "{orientation:?}");
+        }
+
+        // Vertical and double flips don't add new orientations.
+        let mut distinct = Vec::new();
+        for rotation in ROTATIONS {
+            for flip in [Flip::None, Flip::Horizontal, Flip::Vertical, Flip::Both] {
+                let ends = ends(Orientation::new(rotation, flip));
+                if !distinct.contains(&ends) {
+                    distinct.push(ends);
+                }
+            }
+        }
+        assert_eq!(distinct.len(), 8);
+    }
+
+    #[test]
+    fn test_flipped_draw_target() {
+        let offset = Point::new(5, 10);
+        for flip in [Flip::None, Flip::Horizontal, Flip::Vertical, Flip::Both] {
+            let mut display = MockDisplay::new();
+            let mut flipped = FlippedDrawTarget::new(&mut display, flip);
+            let pixels = L_SHAPE.map(|point| Pixel(point + offset, BinaryColor::On));
+            flipped.draw_iter(pixels).unwrap();
+            let bar = Rectangle::new(Point::new(1, 1), Size::new(3, 2));
+            flipped.fill_solid(&bar, BinaryColor::Off).unwrap();
+
+            let mut expected = MockDisplay::new();
+            let (x, y) = (flip.flips_x(), flip.flips_y());
+            for point in L_SHAPE {
+                let point = point + offset;
+                let point = Point::new(
+                    if x { 63 - point.x } else { point.x },
+                    if y { 63 - point.y } else { point.y },
+                );
+                expected.set_pixel(point, Some(BinaryColor::On));
+            }
+            let bar_x = if x { 60 } else { 1 };
+            let bar_y = if y { 61 } else { 1 };
+            let bar = Rectangle::new(Point::new(bar_x, bar_y), Size::new(3, 2));
+            expected.fill_solid(&bar, BinaryColor::Off).unwrap();
+
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_mirrored_edges() {
+        let mut display = MockDisplay::new();
+        let mut mirrored = Mirrored::new(&mut display, Flip::Horizontal);
+        assert_eq!(mirrored.bounding_box().size, Size::new(64, 64));
+
+        let left_edge = Rectangle::new(Point::new(0, 0), Size::new(2, 5));
+        mirrored.fill_solid(&left_edge, BinaryColor::On).unwrap();
+        let right_edge = Rectangle::new(Point::new(61, 10), Size::new(3, 1));
+        let colors = [BinaryColor::On, BinaryColor::Off, BinaryColor::Off];
+        mirrored.fill_contiguous(&right_edge, colors).unwrap();
+
+        let mut expected = MockDisplay::new();
+        let left_edge = Rectangle::new(Point::new(62, 0), Size::new(2, 5));
+        expected.fill_solid(&left_edge, BinaryColor::On).unwrap();
+        expected.set_pixel(Point::new(2, 10), Some(BinaryColor::On));
+        expected.set_pixel(Point::new(1, 10), Some(BinaryColor::Off));
+        expected.set_pixel(Point::new(0, 10), Some(BinaryColor::Off));
+        display.assert_eq(&expected);
+
+        let mut display = MockDisplay::new();
+        let mut mirrored = Mirrored::new(&mut display, Flip::Vertical);
+        let bottom_edge = Rectangle::new(Point::new(5, 60), Size::new(4, 4));
+        mirrored.fill_solid(&bottom_edge, BinaryColor::On).unwrap();
+
+        let mut expected = MockDisplay::new();
+        let top_edge = Rectangle::new(Point::new(5, 0), Size::new(4, 4));
+        expected.fill_solid(&top_edge, BinaryColor::On).unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_flip_point() {
+        let center = Point::new(3, -1);
+        let point = Point::new(5, 4);
+        assert_eq!(flip_point(point, Flip::None, center), point);
+        assert_eq!(
+            flip_point(point, Flip::Horizontal, center),
+            Point::new(1, 4)
+        );
+        assert_eq!(flip_point(point, Flip::Vertical, center), Point::new(5, -6));
+        assert_eq!(
+            flip_point(point, Flip::Both, center),
+            rotate_point(point, Rotation::Rotate180, center)
+        );
+    }
+}
@@ -0,0 +1,488 @@
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+
+use crate::{
+    color::FadeColor,
+    diff_drawable::DiffDrawable,
+    gradient::{GradientDirection, GradientRectangle},
+    rotate::{rotate_point_deg, sin_cos_q15},
+};
+
+/// An arc gauge, filled clockwise from `start_deg` over the share `value / max` of
+/// `sweep_deg`.
+///
+/// Angles are clockwise from the top, like [`rotate_point_deg`]. The filled part is colored
+/// along the sweep from the first to the second color of `ramp`, the rest of the sweep is
+/// drawn in `track`. The arc is `thickness` pixels wide on the inside of `radius`.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Gauge<C = Rgb888> {
+    pub center: Point,
+    pub radius: u32,
+    pub thickness: u32,
+    pub start_deg: i32,
+    /// At most `360`.
+    pub sweep_deg: u32,
+    /// Values above `max` fill the whole sweep.
+    pub value: u32,
+    pub max: u32,
+    pub ramp: (C, C),
+    pub track: C,
+}
+
+impl<C> Gauge<C>
+where
+    C: FadeColor,
+{
+    /// The track defaults to the start of `ramp`, dimmed towards the default fade target.
+    pub fn new(
+        center: Point,
+        radius: u32,
+        thickness: u32,
+        start_deg: i32,
+        sweep_deg: u32,
+        max: u32,
+        ramp: (C, C),
+    ) -> Self {
+        Self {
+            center,
+            radius,
+            thickness,
+            start_deg,
+            sweep_deg,
+            value: 0,
+            max,
+            ramp,
+            track: ramp.0.fade_towards(C::DEFAULT_TARGET, 192),
+        }
+    }
+
+    pub fn with_value(mut self, value: u32) -> Self {
+        self.value = value;
+        self
+    }
+
+    pub fn with_track(mut self, track: C) -> Self {
+        self.track = track;
+        self
+    }
+
+    fn sweep(&self) -> u32 {
+        self.sweep_deg.min(360)
+    }
+
+    fn start(&self) -> i32 {
+        self.start_deg.rem_euclid(360)
+    }
+
+    /// The filled part of the sweep for `value` in degrees, rounded to the nearest one.
+    pub fn filled_deg(&self, value: u32) -> u32 {
+        if self.max == 0 {
+            return 0;
+        }
+        let value = u64::from(value.min(self.max));
+        let max = u64::from(self.max);
+        ((value * u64::from(self.sweep()) + max / 2) / max) as u32
+    }
+
+    // The offset from `start_deg` of the pixel at `point`, `None` outside the arc.
+    fn offset_at(&self, point: Point) -> Option<u32> {
+        let relative = point - self.center;
+        let distance = i64::from(relative.x).pow(2) + i64::from(relative.y).pow(2);
+        let outer = i64::from(self.radius);
+        let inner = outer - i64::from(self.thickness.min(self.radius));
+        if distance > outer * (outer + 1) || (inner > 0 && distance <= inner * (inner + 1)) {
+            return None;
+        }
+
+        let offset = (angle_of(relative) - self.start()).rem_euclid(360) as u32;
+        (offset < self.sweep()).then_some(offset)
+    }
+
+    fn color_at(&self, offset: u32, filled: u32) -> C {
+        if offset >= filled {
+            return self.track;
+        }
+        let ramp = Rectangle::new(Point::zero(), Size::new(self.sweep(), 1));
+        GradientRectangle::new(
+            ramp,
+            self.ramp.0,
+            self.ramp.1,
+            GradientDirection::Horizontal,
+        )
+        .color_at(offset)
+    }
+
+    // The area covered by the arc between the offsets `from` and `to`.
+    fn sector_bounds(&self, from: u32, to: u32) -> Rectangle {
+        let radius = self.radius as i32;
+        let inner = radius - self.thickness.min(self.radius) as i32;
+        let mut corners: Option<(Point, Point)> = None;
+
+        // The ends of the sector and the outermost points on the axes it crosses.
+        let (start, end) = (self.start() + from as i32, self.start() + to as i32);
+        let quarters = (start.div_euclid(90) + 1..).map(|quarter| quarter * 90);
+        for angle in [start, end]
+            .into_iter()
+            .chain(quarters.take_while(|&a| a < end))
+        {
+            for distance in [inner, radius] {
+                let point = self.center - Point::new(0, distance);
+                let corner = rotate_point_deg(point, angle, self.center);
+                corners = Some(match corners {
+                    Some((min, max)) => (min.component_min(corner), max.component_max(corner)),
+                    None => (corner, corner),
+                });
+            }
+        }
+        corners.map_or(Rectangle::zero(), |(min, max)| {
+            Rectangle::with_corners(min, max).offset(1)
+        })
+    }
+
+    // Draws the pixels of the arc with offsets in `from..to`, returns the written area.
+    fn draw_offsets<D>(
+        &self,
+        target: &mut D,
+        from: u32,
+        to: u32,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let filled = self.filled_deg(self.value);
+        let mut written: Option<(Point, Point)> = None;
+        let pixels = self.sector_bounds(from, to).points().filter_map(|point| {
+            let offset = self
+                .offset_at(point)
+                .filter(|offset| (from..to).contains(offset))?;
+            written = Some(match written {
+                Some((min, max)) => (min.component_min(point), max.component_max(point)),
+                None => (point, point),
+            });
+            Some(Pixel(point, self.color_at(offset, filled)))
+        });
+        target.draw_iter(pixels)?;
+        Ok(written.map(|(min, max)| Rectangle::with_corners(min, max)))
+    }
+
+    /// Sets `value` and redraws the part of the sweep between the old and the new filled
+    /// angle over an earlier drawing.
+    ///
+    /// Returns the bounding box of all written pixels, `None` if nothing changed.
+    pub fn update<D>(&mut self, target: &mut D, value: u32) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let previous = self.filled_deg(self.value);
+        self.value = value;
+        let filled = self.filled_deg(value);
+        if filled == previous {
+            return Ok(None);
+        }
+
+        self.draw_offsets(target, previous.min(filled), previous.max(filled))
+    }
+}
+
+// The clockwise angle from the top of `point` relative to the center, rounded down to whole
+// degrees.
+fn angle_of(point: Point) -> i32 {
+    // Turn counterclockwise into the quarter from the top to the right.
+    let (mut x, mut y) = (point.x, point.y);
+    let mut quarter = 0;
+    while !(x >= 0 && y < 0) && quarter < 4 {
+        (x, y) = (y, -x);
+        quarter += 1;
+    }
+    if quarter == 4 {
+        return 0;
+    }
+
+    // The largest angle whose ray is at or before the point.
+    let (x, y) = (i64::from(x), -i64::from(y));
+    let (mut low, mut high) = (0, 89);
+    while low < high {
+        let middle = (low + high + 1) / 2;
+        let (sin, cos) = sin_cos_q15(middle);
+        if x * i64::from(cos) >= y * i64::from(sin) {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    quarter * 90 + low
+}
+
+impl<C> Dimensions for Gauge<C> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::with_center(self.center, Size::new_equal(self.radius * 2 + 1))
+    }
+}
+
+impl<C> Drawable for Gauge<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let filled = self.filled_deg(self.value);
+        let pixels = self.bounding_box().points().filter_map(|point| {
+            let offset = self.offset_at(point)?;
+            Some(Pixel(point, self.color_at(offset, filled)))
+        });
+        target.draw_iter(pixels)
+    }
+}
+
+/// The state is `value`, the other fields must be the same as in the earlier drawing.
+impl<C> DiffDrawable for Gauge<C>
+where
+    C: FadeColor,
+{
+    type State = u32;
+
+    fn state(&self) -> Self::State {
+        self.value
+    }
+
+    fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Self::State,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let mut drawn = self.with_value(*previous);
+        drawn.update(target, self.value)
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+
+    const CENTER: Point = Point::new(32, 32);
+    const RAMP: (Rgb888, Rgb888) = (Rgb888::GREEN, Rgb888::RED);
+
+    fn gauge(value: u32) -> Gauge {
+        Gauge::new(CENTER, 20, 4, -135, 270, 100, RAMP)
+            .with_track(Rgb888::CSS_DIM_GRAY)
+            .with_value(value)
+    }
+
+    fn draw(gauge: &Gauge) -> MockDisplay<Rgb888> {
+        let mut display = MockDisplay::new();
+        gauge.draw(&mut display).unwrap();
+        display
+    }
+
+    // The pixel `distance` pixels from the center at `degrees`.
+    fn at(degrees: i32, distance: i32) -> Point {
+        rotate_point_deg(CENTER - Point::new(0, distance), degrees, CENTER)
+    }
+
+    // The color of the gauge at `point`, checking that it lies in the arc.
+    fn expected(gauge: &Gauge, point: Point) -> Option<Rgb888> {
+        let offset = gauge.offset_at(point).unwrap();
+        Some(gauge.color_at(offset, gauge.filled_deg(gauge.value)))
+    }
+
+    fn colors(display: &MockDisplay<Rgb888>) -> Vec<Rgb888> {
+        let area = display.affected_area();
+        area.points()
+            .filter_map(|point| display.get_pixel(point))
+            .collect()
+    }
+
+    #[test]
+    fn test_angle_of() {
+        let angles = [
+            (0, -5),
+            (5, -5),
+            (5, 0),
+            (5, 5),
+            (0, 5),
+            (-5, 5),
+            (-5, 0),
+            (-5, -5),
+        ];
+        let angles = angles.map(|(x, y)| angle_of(Point::new(x, y)));
+        assert_eq!(angles, [0, 45, 90, 135, 180, 225, 270, 315]);
+        assert_eq!(angle_of(Point::new(1, -100)), 0);
+        assert_eq!(angle_of(Point::new(-1, -100)), 359);
+        assert_eq!(angle_of(Point::new(100, 1)), 90);
+        assert_eq!(angle_of(Point::zero()), 0);
+
+        for degrees in (0..360).step_by(7) {
+            let point = at(degrees, 1000) - CENTER;
+            assert!(angle_of(point).abs_diff(degrees) <= 1, "{degrees}");
+        }
+    }
+
+    #[test]
+    fn test_gauge_empty() {
+        let display = draw(&gauge(0));
+        assert_eq!(
+            display.affected_area(),
+            Rectangle::new(Point::new(12, 12), Size::new(41, 35))
+        );
+        assert!(colors(&display)
+            .iter()
+            .all(|&color| color == Rgb888::CSS_DIM_GRAY));
+
+        // The gap at the bottom stays empty, the ring is four pixels wide.
+        assert_eq!(display.get_pixel(at(180, 18)), None);
+        let top = (0..=21).map(|distance| display.get_pixel(at(0, distance)).is_some());
+        let top = top.collect::<Vec<_>>();
+        assert_eq!(top.iter().filter(|&&drawn| drawn).count(), 4);
+        assert!(top[17..=20].iter().all(|&drawn| drawn));
+    }
+
+    #[test]
+    fn test_gauge_half() {
+        let gauge = gauge(50);
+        assert_eq!(gauge.filled_deg(50), 135);
+        let display = draw(&gauge);
+
+        // Filled up to the top, the ramp halfway between its ends there.
+        assert_eq!(display.get_pixel(at(-135, 18)), Some(Rgb888::GREEN));
+        let before = at(-10, 18);
+        assert!(gauge.offset_at(before).unwrap() < 135);
+        assert_eq!(display.get_pixel(before), expected(&gauge, before));
+        assert_eq!(display.get_pixel(at(10, 18)), Some(Rgb888::CSS_DIM_GRAY));
+        assert_eq!(display.get_pixel(at(130, 18)), Some(Rgb888::CSS_DIM_GRAY));
+        let middle = Rgb888::GREEN.fade_towards(Rgb888::RED, 128);
+        assert_eq!(gauge.color_at(135, 271), middle);
+
+        // The same pixels as the empty gauge.
+        let empty = draw(&self::gauge(0));
+        assert_eq!(display.affected_area(), empty.affected_area());
+        assert_eq!(colors(&display).len(), colors(&empty).len());
+    }
+
+    #[test]
+    fn test_gauge_full() {
+        for value in [100, 101, u32::MAX] {
+            let display = draw(&gauge(value));
+            let colors = colors(&display);
+            assert!(!colors.contains(&Rgb888::CSS_DIM_GRAY), "{value}");
+            assert_eq!(colors.len(), self::colors(&draw(&gauge(0))).len());
+            assert_eq!(display.get_pixel(at(-135, 18)), Some(Rgb888::GREEN));
+        }
+
+        // The ramp ends close to its last color at the end of the sweep.
+        let gauge = gauge(100);
+        let arc = gauge
+            .bounding_box()
+            .points()
+            .filter_map(|p| gauge.offset_at(p));
+        let last = arc.max().unwrap();
+        assert!(last >= 265);
+        assert!(colors(&draw(&gauge)).contains(&gauge.color_at(last, 270)));
+        assert_eq!(gauge.color_at(269, 270), Rgb888::RED);
+
+        // No maximum never fills.
+        let gauge = Gauge { max: 0, ..gauge };
+        assert!(colors(&draw(&gauge))
+            .iter()
+            .all(|&color| color == Rgb888::CSS_DIM_GRAY));
+    }
+
+    #[test]
+    fn test_gauge_update() {
+        let mut gauge = gauge(25);
+        let mut display = draw(&gauge);
+        display.set_allow_overdraw(true);
+
+        for value in [75, 10, 100, 0, 60] {
+            let dirty = gauge.update(&mut display, value).unwrap().unwrap();
+            display.assert_eq(&draw(&gauge));
+            assert_eq!(gauge.update(&mut display, value).unwrap(), None);
+            assert_eq!(dirty.intersection(&gauge.bounding_box()), dirty);
+        }
+
+        // Only the delta is drawn, every pixel once.
+        let mut delta = MockDisplay::new();
+        let dirty = gauge.update(&mut delta, 85).unwrap();
+        assert_eq!(dirty, Some(delta.affected_area()));
+        assert_eq!(delta.get_pixel(at(-100, 18)), None);
+        assert_eq!(delta.get_pixel(at(60, 18)), expected(&gauge, at(60, 18)));
+        assert_eq!(delta.get_pixel(at(130, 18)), None);
+
+        let arc = gauge
+            .bounding_box()
+            .points()
+            .filter_map(|p| gauge.offset_at(p));
+        let delta_offsets = arc.filter(|offset| (162..230).contains(offset));
+        assert_eq!(colors(&delta).len(), delta_offsets.count());
+    }
+
+    #[test]
+    fn test_gauge_wraps() {
+        // From 300° over the top to 60°, half filled up to the top.
+        let gauge = Gauge::new(CENTER, 20, 4, 300, 120, 10, RAMP)
+            .with_track(Rgb888::CSS_DIM_GRAY)
+            .with_value(5);
+        let display = draw(&gauge);
+        assert_eq!(display.get_pixel(at(301, 18)), Some(Rgb888::GREEN));
+        assert_eq!(
+            display.get_pixel(at(330, 18)),
+            expected(&gauge, at(330, 18))
+        );
+        assert_ne!(display.get_pixel(at(330, 18)), Some(Rgb888::CSS_DIM_GRAY));
+        assert_eq!(display.get_pixel(at(30, 18)), Some(Rgb888::CSS_DIM_GRAY));
+        assert_eq!(display.get_pixel(at(90, 18)), None);
+        assert_eq!(display.get_pixel(at(270, 18)), None);
+
+        for start_deg in [-60, 660, -420] {
+            draw(&Gauge { start_deg, ..gauge }).assert_eq(&display);
+        }
+        let extreme = Gauge {
+            start_deg: i32::MIN,
+            ..gauge
+        };
+        draw(&extreme).assert_eq(&draw(&Gauge {
+            start_deg: 232,
+            ..gauge
+        }));
+
+        let mut gauge = gauge;
+        let mut display = display;
+        display.set_allow_overdraw(true);
+        let dirty = gauge.update(&mut display, 10).unwrap().unwrap();
+        display.assert_eq(&draw(&gauge));
+        assert!(dirty.top_left.x >= CENTER.x - 1);
+
+        // A full circle has no gap.
+        let ring = Gauge {
+            sweep_deg: 400,
+            ..gauge
+        };
+        let display = draw(&ring);
+        assert!([0, 90, 180, 270]
+            .iter()
+            .all(|&angle| display.get_pixel(at(angle, 18)).is_some()));
+    }
+
+    #[test]
+    fn test_gauge_draw_diff() {
+        let mut display = draw(&gauge(25));
+        display.set_allow_overdraw(true);
+
+        let mut previous = gauge(25).state();
+        for value in [75, 10, 100, 0, 60] {
+            let dirty = gauge(value).draw_diff(&mut display, &previous).unwrap();
+            assert!(dirty.is_some());
+            display.assert_eq(&draw(&gauge(value)));
+            previous = gauge(value).state();
+        }
+        assert_eq!(gauge(60).draw_diff(&mut display, &60).unwrap(), None);
+    }
+}
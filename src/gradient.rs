@@ -0,0 +1,238 @@
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{rectangle, Rectangle},
+};
+
+use crate::color::FadeColor;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum GradientDirection {
+    /// From `start_color` at the left edge to `end_color` at the right edge.
+    #[default]
+    Horizontal,
+    /// From `start_color` at the top edge to `end_color` at the bottom edge.
+    Vertical,
+}
+
+/// A rectangle filled with a linear gradient across its full width or height.
+///
+/// The first and last column (or row) are exactly `start_color` and `end_color`. A rectangle
+/// that is a single pixel wide (or tall) in the gradient direction is drawn in `start_color`.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: crate::serde_helpers::RawColor"))]
+pub struct GradientRectangle<C = Rgb888> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::rectangle"))]
+    pub rect: Rectangle,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::color"))]
+    pub start_color: C,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::color"))]
+    pub end_color: C,
+    pub direction: GradientDirection,
+}
+
+impl<C> GradientRectangle<C>
+where
+    C: FadeColor,
+{
+    pub const fn new(
+        rect: Rectangle,
+        start_color: C,
+        end_color: C,
+        direction: GradientDirection,
+    ) -> Self {
+        Self {
+            rect,
+            start_color,
+            end_color,
+            direction,
+        }
+    }
+
+    /// Color of the gradient at `position` pixels from the start edge.
+    pub fn color_at(&self, position: u32) -> C {
+        let length = match self.direction {
+            GradientDirection::Horizontal => self.rect.size.width,
+            GradientDirection::Vertical => self.rect.size.height,
+        };
+        if length <= 1 {
+            return self.start_color;
+        }
+
+        let position = position.min(length - 1) as u64;
+        let factor_256 = position * 256 / (length as u64 - 1);
+
+        self.start_color
+            .fade_towards(self.end_color, factor_256 as u16)
+    }
+
+    /// Colors of all pixels in row-major order, as expected by `DrawTarget::fill_contiguous`.
+    pub fn colors(&self) -> GradientColors<C> {
+        GradientColors {
+            gradient: *self,
+            points: self.rect.points(),
+        }
+    }
+}
+
+impl<C> Dimensions for GradientRectangle<C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.rect
+    }
+}
+
+impl<C> Drawable for GradientRectangle<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        target.fill_contiguous(&self.rect, self.colors())
+    }
+}
+
+/// Iterator over the pixel colors of a [`GradientRectangle`].
+#[derive(Clone, Debug)]
+pub struct GradientColors<C = Rgb888> {
+    gradient: GradientRectangle<C>,
+    points: rectangle::Points,
+}
+
+impl<C> Iterator for GradientColors<C>
+where
+    C: FadeColor,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.points.next()? - self.gradient.rect.top_left;
+        let position = match self.gradient.direction {
+            GradientDirection::Horizontal => point.x,
+            GradientDirection::Vertical => point.y,
+        };
+
+        Some(self.gradient.color_at(position as u32))
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb565};
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    #[test]
+    fn visual_test_gradient() {
+        let mut display = SimulatorDisplay::<Rgb888>::new(Size::new(320, 240));
+
+        let sky = Rectangle::new(Point::new(0, 0), Size::new(320, 160));
+        GradientRectangle::new(
+            sky,
+            Rgb888::new(10, 30, 90),
+            Rgb888::new(250, 180, 120),
+            GradientDirection::Vertical,
+        )
+        .draw(&mut display)
+        .unwrap();
+
+        let battery = Rectangle::new(Point::new(10, 180), Size::new(300, 40));
+        GradientRectangle::new(
+            battery,
+            Rgb888::RED,
+            Rgb888::GREEN,
+            GradientDirection::Horizontal,
+        )
+        .draw(&mut display)
+        .unwrap();
+
+        let output_path = "visual_test_gradient.png";
+        display
+            .to_rgb_output_image(&Default::default())
+            .save_png(output_path)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_gradient_ends_and_midpoint() {
+        let rect = Rectangle::new(Point::new(2, 1), Size::new(9, 3));
+        let gradient = GradientRectangle::new(
+            rect,
+            Rgb888::new(0, 255, 100),
+            Rgb888::new(255, 0, 100),
+            GradientDirection::Horizontal,
+        );
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        gradient.draw(&mut display).unwrap();
+
+        for y in 1..4 {
+            assert_eq!(
+                display.get_pixel(Point::new(2, y)),
+                Some(Rgb888::new(0, 255, 100))
+            );
+            assert_eq!(
+                display.get_pixel(Point::new(6, y)),
+                Some(Rgb888::new(127, 127, 100))
+            );
+            assert_eq!(
+                display.get_pixel(Point::new(10, y)),
+                Some(Rgb888::new(255, 0, 100))
+            );
+        }
+        assert_eq!(display.affected_area(), rect);
+    }
+
+    #[test]
+    fn test_vertical_gradient_ends_and_midpoint() {
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(2, 5));
+        let gradient = GradientRectangle::new(
+            rect,
+            Rgb565::BLACK,
+            Rgb565::WHITE,
+            GradientDirection::Vertical,
+        );
+
+        let mut display = MockDisplay::<Rgb565>::new();
+        gradient.draw(&mut display).unwrap();
+
+        assert_eq!(display.get_pixel(Point::new(1, 0)), Some(Rgb565::BLACK));
+        assert_eq!(
+            display.get_pixel(Point::new(1, 2)),
+            Some(Rgb565::new(15, 31, 15))
+        );
+        assert_eq!(display.get_pixel(Point::new(1, 4)), Some(Rgb565::WHITE));
+    }
+
+    #[test]
+    fn test_single_pixel_gradient() {
+        let column = Rectangle::new(Point::new(0, 0), Size::new(1, 4));
+        let gradient = GradientRectangle::new(
+            column,
+            Rgb888::RED,
+            Rgb888::BLUE,
+            GradientDirection::Horizontal,
+        );
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        gradient.draw(&mut display).unwrap();
+        assert!(gradient.colors().all(|color| color == Rgb888::RED));
+
+        let empty = GradientRectangle::new(
+            Rectangle::new(Point::zero(), Size::zero()),
+            Rgb888::RED,
+            Rgb888::BLUE,
+            GradientDirection::Vertical,
+        );
+        assert_eq!(empty.colors().count(), 0);
+        assert_eq!(empty.bounding_box().size, Size::zero());
+    }
+}
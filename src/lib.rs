@@ -1,6 +1,120 @@
 #![cfg_attr(not(test), no_std)]
 
+pub mod bar_chart;
+pub mod bar_group;
+pub mod color;
+pub mod compass;
+pub mod curve;
+pub mod diff_drawable;
+pub mod diff_renderer;
+pub mod faded_ellipse;
+pub mod faded_polyline;
 pub mod faded_rectangle;
+pub mod faded_rounded_rectangle;
+pub mod faded_triangle;
+pub mod flip;
+pub mod gauge;
+pub mod gradient;
+pub mod line_graph;
+pub mod needle;
+pub mod prelude;
+pub mod progress_bar;
 pub mod rotate;
+pub mod scale;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+pub mod signal_bars;
+pub mod sparkline;
+pub mod spectrum;
+pub mod style;
+pub mod transform;
+pub mod vu_meter;
 
-pub use faded_rectangle::{FadedRectangle, Fading};
+pub use bar_chart::BarChart;
+pub use bar_group::BarGroup;
+pub use color::{FadeColor, Interpolation};
+pub use compass::Compass;
+pub use curve::{FadeCurve, FadeProfile};
+pub use diff_drawable::DiffDrawable;
+pub use diff_renderer::DiffRenderer;
+pub use faded_ellipse::FadedEllipse;
+pub use faded_polyline::FadedPolyline;
+pub use faded_rectangle::{
+    BinaryFadedRectangle, Corner, Edge, FadedFrame, FadedRectangle, FadedRectangleColors,
+    FadedRectangleRuns, FadedRectangleWith, Fading, StrokedFadedRectangle,
+};
+pub use faded_rounded_rectangle::FadedRoundedRectangle;
+pub use faded_triangle::FadedTriangle;
+pub use gauge::Gauge;
+pub use gradient::{GradientDirection, GradientRectangle};
+pub use line_graph::LineGraph;
+pub use needle::Needle;
+pub use progress_bar::{ProgressBar, SegmentedProgressBar};
+pub use signal_bars::SignalBars;
+pub use sparkline::Sparkline;
+pub use spectrum::Spectrum;
+pub use style::FadeStyle;
+pub use vu_meter::{MeterArray, PeakHold, VuMeter};
+
+// Fails to compile if a public type is missing its `defmt::Format` implementation.
+#[cfg(all(test, feature = "defmt"))]
+mod defmt_tests {
+    use embedded_graphics::pixelcolor::Rgb888;
+
+    use crate::{
+        color::Hsv,
+        flip::{Flip, Orientation},
+        rotate::{InvalidAngle, Rotated, Rotation},
+        transform::{Sheared, Transform2D, Transformed},
+        *,
+    };
+
+    fn assert_format<T: defmt::Format>() {}
+
+    #[test]
+    fn test_public_types_implement_format() {
+        assert_format::<BarGroup<Rgb888, 3>>();
+        assert_format::<Interpolation>();
+        assert_format::<Compass>();
+        assert_format::<Hsv>();
+        assert_format::<FadeCurve>();
+        assert_format::<FadeProfile>();
+        assert_format::<DiffRenderer>();
+        assert_format::<FadedEllipse>();
+        assert_format::<FadedPolyline>();
+        assert_format::<Fading>();
+        assert_format::<Edge>();
+        assert_format::<Corner>();
+        assert_format::<FadedRectangle>();
+        assert_format::<StrokedFadedRectangle>();
+        assert_format::<FadedFrame>();
+        assert_format::<FadedRectangleWith<Rgb888, fn(u32, u32, u32) -> u16>>();
+        assert_format::<FadedRoundedRectangle>();
+        assert_format::<FadedTriangle>();
+        assert_format::<GradientDirection>();
+        assert_format::<GradientRectangle>();
+        assert_format::<Needle>();
+        assert_format::<ProgressBar>();
+        assert_format::<SegmentedProgressBar>();
+        assert_format::<VuMeter>();
+        assert_format::<PeakHold>();
+        assert_format::<MeterArray<Rgb888, 2>>();
+        assert_format::<Spectrum<Rgb888, 16>>();
+        assert_format::<SignalBars>();
+        assert_format::<Sparkline<8>>();
+        assert_format::<BarChart>();
+        assert_format::<LineGraph<[i32; 4]>>();
+        assert_format::<Gauge>();
+        assert_format::<FadeStyle<Rgb888>>();
+        assert_format::<InvalidAngle>();
+        assert_format::<Rotation>();
+        assert_format::<Rotated<FadedRectangle>>();
+        assert_format::<Flip>();
+        assert_format::<Orientation>();
+        assert_format::<Transform2D>();
+        assert_format::<Transformed<FadedRectangle>>();
+        assert_format::<Sheared<FadedRectangle>>();
+        #[cfg(feature = "libm")]
+        assert_format::<rotate::AngleRotated<FadedRectangle>>();
+    }
+}
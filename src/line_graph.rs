@@ -0,0 +1,365 @@
+use core::{iter::Map, ops::Range};
+
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+
+use crate::{
+    color::FadeColor,
+    gradient::{GradientDirection, GradientRectangle},
+};
+
+/// A line graph of `samples`, spread evenly over the width of `bounds` and linearly
+/// interpolated between them, with a vertical gradient from `line_color` at the top of
+/// `bounds` to `background` at the bottom filling the area below the line.
+///
+/// The samples can be any cloneable iterator, see [`LineGraph::from_fn`] for samples computed
+/// from their index. Without a `range` the lowest sample maps to the bottom and the highest
+/// one to the top of `bounds`, constant samples give a flat line in the middle. With a
+/// `range`, samples outside of it are clamped.
+///
+/// The graph is drawn one column at a time and covers all of `bounds`, so it can be drawn
+/// over an earlier graph.
+#[derive(Copy, Clone, Debug)]
+pub struct LineGraph<S, C = Rgb888> {
+    pub bounds: Rectangle,
+    pub samples: S,
+    pub range: Option<(i32, i32)>,
+    pub line_color: C,
+    /// `1` or `2` pixels, a thicker line extends downwards.
+    pub thickness: u32,
+    pub background: C,
+}
+
+// The samples can't be formatted.
+#[cfg(feature = "defmt")]
+impl<S, C> defmt::Format for LineGraph<S, C>
+where
+    C: defmt::Format,
+{
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "LineGraph {{ bounds: {}, range: {}, line_color: {}, thickness: {}, background: {}, .. }}",
+            self.bounds,
+            self.range,
+            self.line_color,
+            self.thickness,
+            self.background
+        )
+    }
+}
+
+impl<F, C> LineGraph<Map<Range<usize>, F>, C>
+where
+    F: Fn(usize) -> i32 + Clone,
+    C: FadeColor,
+{
+    /// A graph of `len` samples returned by `sample` for each index.
+    pub fn from_fn(bounds: Rectangle, len: usize, sample: F, line_color: C) -> Self {
+        Self::new(bounds, (0..len).map(sample), line_color)
+    }
+}
+
+impl<S, C> LineGraph<S, C>
+where
+    S: IntoIterator<Item = i32> + Clone,
+    C: FadeColor,
+{
+    pub fn new(bounds: Rectangle, samples: S, line_color: C) -> Self {
+        Self {
+            bounds,
+            samples,
+            range: None,
+            line_color,
+            thickness: 1,
+            background: C::DEFAULT_TARGET,
+        }
+    }
+
+    pub fn with_range(mut self, low: i32, high: i32) -> Self {
+        self.range = Some((low, high));
+        self
+    }
+
+    pub fn with_thickness(mut self, thickness: u32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    pub fn with_background(mut self, background: C) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// The range mapped to the height of `bounds`, lowest value first.
+    pub fn y_range(&self) -> (i32, i32) {
+        match self.range {
+            Some((a, b)) => (a.min(b), a.max(b)),
+            None => {
+                let samples = self.samples.clone().into_iter();
+                samples.fold(None, |range, sample| match range {
+                    None => Some((sample, sample)),
+                    Some((low, high)) => Some((sample.min(low), sample.max(high))),
+                })
+            }
+            .unwrap_or((0, 0)),
+        }
+    }
+
+    /// The row `value` is plotted at.
+    pub fn value_to_y(&self, value: i32) -> i32 {
+        self.scaled_to_y(self.y_range(), i128::from(value), 1)
+    }
+
+    // The row of `value / scale`, rounded to the nearest one.
+    fn scaled_to_y(&self, (low, high): (i32, i32), value: i128, scale: i128) -> i32 {
+        let rows = i128::from(self.bounds.size.height.saturating_sub(1));
+        let span = i128::from(high) - i128::from(low);
+        let offset = if span == 0 {
+            rows / 2
+        } else {
+            let value = value.clamp(i128::from(low) * scale, i128::from(high) * scale);
+            ((i128::from(high) * scale - value) * rows + span * scale / 2) / (span * scale)
+        };
+        self.bounds.top_left.y + offset as i32
+    }
+
+    /// The row of the line in every column of `bounds`, from left to right.
+    ///
+    /// Sample `i` of `n` lies in column `i * (width - 1) / (n - 1)`, the columns in between
+    /// are interpolated. No samples give no rows.
+    pub fn line_rows(&self) -> impl Iterator<Item = i32> + '_ {
+        let range = self.y_range();
+        let count = self.samples.clone().into_iter().count() as u64;
+        let width = if count == 0 {
+            0
+        } else {
+            self.bounds.size.width
+        };
+        let scale = u64::from(width.saturating_sub(1)).max(1);
+
+        let mut samples = self.samples.clone().into_iter();
+        let mut index = 0;
+        let mut start = samples.next().unwrap_or(0);
+        let mut end = samples.next().unwrap_or(start);
+
+        (0..width).map(move |column| {
+            let position = u64::from(column) * (count - 1);
+            while index < position / scale {
+                start = end;
+                end = samples.next().unwrap_or(start);
+                index += 1;
+            }
+            let fraction = i128::from(position % scale);
+            let scale = i128::from(scale);
+            let value =
+                i128::from(start) * scale + (i128::from(end) - i128::from(start)) * fraction;
+            self.scaled_to_y(range, value, scale)
+        })
+    }
+}
+
+impl<S, C> Dimensions for LineGraph<S, C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+impl<S, C> Drawable for LineGraph<S, C>
+where
+    S: IntoIterator<Item = i32> + Clone,
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let Rectangle { top_left, size } = self.bounds;
+        let bottom = top_left.y + size.height as i32 - 1;
+        let fill = GradientRectangle::new(
+            self.bounds,
+            self.line_color,
+            self.background,
+            GradientDirection::Vertical,
+        );
+        let extra = self.thickness.clamp(1, 2) as i32 - 1;
+
+        let mut rows = self.line_rows();
+        let Some(first) = rows.next() else {
+            return target.fill_solid(&self.bounds, self.background);
+        };
+
+        // Steep segments are drawn as vertical runs reaching up to the row next to the line
+        // in the previous column.
+        let mut previous = first;
+        for (x, row) in (top_left.x..).zip(core::iter::once(first).chain(rows)) {
+            let (line_top, line_bottom) = match row.cmp(&previous) {
+                core::cmp::Ordering::Less => (row, previous - 1),
+                core::cmp::Ordering::Equal => (row, row),
+                core::cmp::Ordering::Greater => (previous + 1, row),
+            };
+            let line_bottom = (line_bottom + extra).min(bottom);
+            previous = row;
+
+            let column = Rectangle::new(Point::new(x, top_left.y), Size::new(1, size.height));
+            let colors = (top_left.y..=bottom).map(|y| {
+                if y < line_top {
+                    self.background
+                } else if y <= line_bottom {
+                    self.line_color
+                } else {
+                    fill.color_at((y - top_left.y) as u32)
+                }
+            });
+            target.fill_contiguous(&column, colors)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use crate::rotate::sin_cos_q15;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    const BOUNDS: Rectangle = Rectangle::new(Point::new(2, 3), Size::new(31, 21));
+
+    // The row of `value` following the mapping formula.
+    fn expected_row(value: i32, (low, high): (i32, i32)) -> i32 {
+        let value = value.clamp(low, high);
+        let (rows, span) = (BOUNDS.size.height as i32 - 1, high - low);
+        BOUNDS.top_left.y + ((high - value) * rows + span / 2) / span
+    }
+
+    #[test]
+    fn test_line_graph_mapping() {
+        let samples = [0, 10, 20, 5];
+        let graph = LineGraph::new(BOUNDS, samples, Rgb888::WHITE);
+        assert_eq!(graph.y_range(), (0, 20));
+
+        // The samples lie in every tenth column.
+        let rows = graph.line_rows().collect::<Vec<_>>();
+        assert_eq!(rows.len(), 31);
+        for (index, sample) in samples.into_iter().enumerate() {
+            assert_eq!(rows[index * 10], expected_row(sample, (0, 20)), "{index}");
+            assert_eq!(rows[index * 10], graph.value_to_y(sample));
+        }
+        // In between the rows are interpolated.
+        assert_eq!(rows[5], expected_row(5, (0, 20)));
+        assert_eq!(rows[13], expected_row(13, (0, 20)));
+        // 12.5 rounds towards the bottom.
+        assert_eq!(rows[25], BOUNDS.top_left.y + 8);
+
+        // A configured range clamps the samples.
+        let graph = graph.with_range(15, 5);
+        assert_eq!(graph.y_range(), (5, 15));
+        let rows = graph.line_rows().collect::<Vec<_>>();
+        for (index, sample) in samples.into_iter().enumerate() {
+            assert_eq!(rows[index * 10], expected_row(sample, (5, 15)), "{index}");
+        }
+        assert_eq!(rows[0], BOUNDS.top_left.y + 20);
+        assert_eq!(rows[20], BOUNDS.top_left.y);
+    }
+
+    #[test]
+    fn test_line_graph_from_fn() {
+        let graph = LineGraph::from_fn(BOUNDS, 7, |index| (index * index) as i32, Rgb888::WHITE);
+        assert_eq!(graph.y_range(), (0, 36));
+        let rows = graph.line_rows().collect::<Vec<_>>();
+        for index in 0..7 {
+            let sample = (index * index) as i32;
+            assert_eq!(rows[index * 5], expected_row(sample, (0, 36)), "{index}");
+        }
+
+        // Constant and single samples give a flat line in the middle, no samples no line.
+        let middle = BOUNDS.top_left.y + 10;
+        let constant = LineGraph::from_fn(BOUNDS, 4, |_| -7, Rgb888::WHITE);
+        assert!(constant.line_rows().all(|row| row == middle));
+        let single = LineGraph::new(BOUNDS, [i32::MIN], Rgb888::WHITE);
+        assert_eq!(single.line_rows().count(), 31);
+        assert!(single.line_rows().all(|row| row == middle));
+        let empty = LineGraph::new(BOUNDS, [0; 0], Rgb888::WHITE);
+        assert_eq!(empty.line_rows().count(), 0);
+
+        // Every pixel of the bounds is drawn once, also without samples.
+        for thickness in [1, 2] {
+            let mut display = MockDisplay::new();
+            graph
+                .clone()
+                .with_thickness(thickness)
+                .draw(&mut display)
+                .unwrap();
+            assert_eq!(display.affected_area(), BOUNDS);
+        }
+        let mut display = MockDisplay::new();
+        empty.draw(&mut display).unwrap();
+        assert_eq!(display.affected_area(), BOUNDS);
+    }
+
+    #[test]
+    fn test_line_graph_simulator() {
+        let size = Size::new(128, 64);
+        let bounds = Rectangle::new(Point::new(4, 4), Size::new(120, 56));
+        let (line, background) = (Rgb888::CSS_CYAN, Rgb888::CSS_MIDNIGHT_BLUE);
+        let wave = |index: usize| sin_cos_q15(index as i32 * 30).0 / 100;
+        let graph = LineGraph::from_fn(bounds, 25, wave, line)
+            .with_thickness(2)
+            .with_background(background);
+
+        let mut display = SimulatorDisplay::<Rgb888>::new(size);
+        graph.draw(&mut display).unwrap();
+
+        let fill = GradientRectangle::new(bounds, line, background, GradientDirection::Vertical);
+        let rows = graph.line_rows().collect::<Vec<_>>();
+        for (x, row) in bounds.columns().zip(rows.iter().copied()) {
+            let column = bounds.rows().map(|y| display.get_pixel(Point::new(x, y)));
+            let column = column.collect::<Vec<_>>();
+            let top = column.iter().position(|&color| color == line).unwrap();
+            let bottom = column.iter().rposition(|&color| color == line).unwrap();
+
+            // Background above a contiguous line of at least two pixels, the gradient below.
+            assert!(
+                column[..top].iter().all(|&color| color == background),
+                "{x}"
+            );
+            assert!(
+                column[top..=bottom].iter().all(|&color| color == line),
+                "{x}"
+            );
+            let last = bounds.size.height as usize - 1;
+            assert!(bottom > top || bottom == last, "{x}");
+            assert!(
+                (top..=bottom).contains(&((row - bounds.top_left.y) as usize)),
+                "{x}"
+            );
+            for (offset, &color) in column.iter().enumerate().skip(bottom + 1) {
+                assert_eq!(color, fill.color_at(offset as u32), "{x} {offset}");
+            }
+        }
+
+        // Neighboring columns of the line touch.
+        let display = &display;
+        for x in bounds.columns().skip(1) {
+            let lines = |x| {
+                let rows = bounds
+                    .rows()
+                    .filter(move |&y| display.get_pixel(Point::new(x, y)) == line);
+                (rows.clone().min().unwrap(), rows.max().unwrap())
+            };
+            let (top, bottom) = lines(x);
+            let (previous_top, previous_bottom) = lines(x - 1);
+            assert!(
+                top <= previous_bottom + 1 && previous_top <= bottom + 1,
+                "{x}"
+            );
+        }
+
+        // Both ends of the wave reach the full height.
+        assert_eq!(rows.iter().min(), Some(&bounds.top_left.y));
+        assert_eq!(rows.iter().max(), Some(&(bounds.top_left.y + 55)));
+    }
+}
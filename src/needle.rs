@@ -0,0 +1,173 @@
+use embedded_graphics::{
+    pixelcolor::{PixelColor, Rgb888},
+    prelude::*,
+    primitives::{Line, PrimitiveStyle, Rectangle, Triangle},
+};
+
+use crate::rotate::rotate_point_deg;
+
+/// A clock hand or gauge needle from `center` outwards, pointing up at 0° and turning
+/// clockwise, see [`rotate_point_deg`].
+///
+/// A needle that is one pixel thick is drawn as a line, thicker needles as a triangle that is
+/// `thickness` wide at the center and tapers to the tip.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: crate::serde_helpers::RawColor"))]
+pub struct Needle<C = Rgb888> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::point"))]
+    pub center: Point,
+    pub length: u32,
+    pub thickness: u32,
+    pub degrees: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::color"))]
+    pub color: C,
+}
+
+impl<C> Needle<C>
+where
+    C: PixelColor,
+{
+    pub const fn new(center: Point, length: u32, thickness: u32, degrees: i32, color: C) -> Self {
+        Self {
+            center,
+            length,
+            thickness,
+            degrees,
+            color,
+        }
+    }
+
+    /// The pixel the needle points to.
+    pub fn tip(&self) -> Point {
+        self.rotated(Point::new(0, -(self.length as i32)))
+    }
+
+    // Rotates `offset` from the center by the needle's angle.
+    fn rotated(&self, offset: Point) -> Point {
+        rotate_point_deg(self.center + offset, self.degrees, self.center)
+    }
+
+    fn triangle(&self) -> Triangle {
+        let half = (self.thickness / 2) as i32;
+        Triangle::new(
+            self.rotated(Point::new(-half, 0)),
+            self.rotated(Point::new(self.thickness as i32 - 1 - half, 0)),
+            self.tip(),
+        )
+    }
+}
+
+/// The angle of an hour hand at `hours:minutes`, rounded down to whole degrees.
+pub const fn clock_angle(hours: u32, minutes: u32) -> i32 {
+    ((hours % 12 * 60 + minutes % 60) / 2) as i32
+}
+
+/// The angle of a minute hand at `minutes`.
+pub const fn minute_angle(minutes: u32) -> i32 {
+    (minutes % 60 * 6) as i32
+}
+
+impl<C> Dimensions for Needle<C>
+where
+    C: PixelColor,
+{
+    fn bounding_box(&self) -> Rectangle {
+        if self.thickness <= 1 {
+            return Line::new(self.center, self.tip()).bounding_box();
+        }
+        self.triangle().bounding_box()
+    }
+}
+
+impl<C> Drawable for Needle<C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        match self.thickness {
+            0 => Ok(()),
+            1 => Line::new(self.center, self.tip())
+                .into_styled(PrimitiveStyle::with_stroke(self.color, 1))
+                .draw(target),
+            _ => self
+                .triangle()
+                .into_styled(PrimitiveStyle::with_fill(self.color))
+                .draw(target),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    #[test]
+    fn test_needle_tip() {
+        let center = Point::new(30, 30);
+        let expected = [
+            (0, (30, 10)),
+            (90, (50, 30)),
+            (180, (30, 50)),
+            (270, (10, 30)),
+            (-90, (10, 30)),
+            (30, (40, 13)),
+            (45, (44, 16)),
+            (135, (44, 44)),
+            (300, (13, 20)),
+        ];
+        for (degrees, (x, y)) in expected {
+            let needle = Needle::new(center, 20, 1, degrees, BinaryColor::On);
+            assert_eq!(needle.tip(), Point::new(x, y), "{degrees}");
+        }
+    }
+
+    #[test]
+    fn test_needle_draw() {
+        let center = Point::new(30, 30);
+        let line = Needle::new(center, 20, 1, 90, BinaryColor::On);
+        let mut display = MockDisplay::new();
+        line.draw(&mut display).unwrap();
+        let mut expected = MockDisplay::new();
+        let row = Rectangle::new(center, Size::new(21, 1));
+        expected.fill_solid(&row, BinaryColor::On).unwrap();
+        display.assert_eq(&expected);
+        assert_eq!(line.bounding_box(), row);
+
+        let hand = Needle::new(center, 20, 6, 180, BinaryColor::On);
+        let mut display = MockDisplay::new();
+        hand.draw(&mut display).unwrap();
+        assert_eq!(
+            display.affected_area(),
+            Rectangle::new(Point::new(28, 30), Size::new(6, 21))
+        );
+        assert_eq!(display.affected_area(), hand.bounding_box());
+        assert_eq!(display.get_pixel(Point::new(30, 50)), Some(BinaryColor::On));
+
+        // Odd thicknesses are centered on the needle.
+        let hand = Needle::new(center, 20, 5, 0, BinaryColor::On);
+        assert_eq!(
+            hand.bounding_box(),
+            Rectangle::new(Point::new(28, 10), Size::new(5, 21))
+        );
+    }
+
+    #[test]
+    fn test_clock_angle() {
+        assert_eq!(clock_angle(0, 0), 0);
+        assert_eq!(clock_angle(3, 0), 90);
+        assert_eq!(clock_angle(6, 30), 195);
+        assert_eq!(clock_angle(21, 45), 292);
+        assert_eq!(clock_angle(12, 0), 0);
+        assert_eq!(minute_angle(15), 90);
+        assert_eq!(minute_angle(75), 90);
+    }
+}
@@ -0,0 +1,4 @@
+pub use crate::{
+    diff_drawable::DiffDrawable,
+    rotate::{PointRotateExt, RotateExt},
+};
@@ -0,0 +1,555 @@
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+};
+
+use crate::{
+    color::FadeColor,
+    diff_drawable::DiffDrawable,
+    faded_rectangle::{union, FadedRectangle, Fading},
+};
+
+/// A 1 pixel frame around a bar that fills from the left in proportion to `value / max`.
+///
+/// The fill is a [`FadedRectangle`] that fades towards `background`, e.g. with
+/// [`Fading::Right`] for a soft leading edge.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct ProgressBar<C = Rgb888> {
+    pub bounds: Rectangle,
+    /// Values above `max` are shown as a full bar.
+    pub value: u32,
+    /// A bar with a `max` of zero is empty.
+    pub max: u32,
+    pub fill_color: C,
+    pub frame_color: C,
+    pub background: C,
+    pub fading: Fading,
+}
+
+impl<C> ProgressBar<C>
+where
+    C: FadeColor,
+{
+    pub fn new(bounds: Rectangle, max: u32, fill_color: C, frame_color: C) -> Self {
+        Self {
+            bounds,
+            value: 0,
+            max,
+            fill_color,
+            frame_color,
+            background: C::DEFAULT_TARGET,
+            fading: Fading::Right { steps: 4 },
+        }
+    }
+
+    pub fn with_value(mut self, value: u32) -> Self {
+        self.value = value;
+        self
+    }
+
+    pub fn with_background(mut self, background: C) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn with_fading(mut self, fading: Fading) -> Self {
+        self.fading = fading;
+        self
+    }
+
+    /// The area inside the frame.
+    pub fn inner(&self) -> Rectangle {
+        self.bounds.offset(-1)
+    }
+
+    /// Width of the filled part of the inner area, rounded to the nearest pixel.
+    pub fn filled_width(&self) -> u32 {
+        if self.max == 0 {
+            return 0;
+        }
+
+        let width = u64::from(self.inner().size.width);
+        let value = u64::from(self.value.min(self.max));
+        let max = u64::from(self.max);
+        ((value * width + max / 2) / max) as u32
+    }
+
+    /// The filled part of the bar.
+    pub fn fill(&self) -> FadedRectangle<C> {
+        let inner = self.inner();
+        let rect = Rectangle::new(
+            inner.top_left,
+            Size::new(self.filled_width(), inner.size.height),
+        );
+        FadedRectangle::new(rect, self.fill_color, self.fading).with_fade_target(self.background)
+    }
+
+    /// Sets `value` and redraws the strip that changed over an earlier drawing of this bar,
+    /// see [`FadedRectangle::draw_diff`].
+    ///
+    /// Returns the bounding box of all written pixels, `None` if nothing changed.
+    pub fn update<D>(&mut self, target: &mut D, value: u32) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let previous = self.fill().rect;
+        self.value = value;
+        self.fill().draw_diff(target, &previous)
+    }
+}
+
+impl<C> Dimensions for ProgressBar<C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+impl<C> Drawable for ProgressBar<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.bounds
+            .into_styled(PrimitiveStyle::with_stroke(self.frame_color, 1))
+            .draw(target)?;
+
+        let fill = self.fill();
+        fill.draw(target)?;
+
+        let inner = self.inner();
+        let empty = Rectangle::new(
+            inner.top_left + Point::new(fill.rect.size.width as i32, 0),
+            Size::new(inner.size.width - fill.rect.size.width, inner.size.height),
+        );
+        target.fill_solid(&empty, self.background)
+    }
+}
+
+/// The state is `value`, the other fields must be the same as in the earlier drawing.
+impl<C> DiffDrawable for ProgressBar<C>
+where
+    C: FadeColor,
+{
+    type State = u32;
+
+    fn state(&self) -> Self::State {
+        self.value
+    }
+
+    fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Self::State,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let mut drawn = self.with_value(*previous);
+        drawn.update(target, self.value)
+    }
+}
+
+// Splits `bounds` into `count` parts with `gap` pixels between them, from left to right or top
+// to bottom. Spare pixels are spread evenly, so the lengths of the parts differ by at most one.
+pub(crate) fn split(
+    bounds: Rectangle,
+    count: u32,
+    gap: u32,
+    horizontal: bool,
+) -> impl Iterator<Item = Rectangle> {
+    let Size { width, height } = bounds.size;
+    let full = if horizontal { width } else { height };
+    let len = u64::from(full.saturating_sub(gap * count.saturating_sub(1)));
+    let edge = move |index: u32| (u64::from(index) * len / u64::from(count)) as u32;
+
+    (0..count).map(move |index| {
+        let (start, end) = (edge(index), edge(index + 1));
+        let offset = (start + index * gap) as i32;
+        if horizontal {
+            Rectangle::new(
+                bounds.top_left + Point::new(offset, 0),
+                Size::new(end - start, height),
+            )
+        } else {
+            Rectangle::new(
+                bounds.top_left + Point::new(0, offset),
+                Size::new(width, end - start),
+            )
+        }
+    })
+}
+
+/// A bar split into `segments` blocks with `gap` pixels between them, filling from the left.
+///
+/// Spare pixels are spread evenly over the segments, so their widths differ by at most one.
+/// A partially filled segment is dimmed towards `background` by the unfilled fraction, and
+/// every filled segment uses `fading`, e.g. [`Fading::Right`] for a soft trailing edge. The
+/// gaps aren't drawn.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct SegmentedProgressBar<C = Rgb888> {
+    pub bounds: Rectangle,
+    pub segments: u8,
+    pub gap: u32,
+    /// Values above `max` are shown as a full bar.
+    pub value: u32,
+    /// A bar with a `max` of zero is empty.
+    pub max: u32,
+    pub color: C,
+    pub background: C,
+    pub fading: Fading,
+}
+
+impl<C> SegmentedProgressBar<C>
+where
+    C: FadeColor,
+{
+    pub fn new(bounds: Rectangle, segments: u8, gap: u32, max: u32, color: C) -> Self {
+        Self {
+            bounds,
+            segments,
+            gap,
+            value: 0,
+            max,
+            color,
+            background: C::DEFAULT_TARGET,
+            fading: Fading::None,
+        }
+    }
+
+    pub fn with_value(mut self, value: u32) -> Self {
+        self.value = value;
+        self
+    }
+
+    pub fn with_background(mut self, background: C) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn with_fading(mut self, fading: Fading) -> Self {
+        self.fading = fading;
+        self
+    }
+
+    /// The areas of the segments from left to right.
+    pub fn segment_rects(&self) -> impl Iterator<Item = Rectangle> {
+        split(self.bounds, u32::from(self.segments), self.gap, true)
+    }
+
+    /// How far each segment is filled, from `0` to `256`.
+    pub fn segment_levels(&self) -> impl Iterator<Item = u16> {
+        let filled = if self.max == 0 {
+            0
+        } else {
+            let value = u64::from(self.value.min(self.max));
+            value * u64::from(self.segments) * 256 / u64::from(self.max)
+        };
+
+        (0..u64::from(self.segments))
+            .map(move |index| filled.saturating_sub(index * 256).min(256) as u16)
+    }
+
+    fn draw_segment<D>(&self, target: &mut D, rect: Rectangle, level: u16) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if level == 0 {
+            return target.fill_solid(&rect, self.background);
+        }
+
+        let color = self.color.fade_towards(self.background, 256 - level);
+        FadedRectangle::new(rect, color, self.fading)
+            .with_fade_target(self.background)
+            .draw(target)
+    }
+
+    /// Sets `value` and redraws the segments whose fill changed over an earlier drawing of
+    /// this bar.
+    ///
+    /// Returns the bounding box of all written pixels, `None` if nothing changed.
+    pub fn update<D>(&mut self, target: &mut D, value: u32) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let previous = *self;
+        self.value = value;
+
+        let mut dirty = None;
+        let levels = previous.segment_levels().zip(self.segment_levels());
+        for (rect, (old, new)) in self.segment_rects().zip(levels) {
+            if old != new {
+                self.draw_segment(target, rect, new)?;
+                dirty = union(dirty, &rect);
+            }
+        }
+        Ok(dirty)
+    }
+}
+
+impl<C> Dimensions for SegmentedProgressBar<C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+impl<C> Drawable for SegmentedProgressBar<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.segment_rects()
+            .zip(self.segment_levels())
+            .try_for_each(|(rect, level)| self.draw_segment(target, rect, level))
+    }
+}
+
+/// The state is `value`, the other fields must be the same as in the earlier drawing.
+impl<C> DiffDrawable for SegmentedProgressBar<C>
+where
+    C: FadeColor,
+{
+    type State = u32;
+
+    fn state(&self) -> Self::State {
+        self.value
+    }
+
+    fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Self::State,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let mut drawn = self.with_value(*previous);
+        drawn.update(target, self.value)
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+
+    fn bar(value: u32) -> ProgressBar {
+        let bounds = Rectangle::new(Point::new(1, 1), Size::new(62, 10));
+        ProgressBar::new(bounds, 200, Rgb888::CSS_LIME_GREEN, Rgb888::WHITE).with_value(value)
+    }
+
+    #[test]
+    fn test_progress_bar_widths() {
+        assert_eq!(bar(0).filled_width(), 0);
+        assert_eq!(bar(100).filled_width(), 30);
+        assert_eq!(bar(200).filled_width(), 60);
+        assert_eq!(bar(1000).filled_width(), 60);
+        // 1/200 of 60 pixels rounds to 0, 2/200 to 1 (0.6).
+        assert_eq!(bar(1).filled_width(), 0);
+        assert_eq!(bar(2).filled_width(), 1);
+
+        let mut empty = bar(5);
+        empty.max = 0;
+        assert_eq!(empty.filled_width(), 0);
+        let mut display = MockDisplay::new();
+        empty.draw(&mut display).unwrap();
+        let inner = Rectangle::new(Point::new(2, 2), Size::new(60, 8));
+        assert_eq!(display.get_pixel(inner.center()), Some(Rgb888::BLACK));
+    }
+
+    #[test]
+    fn test_progress_bar_draw() {
+        for (value, filled) in [(0, 0), (100, 30), (200, 60), (300, 60)] {
+            let bar = bar(value);
+            let mut display = MockDisplay::new();
+            bar.draw(&mut display).unwrap();
+            assert_eq!(display.affected_area(), bar.bounds);
+
+            let mut expected = MockDisplay::new();
+            bar.bounds
+                .into_styled(PrimitiveStyle::with_stroke(Rgb888::WHITE, 1))
+                .draw(&mut expected)
+                .unwrap();
+            let fill = Rectangle::new(Point::new(2, 2), Size::new(filled, 8));
+            FadedRectangle::new(fill, Rgb888::CSS_LIME_GREEN, Fading::Right { steps: 4 })
+                .draw(&mut expected)
+                .unwrap();
+            let empty = Rectangle::new(Point::new(2 + filled as i32, 2), Size::new(60 - filled, 8));
+            expected.fill_solid(&empty, Rgb888::BLACK).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_progress_bar_update() {
+        let mut progress = bar(100);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        progress.draw(&mut display).unwrap();
+
+        // From 30 to 36 pixels, the old fade band and the new pixels are redrawn.
+        let dirty = progress.update(&mut display, 120).unwrap();
+        assert_eq!(
+            dirty,
+            Some(Rectangle::new(Point::new(28, 2), Size::new(10, 8)))
+        );
+        assert_eq!(progress.update(&mut display, 120).unwrap(), None);
+
+        for value in [40, 0, 200, 500, 150] {
+            progress.update(&mut display, value).unwrap();
+            let mut expected = MockDisplay::new();
+            bar(value).draw(&mut expected).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_progress_bar_draw_diff() {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        bar(100).draw(&mut display).unwrap();
+
+        let mut previous = bar(100).state();
+        assert_eq!(bar(100).draw_diff(&mut display, &previous).unwrap(), None);
+        for value in [120, 40, 0, 500, 150] {
+            let dirty = bar(value).draw_diff(&mut display, &previous).unwrap();
+            assert_eq!(
+                dirty,
+                bar(previous)
+                    .update(&mut MockDisplay::new(), value)
+                    .unwrap()
+            );
+            previous = bar(value).state();
+
+            let mut expected = MockDisplay::new();
+            bar(value).draw(&mut expected).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    fn segmented(value: u32) -> SegmentedProgressBar {
+        let bounds = Rectangle::new(Point::new(0, 4), Size::new(64, 6));
+        SegmentedProgressBar::new(bounds, 7, 2, 14, Rgb888::CSS_ORANGE)
+            .with_value(value)
+            .with_background(Rgb888::CSS_NAVY)
+    }
+
+    #[test]
+    fn test_segment_layout() {
+        let spans = |width: u32, segments: u8, gap: u32| {
+            let bounds = Rectangle::new(Point::new(3, 0), Size::new(width, 5));
+            SegmentedProgressBar::new(bounds, segments, gap, 1, Rgb888::WHITE)
+                .segment_rects()
+                .map(|rect| (rect.top_left.x, rect.size.width))
+                .collect::<Vec<_>>()
+        };
+
+        // 88 pixels without the gaps, the 4 spare ones go to every other segment.
+        assert_eq!(
+            spans(100, 7, 2),
+            [
+                (3, 12),
+                (17, 13),
+                (32, 12),
+                (46, 13),
+                (61, 12),
+                (75, 13),
+                (90, 13)
+            ]
+        );
+        assert_eq!(
+            spans(64, 5, 3),
+            [(3, 10), (16, 10), (29, 11), (43, 10), (56, 11)]
+        );
+        assert_eq!(spans(10, 1, 4), [(3, 10)]);
+        assert_eq!(spans(10, 0, 4), []);
+        // Gaps wider than the bar leave empty segments instead of overflowing.
+        assert_eq!(spans(4, 3, 4), [(3, 0), (7, 0), (11, 0)]);
+    }
+
+    #[test]
+    fn test_segmented_draw() {
+        let bar = segmented(7);
+        assert_eq!(
+            bar.segment_levels().collect::<Vec<_>>(),
+            [256, 256, 256, 128, 0, 0, 0]
+        );
+        assert!(segmented(100).segment_levels().all(|level| level == 256));
+        let mut empty = segmented(3);
+        empty.max = 0;
+        assert!(empty.segment_levels().all(|level| level == 0));
+
+        let mut display = MockDisplay::new();
+        bar.draw(&mut display).unwrap();
+        let dimmed = Rgb888::CSS_ORANGE.fade_towards(Rgb888::CSS_NAVY, 128);
+        let expected = [
+            Rgb888::CSS_ORANGE,
+            Rgb888::CSS_ORANGE,
+            Rgb888::CSS_ORANGE,
+            dimmed,
+            Rgb888::CSS_NAVY,
+            Rgb888::CSS_NAVY,
+            Rgb888::CSS_NAVY,
+        ];
+        for (rect, color) in bar.segment_rects().zip(expected) {
+            assert_eq!(display.get_pixel(rect.center()), Some(color), "{rect:?}");
+        }
+        // Gaps stay untouched.
+        assert_eq!(display.get_pixel(Point::new(7, 6)), None);
+    }
+
+    #[test]
+    fn test_segmented_update() {
+        let mut bar = segmented(7).with_fading(Fading::Right { steps: 3 });
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        bar.draw(&mut display).unwrap();
+
+        let fourth = bar.segment_rects().nth(3).unwrap();
+        assert_eq!(bar.update(&mut display, 8).unwrap(), Some(fourth));
+        assert_eq!(bar.update(&mut display, 8).unwrap(), None);
+
+        for value in [9, 2, 14, 20, 0, 5] {
+            bar.update(&mut display, value).unwrap();
+            let mut expected = MockDisplay::new();
+            bar.draw(&mut expected).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_segmented_draw_diff() {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        segmented(7).draw(&mut display).unwrap();
+
+        let fourth = segmented(0).segment_rects().nth(3).unwrap();
+        let dirty = segmented(8).draw_diff(&mut display, &7).unwrap();
+        assert_eq!(dirty, Some(fourth));
+        assert_eq!(segmented(8).draw_diff(&mut display, &8).unwrap(), None);
+
+        let mut previous = segmented(8).state();
+        for value in [9, 2, 14, 20, 0, 5] {
+            segmented(value).draw_diff(&mut display, &previous).unwrap();
+            previous = segmented(value).state();
+
+            let mut expected = MockDisplay::new();
+            segmented(value).draw(&mut expected).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+}
@@ -1,13 +1,184 @@
-use embedded_graphics::prelude::*;
+use core::{
+    fmt,
+    iter::FusedIterator,
+    ops::{Add, AddAssign},
+};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+use embedded_graphics::{pixelcolor::PixelColor, prelude::*, primitives::Rectangle};
+
+use crate::flip::Flip;
+
+// Longest row that `RotatedDrawTarget` reverses in a buffer for 180° rotations.
+const ROW_BUFFER: usize = 64;
+
+// Memory access control bits of ST77xx and ILI9xxx controllers.
+const MADCTL_MY: u8 = 0x80;
+const MADCTL_MX: u8 = 0x40;
+const MADCTL_MV: u8 = 0x20;
+const MADCTL_BGR: u8 = 0x08;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum Rotation {
+    #[default]
     Rotate0,
     Rotate90,
     Rotate180,
     Rotate270,
 }
 
+impl Rotation {
+    /// The rotation by `quadrants` times 90°, negative values rotate the other way.
+    pub const fn from_quadrants(quadrants: i32) -> Self {
+        match quadrants.rem_euclid(4) {
+            0 => Rotation::Rotate0,
+            1 => Rotation::Rotate90,
+            2 => Rotation::Rotate180,
+            _ => Rotation::Rotate270,
+        }
+    }
+
+    /// Number of 90° steps, from 0 to 3.
+    pub const fn quadrants(self) -> i32 {
+        match self {
+            Rotation::Rotate0 => 0,
+            Rotation::Rotate90 => 1,
+            Rotation::Rotate180 => 2,
+            Rotation::Rotate270 => 3,
+        }
+    }
+
+    /// The rotation by `degrees` clockwise, `None` unless it is a multiple of 90°.
+    pub const fn from_degrees(degrees: i32) -> Option<Self> {
+        if degrees % 90 != 0 {
+            return None;
+        }
+        Some(Self::from_quadrants(degrees / 90))
+    }
+
+    /// The rotation closest to `degrees` clockwise, halfway angles round up.
+    pub const fn from_degrees_rounded(degrees: i32) -> Self {
+        Self::from_quadrants((degrees.rem_euclid(360) + 45) / 90)
+    }
+
+    /// The clockwise angle, 0, 90, 180 or 270.
+    pub const fn to_degrees(self) -> u16 {
+        self.quadrants() as u16 * 90
+    }
+
+    // Rotation matrix `[xx, xy, yx, yy]`, the rotated x is `xx * x + xy * y`.
+    pub(crate) const fn matrix(self) -> [i32; 4] {
+        match self {
+            Rotation::Rotate0 => [1, 0, 0, 1],
+            Rotation::Rotate90 => [0, -1, 1, 0],
+            Rotation::Rotate180 => [-1, 0, 0, -1],
+            Rotation::Rotate270 => [0, 1, -1, 0],
+        }
+    }
+
+    /// This rotation followed by `other`, like `self + other`.
+    pub const fn then(self, other: Rotation) -> Self {
+        Self::from_quadrants(self.quadrants() + other.quadrants())
+    }
+
+    /// Returns `true` if the rotation swaps the horizontal and vertical axis.
+    pub const fn is_axis_swapped(self) -> bool {
+        matches!(self, Rotation::Rotate90 | Rotation::Rotate270)
+    }
+
+    /// The rotation that undoes this one.
+    pub const fn inverse(self) -> Self {
+        Self::from_quadrants(-self.quadrants())
+    }
+
+    /// The MADCTL register value that makes the controller apply `flip` and then this rotation,
+    /// like [`Orientation`](crate::flip::Orientation). `bgr` sets the BGR color order bit.
+    ///
+    /// MV swaps the axes before MX mirrors the columns and MY the rows.
+    pub const fn to_madctl(self, flip: Flip, bgr: bool) -> u8 {
+        let [xx, xy, yx, yy] = self.matrix();
+        let sx = if flip.flips_x() { -1 } else { 1 };
+        let sy = if flip.flips_y() { -1 } else { 1 };
+        let (xx, xy, yx, yy) = (xx * sx, xy * sy, yx * sx, yy * sy);
+
+        let mut madctl = 0;
+        if xx == 0 {
+            madctl |= MADCTL_MV;
+        }
+        if xx + xy < 0 {
+            madctl |= MADCTL_MX;
+        }
+        if yx + yy < 0 {
+            madctl |= MADCTL_MY;
+        }
+        if bgr {
+            madctl |= MADCTL_BGR;
+        }
+        madctl
+    }
+
+    /// The rotation, flip and BGR bit of a MADCTL value, see [`Rotation::to_madctl`].
+    ///
+    /// Vertical and double flips are returned as the equivalent horizontal flip or rotation.
+    /// Returns `None` if other bits than MY, MX, MV and BGR are set.
+    pub fn from_madctl(madctl: u8) -> Option<(Rotation, Flip, bool)> {
+        if madctl & !(MADCTL_MY | MADCTL_MX | MADCTL_MV | MADCTL_BGR) != 0 {
+            return None;
+        }
+        let bgr = madctl & MADCTL_BGR != 0;
+
+        (0..4)
+            .map(Rotation::from_quadrants)
+            .flat_map(|rotation| [(rotation, Flip::None), (rotation, Flip::Horizontal)])
+            .find(|(rotation, flip)| rotation.to_madctl(*flip, bgr) == madctl)
+            .map(|(rotation, flip)| (rotation, flip, bgr))
+    }
+
+    /// Size of a `size` footprint after the rotation.
+    pub const fn rotate_size(self, size: Size) -> Size {
+        if self.is_axis_swapped() {
+            Size::new(size.height, size.width)
+        } else {
+            size
+        }
+    }
+}
+
+/// An angle that isn't a multiple of 90°, see [`Rotation::from_degrees`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct InvalidAngle(pub i32);
+
+impl fmt::Display for InvalidAngle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}° is not a multiple of 90°", self.0)
+    }
+}
+
+impl TryFrom<i32> for Rotation {
+    type Error = InvalidAngle;
+
+    fn try_from(degrees: i32) -> Result<Self, Self::Error> {
+        Self::from_degrees(degrees).ok_or(InvalidAngle(degrees))
+    }
+}
+
+impl Add for Rotation {
+    type Output = Rotation;
+
+    fn add(self, rhs: Rotation) -> Self::Output {
+        self.then(rhs)
+    }
+}
+
+impl AddAssign for Rotation {
+    fn add_assign(&mut self, rhs: Rotation) {
+        *self = self.then(rhs);
+    }
+}
+
 pub fn rotate_point(point: Point, rotation: Rotation, center: Point) -> Point {
     let relative = point - center;
     let rotated = match rotation {
@@ -18,3 +189,1304 @@ pub fn rotate_point(point: Point, rotation: Rotation, center: Point) -> Point {
     };
     rotated + center
 }
+
+// sin(0°) to sin(90°) in steps of 1°, scaled by 32768.
+const SINE_Q15: [u16; 91] = [
+    0, 572, 1144, 1715, 2286, 2856, 3425, 3993, 4560, 5126, 5690, 6252, 6813, 7371, 7927, 8481,
+    9032, 9580, 10126, 10668, 11207, 11743, 12275, 12803, 13328, 13848, 14365, 14876, 15384, 15886,
+    16384, 16877, 17364, 17847, 18324, 18795, 19261, 19720, 20174, 20622, 21063, 21498, 21926,
+    22348, 22763, 23170, 23571, 23965, 24351, 24730, 25102, 25466, 25822, 26170, 26510, 26842,
+    27166, 27482, 27789, 28088, 28378, 28660, 28932, 29197, 29452, 29698, 29935, 30163, 30382,
+    30592, 30792, 30983, 31164, 31336, 31499, 31651, 31795, 31928, 32052, 32166, 32270, 32365,
+    32449, 32524, 32588, 32643, 32688, 32723, 32748, 32763, 32768,
+];
+
+/// Sine and cosine of the clockwise angle `degrees` as fixed-point numbers with 15 fraction
+/// bits, i.e. `32768` is 1.0.
+pub const fn sin_cos_q15(degrees: i32) -> (i32, i32) {
+    const fn sine(degrees: usize) -> i32 {
+        SINE_Q15[degrees] as i32
+    }
+
+    let degrees = degrees.rem_euclid(360) as usize;
+    match degrees {
+        0..=90 => (sine(degrees), sine(90 - degrees)),
+        91..=180 => (sine(180 - degrees), -sine(degrees - 90)),
+        181..=270 => (-sine(degrees - 180), -sine(270 - degrees)),
+        _ => (-sine(360 - degrees), sine(degrees - 270)),
+    }
+}
+
+/// Rotates `point` clockwise by `degrees` around `center`, rounded to the nearest pixel.
+///
+/// Multiples of 90° give the same results as [`rotate_point`].
+pub fn rotate_point_deg(point: Point, degrees: i32, center: Point) -> Point {
+    let (sin, cos) = sin_cos_q15(degrees);
+    let (sin, cos) = (i64::from(sin), i64::from(cos));
+    let relative = point - center;
+    let (x, y) = (i64::from(relative.x), i64::from(relative.y));
+
+    let round = |value: i64| ((value + (1 << 14)) >> 15) as i32;
+    Point::new(round(x * cos - y * sin), round(x * sin + y * cos)) + center
+}
+
+/// Rotates `point` clockwise by `radians` around `center`, rounded to the nearest pixel.
+#[cfg(feature = "libm")]
+pub fn rotate_point_f32(point: Point, radians: f32, center: Point) -> Point {
+    let (sin, cos) = libm::sincosf(radians);
+    let relative = point - center;
+    let (x, y) = (relative.x as f32, relative.y as f32);
+
+    let round = |value: f32| libm::roundf(value) as i32;
+    Point::new(round(x * cos - y * sin), round(x * sin + y * cos)) + center
+}
+
+/// Rotates all `points` around `center`, like [`rotate_point`].
+pub fn rotate_points_in_place(points: &mut [Point], rotation: Rotation, center: Point) {
+    let [xx, xy, yx, yy] = rotation.matrix();
+    for point in points {
+        let Point { x, y } = *point - center;
+        *point = Point::new(xx * x + xy * y, yx * x + yy * y) + center;
+    }
+}
+
+/// Iterates over `points` rotated around `center`, like [`rotate_point`].
+pub fn rotate_points(
+    points: &[Point],
+    rotation: Rotation,
+    center: Point,
+) -> impl Iterator<Item = Point> + '_ {
+    let [xx, xy, yx, yy] = rotation.matrix();
+    points.iter().map(move |point| {
+        let Point { x, y } = *point - center;
+        Point::new(xx * x + xy * y, yx * x + yy * y) + center
+    })
+}
+
+/// Adds rotations and flips to [`Point`].
+pub trait PointRotateExt {
+    /// Rotates the point around the origin, see [`rotate_point`].
+    fn rotated(self, rotation: Rotation) -> Point;
+
+    /// Rotates the point around `center`, see [`rotate_point`].
+    fn rotated_around(self, rotation: Rotation, center: Point) -> Point;
+
+    /// Mirrors the point at the vertical line through `axis`.
+    fn flipped_x(self, axis: i32) -> Point;
+
+    /// Mirrors the point at the horizontal line through `axis`.
+    fn flipped_y(self, axis: i32) -> Point;
+}
+
+impl PointRotateExt for Point {
+    #[inline]
+    fn rotated(self, rotation: Rotation) -> Point {
+        rotate_point(self, rotation, Point::zero())
+    }
+
+    #[inline]
+    fn rotated_around(self, rotation: Rotation, center: Point) -> Point {
+        rotate_point(self, rotation, center)
+    }
+
+    #[inline]
+    fn flipped_x(self, axis: i32) -> Point {
+        Point::new(2 * axis - self.x, self.y)
+    }
+
+    #[inline]
+    fn flipped_y(self, axis: i32) -> Point {
+        Point::new(self.x, 2 * axis - self.y)
+    }
+}
+
+/// Rotates the pixels covered by `rect` around `center`, the returned rectangle covers the
+/// rotated pixels with a positive size.
+pub fn rotate_rectangle(rect: &Rectangle, rotation: Rotation, center: Point) -> Rectangle {
+    let top_left = rotate_point(rect.top_left, rotation, center);
+    let Some(bottom_right) = rect.bottom_right() else {
+        return Rectangle::new(top_left, rotation.rotate_size(rect.size));
+    };
+
+    let bottom_right = rotate_point(bottom_right, rotation, center);
+    normalized_rect_from_corners(top_left, bottom_right)
+}
+
+/// The rectangle covering the pixels from `a` to `b`, which can be any two opposite corner
+/// pixels. Both corners are included, so the size is one larger than their distance.
+pub fn normalized_rect_from_corners(a: Point, b: Point) -> Rectangle {
+    Rectangle::with_corners(a.component_min(b), a.component_max(b))
+}
+
+/// The corner pixels of `rect` in clockwise order, starting at the top left. All corners are
+/// `top_left` if `rect` is zero sized.
+pub fn rect_corners(rect: &Rectangle) -> [Point; 4] {
+    let Some(bottom_right) = rect.bottom_right() else {
+        return [rect.top_left; 4];
+    };
+
+    [
+        rect.top_left,
+        Point::new(bottom_right.x, rect.top_left.y),
+        bottom_right,
+        Point::new(rect.top_left.x, bottom_right.y),
+    ]
+}
+
+/// Rotates `rect` around its own center, keeping the pixel at [`Rectangle::center`] in place.
+///
+/// The center pixel is rounded towards the top left corner if a dimension is even, so
+/// rotating back always returns the original rectangle, whatever the parity of its width and
+/// height.
+pub fn rotate_rect_in_place(rect: &Rectangle, rotation: Rotation) -> Rectangle {
+    Rectangle::with_center(rect.center(), rotation.rotate_size(rect.size))
+}
+
+/// A [`DrawTarget`] that rotates everything drawn through it onto `parent`, e.g. for a panel
+/// that is mounted sideways.
+///
+/// The logical size is the parent's size rotated with [`Rotation::rotate_size`] and the
+/// logical top left corner is at the origin.
+#[derive(Debug)]
+pub struct RotatedDrawTarget<'a, D> {
+    parent: &'a mut D,
+    rotation: Rotation,
+    offset: Point,
+    // Unrotated size of the visible part of the parent, see `RotatedDrawTarget::with_window`.
+    window: Option<Size>,
+}
+
+impl<'a, D> RotatedDrawTarget<'a, D>
+where
+    D: DrawTarget,
+{
+    pub fn new(parent: &'a mut D, rotation: Rotation) -> Self {
+        let physical = parent.bounding_box();
+        let logical = Rectangle::new(Point::zero(), rotation.rotate_size(physical.size));
+        let rotated = rotate_rectangle(&logical, rotation, Point::zero());
+
+        Self {
+            parent,
+            rotation,
+            offset: physical.top_left - rotated.top_left,
+            window: None,
+        }
+    }
+
+    /// Rotates onto a visible window of `size` within a larger parent, e.g. a 135x240 panel
+    /// driven by a controller with 240x320 pixels of RAM.
+    ///
+    /// The rotated window is placed at `offsets[rotation.quadrants()]` on the parent, like the
+    /// per orientation offsets of display drivers. The logical area covers the window only.
+    pub fn with_window(
+        parent: &'a mut D,
+        rotation: Rotation,
+        size: Size,
+        offsets: [Point; 4],
+    ) -> Self {
+        let logical = Rectangle::new(Point::zero(), rotation.rotate_size(size));
+        let rotated = rotate_rectangle(&logical, rotation, Point::zero());
+
+        Self {
+            parent,
+            rotation,
+            offset: offsets[rotation.quadrants() as usize] - rotated.top_left,
+            window: Some(size),
+        }
+    }
+
+    // Rotates around `center` instead of keeping the logical area at the origin.
+    fn around(parent: &'a mut D, rotation: Rotation, center: Point) -> Self {
+        Self {
+            parent,
+            rotation,
+            offset: center - rotate_point(center, rotation, Point::zero()),
+            window: None,
+        }
+    }
+
+    /// Position of the logical `point` on the parent.
+    pub fn to_physical(&self, point: Point) -> Point {
+        rotate_point(point, self.rotation, Point::zero()) + self.offset
+    }
+
+    /// The logical point drawn at `point` on the parent, e.g. to hit test touch input that is
+    /// reported in physical coordinates.
+    pub fn to_logical(&self, point: Point) -> Point {
+        rotate_point(point - self.offset, self.rotation.inverse(), Point::zero())
+    }
+}
+
+impl<D> Dimensions for RotatedDrawTarget<'_, D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        if let Some(size) = self.window {
+            return Rectangle::new(Point::zero(), self.rotation.rotate_size(size));
+        }
+
+        let physical = self.parent.bounding_box().translate(-self.offset);
+        rotate_rectangle(&physical, self.rotation.inverse(), Point::zero())
+    }
+}
+
+impl<D, C> DrawTarget for RotatedDrawTarget<'_, D>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        if self.rotation == Rotation::Rotate0 && self.offset == Point::zero() {
+            return self.parent.draw_iter(pixels);
+        }
+
+        let (rotation, offset) = (self.rotation, self.offset);
+        self.parent
+            .draw_iter(pixels.into_iter().map(|Pixel(point, color)| {
+                Pixel(rotate_point(point, rotation, Point::zero()) + offset, color)
+            }))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let width = area.size.width as usize;
+        match self.rotation {
+            Rotation::Rotate0 => self
+                .parent
+                .fill_contiguous(&area.translate(self.offset), colors),
+            // Rows stay rows but run backwards, so each one is reversed in a buffer.
+            Rotation::Rotate180 if width <= ROW_BUFFER => {
+                let mut colors = colors.into_iter();
+                for y in area.rows() {
+                    let mut row = [None; ROW_BUFFER];
+                    row[..width].fill_with(|| colors.next());
+                    let logical = Rectangle::new(
+                        Point::new(area.top_left.x, y),
+                        Size::new(area.size.width, 1),
+                    );
+
+                    // Fewer colors than pixels end the fill like a short iterator does.
+                    if row[width.saturating_sub(1)].is_none() {
+                        let pixels = logical
+                            .points()
+                            .zip(row)
+                            .filter_map(|(point, color)| Some(Pixel(point, color?)));
+                        return self.draw_iter(pixels);
+                    }
+
+                    let physical = rotate_rectangle(&logical, self.rotation, Point::zero())
+                        .translate(self.offset);
+                    let reversed = row[..width].iter().rev().flatten().copied();
+                    self.parent.fill_contiguous(&physical, reversed)?;
+                }
+                Ok(())
+            }
+            // Columns would need a buffer of the whole area, so 90° and 270° rotations draw
+            // pixel by pixel.
+            _ => {
+                let pixels = area
+                    .points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color));
+                self.draw_iter(pixels)
+            }
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = rotate_rectangle(area, self.rotation, Point::zero()).translate(self.offset);
+        self.parent.fill_solid(&area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        // Only the window is visible, the rest of the parent is left alone.
+        if self.window.is_some() {
+            return self.fill_solid(&self.bounding_box(), color);
+        }
+        self.parent.clear(color)
+    }
+}
+
+/// Draws `inner` rotated around `center`, e.g. to draw a [`FadedRectangle`] sideways.
+///
+/// The inner drawable is drawn through a [`RotatedDrawTarget`], so solid fills stay solid fills.
+///
+/// [`FadedRectangle`]: crate::FadedRectangle
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Rotated<T> {
+    pub inner: T,
+    pub rotation: Rotation,
+    pub center: Point,
+}
+
+impl<T> Rotated<T> {
+    pub const fn new(inner: T, rotation: Rotation, center: Point) -> Self {
+        Self {
+            inner,
+            rotation,
+            center,
+        }
+    }
+}
+
+impl<T> Dimensions for Rotated<T>
+where
+    T: Dimensions,
+{
+    fn bounding_box(&self) -> Rectangle {
+        rotate_rectangle(&self.inner.bounding_box(), self.rotation, self.center)
+    }
+}
+
+impl<T> Drawable for Rotated<T>
+where
+    T: Drawable,
+{
+    type Color = T::Color;
+    type Output = T::Output;
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let mut rotated = RotatedDrawTarget::around(target, self.rotation, self.center);
+        self.inner.draw(&mut rotated)
+    }
+}
+
+/// Draws `inner` rotated clockwise by `radians` around `center`, see [`rotate_point_f32`].
+///
+/// Every pixel is moved on its own, so filled areas can show gaps at angles that aren't
+/// multiples of 90°.
+#[cfg(feature = "libm")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct AngleRotated<T> {
+    pub inner: T,
+    pub radians: f32,
+    pub center: Point,
+}
+
+#[cfg(feature = "libm")]
+impl<T> AngleRotated<T> {
+    pub const fn new(inner: T, radians: f32, center: Point) -> Self {
+        Self {
+            inner,
+            radians,
+            center,
+        }
+    }
+}
+
+#[cfg(feature = "libm")]
+impl<T> Dimensions for AngleRotated<T>
+where
+    T: Dimensions,
+{
+    fn bounding_box(&self) -> Rectangle {
+        rotate_bounds(&self.inner.bounding_box(), self.radians, self.center)
+    }
+}
+
+// Bounding box of the corners of `rect` rotated like `rotate_point_f32`.
+#[cfg(feature = "libm")]
+fn rotate_bounds(rect: &Rectangle, radians: f32, center: Point) -> Rectangle {
+    let top_left = rect.top_left;
+    let Some(bottom_right) = rect.bottom_right() else {
+        return Rectangle::new(rotate_point_f32(top_left, radians, center), Size::zero());
+    };
+
+    let corners = [
+        top_left,
+        Point::new(bottom_right.x, top_left.y),
+        Point::new(top_left.x, bottom_right.y),
+        bottom_right,
+    ]
+    .map(|corner| rotate_point_f32(corner, radians, center));
+    let (min, max) = corners
+        .iter()
+        .fold((corners[0], corners[0]), |(min, max), corner| {
+            (min.component_min(*corner), max.component_max(*corner))
+        });
+    Rectangle::with_corners(min, max)
+}
+
+#[cfg(feature = "libm")]
+impl<T> Drawable for AngleRotated<T>
+where
+    T: Drawable,
+{
+    type Color = T::Color;
+    type Output = T::Output;
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let mut rotated = AngleRotatedTarget {
+            parent: target,
+            radians: self.radians,
+            center: self.center,
+        };
+        self.inner.draw(&mut rotated)
+    }
+}
+
+// Moves every pixel drawn through it, fills fall back to drawing single pixels.
+#[cfg(feature = "libm")]
+struct AngleRotatedTarget<'a, D> {
+    parent: &'a mut D,
+    radians: f32,
+    center: Point,
+}
+
+#[cfg(feature = "libm")]
+impl<D> Dimensions for AngleRotatedTarget<'_, D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        rotate_bounds(&self.parent.bounding_box(), -self.radians, self.center)
+    }
+}
+
+#[cfg(feature = "libm")]
+impl<D, C> DrawTarget for AngleRotatedTarget<'_, D>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (radians, center) = (self.radians, self.center);
+        self.parent.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(rotate_point_f32(point, radians, center), color)),
+        )
+    }
+}
+
+/// Iterator that rotates the pixels of `iter` around `center`, see [`RotateExt::rotated`].
+#[derive(Clone, Debug)]
+pub struct RotateIter<I> {
+    iter: I,
+    rotation: Rotation,
+    center: Point,
+}
+
+impl<I, C> Iterator for RotateIter<I>
+where
+    I: Iterator<Item = Pixel<C>>,
+    C: PixelColor,
+{
+    type Item = Pixel<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Pixel(point, color) = self.iter.next()?;
+        Some(Pixel(
+            rotate_point(point, self.rotation, self.center),
+            color,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, C> ExactSizeIterator for RotateIter<I>
+where
+    I: ExactSizeIterator<Item = Pixel<C>>,
+    C: PixelColor,
+{
+}
+
+impl<I, C> FusedIterator for RotateIter<I>
+where
+    I: FusedIterator<Item = Pixel<C>>,
+    C: PixelColor,
+{
+}
+
+/// Adds [`RotateExt::rotated`] to pixel iterators.
+pub trait RotateExt<C>: Iterator<Item = Pixel<C>> + Sized
+where
+    C: PixelColor,
+{
+    /// Rotates every pixel around `center`.
+    fn rotated(self, rotation: Rotation, center: Point) -> RotateIter<Self> {
+        RotateIter {
+            iter: self,
+            rotation,
+            center,
+        }
+    }
+}
+
+impl<I, C> RotateExt<C> for I
+where
+    I: Iterator<Item = Pixel<C>>,
+    C: PixelColor,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::pixelcolor::Rgb888;
+
+    const ROTATIONS: [Rotation; 4] = [
+        Rotation::Rotate0,
+        Rotation::Rotate90,
+        Rotation::Rotate180,
+        Rotation::Rotate270,
+    ];
+
+    #[test]
+    fn test_rotation_composition() {
+        // Row `a`, column `b` holds `a + b`.
+        let table = [[0, 1, 2, 3], [1, 2, 3, 0], [2, 3, 0, 1], [3, 0, 1, 2]];
+        for (a, row) in ROTATIONS.into_iter().zip(table) {
+            for (b, sum) in ROTATIONS.into_iter().zip(row) {
+                assert_eq!(a + b, ROTATIONS[sum], "{a:?} + {b:?}");
+
+                let mut accumulated = a;
+                accumulated += b;
+                assert_eq!(accumulated, a + b);
+
+                let point = Point::new(5, -3);
+                let center = Point::new(1, 2);
+                assert_eq!(
+                    rotate_point(rotate_point(point, a, center), b, center),
+                    rotate_point(point, a + b, center)
+                );
+            }
+        }
+
+        const STATIC: Rotation = Rotation::Rotate90.then(Rotation::Rotate270);
+        assert_eq!(STATIC, Rotation::default());
+    }
+
+    #[test]
+    fn test_rotation_inverse_and_quadrants() {
+        for rotation in ROTATIONS {
+            assert_eq!(rotation + rotation.inverse(), Rotation::Rotate0);
+            assert_eq!(rotation.inverse().inverse(), rotation);
+            assert_eq!(Rotation::from_quadrants(rotation.quadrants()), rotation);
+        }
+
+        assert_eq!(Rotation::from_quadrants(-1), Rotation::Rotate270);
+        assert_eq!(Rotation::from_quadrants(-6), Rotation::Rotate180);
+        assert_eq!(Rotation::from_quadrants(9), Rotation::Rotate90);
+        assert_eq!(Rotation::from_quadrants(i32::MIN), Rotation::Rotate0);
+        assert_eq!(Rotation::from_quadrants(i32::MAX), Rotation::Rotate270);
+    }
+
+    #[test]
+    fn test_rotation_degrees() {
+        for rotation in ROTATIONS {
+            let degrees = i32::from(rotation.to_degrees());
+            assert_eq!(Rotation::from_degrees(degrees), Some(rotation));
+            assert_eq!(Rotation::from_degrees_rounded(degrees), rotation);
+        }
+
+        let cases = [
+            (-90, Some(Rotation::Rotate270)),
+            (-360, Some(Rotation::Rotate0)),
+            (450, Some(Rotation::Rotate90)),
+            (900, Some(Rotation::Rotate180)),
+            (45, None),
+            (-1, None),
+            // -2147483610 is 270° modulo 360°.
+            (i32::MIN + 38, Some(Rotation::Rotate270)),
+            (i32::MIN, None),
+            (i32::MAX, None),
+        ];
+        for (degrees, rotation) in cases {
+            assert_eq!(Rotation::from_degrees(degrees), rotation, "{degrees}");
+            assert_eq!(
+                Rotation::try_from(degrees),
+                rotation.ok_or(InvalidAngle(degrees))
+            );
+        }
+
+        let rounded = [
+            (44, Rotation::Rotate0),
+            (45, Rotation::Rotate90),
+            (-44, Rotation::Rotate0),
+            (-46, Rotation::Rotate270),
+            (359, Rotation::Rotate0),
+            (181, Rotation::Rotate180),
+            // i32::MIN is 232° and i32::MAX 127° modulo 360°.
+            (i32::MIN, Rotation::Rotate270),
+            (i32::MAX, Rotation::Rotate90),
+        ];
+        for (degrees, rotation) in rounded {
+            assert_eq!(
+                Rotation::from_degrees_rounded(degrees),
+                rotation,
+                "{degrees}"
+            );
+        }
+        for degrees in (-100_000..100_000).step_by(7) {
+            let rotation = Rotation::from_degrees_rounded(degrees);
+            let error = (degrees - i32::from(rotation.to_degrees())).rem_euclid(360);
+            assert!(error <= 45 || error >= 315, "{degrees}");
+        }
+    }
+
+    #[test]
+    fn test_sin_cos_q15() {
+        assert_eq!(sin_cos_q15(0), (0, 32768));
+        assert_eq!(sin_cos_q15(90), (32768, 0));
+        assert_eq!(sin_cos_q15(180), (0, -32768));
+        assert_eq!(sin_cos_q15(-90), (-32768, 0));
+        assert_eq!(sin_cos_q15(i32::MIN), sin_cos_q15(232));
+
+        for degrees in -720..720 {
+            let radians = (degrees as f64).to_radians();
+            let (sin, cos) = sin_cos_q15(degrees);
+            assert!(
+                (sin as f64 - radians.sin() * 32768.0).abs() <= 0.5,
+                "{degrees}"
+            );
+            assert!(
+                (cos as f64 - radians.cos() * 32768.0).abs() <= 0.5,
+                "{degrees}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rotate_point_deg() {
+        let center = Point::new(7, -4);
+        for rotation in ROTATIONS {
+            let degrees = i32::from(rotation.to_degrees());
+            for point in Rectangle::new(Point::new(-30, -30), Size::new(60, 60)).points() {
+                assert_eq!(
+                    rotate_point_deg(point, degrees, center),
+                    rotate_point(point, rotation, center)
+                );
+            }
+        }
+
+        for degrees in (-360..=360).step_by(7) {
+            let radians = (degrees as f64).to_radians();
+            for radius in [1, 5, 17, 60, 133, 200] {
+                for point in [
+                    Point::new(radius, 0),
+                    Point::new(0, -radius),
+                    Point::new(radius, radius / 3),
+                    Point::new(-radius / 2, radius),
+                ] {
+                    let rotated = rotate_point_deg(point + center, degrees, center) - center;
+                    let (x, y) = (point.x as f64, point.y as f64);
+                    let expected_x = x * radians.cos() - y * radians.sin();
+                    let expected_y = x * radians.sin() + y * radians.cos();
+                    assert!(
+                        (rotated.x as f64 - expected_x).abs() <= 1.0
+                            && (rotated.y as f64 - expected_y).abs() <= 1.0,
+                        "{degrees}° {point:?} -> {rotated:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_point_rotate_ext() {
+        let center = Point::new(-3, 11);
+        for rotation in ROTATIONS {
+            for point in Rectangle::new(Point::new(-5, -5), Size::new(10, 10)).points() {
+                assert_eq!(
+                    point.rotated(rotation),
+                    rotate_point(point, rotation, Point::zero())
+                );
+                assert_eq!(
+                    point.rotated_around(rotation, center),
+                    rotate_point(point, rotation, center)
+                );
+            }
+        }
+
+        let point = Point::new(7, -2);
+        assert_eq!(point.flipped_x(3), Point::new(-1, -2));
+        assert_eq!(point.flipped_y(0), Point::new(7, 2));
+        assert_eq!(point.flipped_x(7), point);
+        assert_eq!(point.flipped_x(-4).flipped_x(-4), point);
+
+        // Flipping both axes is the same as rotating by 180°.
+        assert_eq!(
+            point.flipped_x(center.x).flipped_y(center.y),
+            point.rotated_around(Rotation::Rotate180, center)
+        );
+    }
+
+    #[test]
+    fn test_rotate_points() {
+        let center = Point::new(12, -7);
+        let outline: Vec<Point> = (0..200)
+            .map(|i| Point::new(i * 7 % 61 - 30, i * 13 % 47 - 20))
+            .collect();
+
+        for rotation in ROTATIONS {
+            let expected: Vec<Point> = outline
+                .iter()
+                .map(|point| rotate_point(*point, rotation, center))
+                .collect();
+
+            let mut points = outline.clone();
+            rotate_points_in_place(&mut points, rotation, center);
+            assert_eq!(points, expected, "{rotation:?}");
+            assert!(rotate_points(&outline, rotation, center).eq(expected));
+
+            let mut empty: [Point; 0] = [];
+            rotate_points_in_place(&mut empty, rotation, center);
+            assert_eq!(rotate_points(&empty, rotation, center).count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_rotate_size() {
+        const PANEL: Size = Rotation::Rotate90.rotate_size(Size::new(240, 320));
+        assert_eq!(PANEL, Size::new(320, 240));
+
+        let size = Size::new(5, 3);
+        let expected = [
+            (Rotation::Rotate0, false, Size::new(5, 3)),
+            (Rotation::Rotate90, true, Size::new(3, 5)),
+            (Rotation::Rotate180, false, Size::new(5, 3)),
+            (Rotation::Rotate270, true, Size::new(3, 5)),
+        ];
+        for (rotation, swapped, rotated) in expected {
+            assert_eq!(rotation.is_axis_swapped(), swapped);
+            assert_eq!(rotation.rotate_size(size), rotated, "{rotation:?}");
+            assert_eq!(rotation.rotate_size(Size::zero()), Size::zero());
+            assert_eq!(
+                rotation.rotate_size(Size::new(0, 4)).width,
+                u32::from(swapped) * 4
+            );
+        }
+    }
+
+    #[test]
+    fn test_rotate_rectangle_round_trip() {
+        let rect = Rectangle::new(Point::new(3, -2), Size::new(7, 4));
+        let center = Point::new(10, 5);
+
+        let mut rotated = rect;
+        for _ in 0..4 {
+            rotated = rotate_rectangle(&rotated, Rotation::Rotate90, center);
+            assert_eq!(rotated.size.width * rotated.size.height, 28);
+        }
+        assert_eq!(rotated, rect);
+        assert_eq!(rotate_rectangle(&rect, Rotation::Rotate0, center), rect);
+    }
+
+    #[test]
+    fn test_rotate_rectangle_bounding_box() {
+        // A 10x4 bar whose pixels span x 2..=11 and y 1..=4, rotated around the origin.
+        let rect = Rectangle::new(Point::new(2, 1), Size::new(10, 4));
+        let expected = [
+            (Rotation::Rotate90, Point::new(-4, 2), Size::new(4, 10)),
+            (Rotation::Rotate180, Point::new(-11, -4), Size::new(10, 4)),
+            (Rotation::Rotate270, Point::new(1, -11), Size::new(4, 10)),
+        ];
+        for (rotation, top_left, size) in expected {
+            let rotated = rotate_rectangle(&rect, rotation, Point::zero());
+            assert_eq!(rotated, Rectangle::new(top_left, size), "{rotation:?}");
+
+            for point in rect.points() {
+                assert!(rotated.contains(rotate_point(point, rotation, Point::zero())));
+            }
+        }
+
+        let empty = Rectangle::new(Point::new(2, 1), Size::new(0, 3));
+        let rotated = rotate_rectangle(&empty, Rotation::Rotate90, Point::zero());
+        assert_eq!(rotated.size, Size::new(3, 0));
+    }
+
+    #[test]
+    fn test_madctl() {
+        assert_eq!(Rotation::Rotate0.to_madctl(Flip::None, false), 0x00);
+        assert_eq!(Rotation::Rotate90.to_madctl(Flip::None, false), 0x60);
+        assert_eq!(Rotation::Rotate180.to_madctl(Flip::None, true), 0xc8);
+        assert_eq!(Rotation::Rotate270.to_madctl(Flip::None, false), 0xa0);
+        assert_eq!(Rotation::Rotate0.to_madctl(Flip::Horizontal, false), 0x40);
+
+        for rotation in ROTATIONS {
+            for bgr in [false, true] {
+                for flip in [Flip::None, Flip::Horizontal] {
+                    let madctl = rotation.to_madctl(flip, bgr);
+                    assert_eq!(Rotation::from_madctl(madctl), Some((rotation, flip, bgr)));
+                }
+
+                // Other flips decode to an orientation that maps points the same way.
+                for flip in [Flip::Vertical, Flip::Both] {
+                    let madctl = rotation.to_madctl(flip, bgr);
+                    let (decoded, decoded_flip, decoded_bgr) =
+                        Rotation::from_madctl(madctl).unwrap();
+                    assert_eq!(decoded_bgr, bgr);
+                    let point = Point::new(3, -8);
+                    let orientation = crate::flip::Orientation::new(rotation, flip);
+                    let decoded = crate::flip::Orientation::new(decoded, decoded_flip);
+                    assert_eq!(
+                        decoded.transform_point(point, Point::zero()),
+                        orientation.transform_point(point, Point::zero())
+                    );
+                }
+            }
+        }
+
+        let values: Vec<_> = (0..=u8::MAX).filter_map(Rotation::from_madctl).collect();
+        assert_eq!(values.len(), 16);
+        assert_eq!(Rotation::from_madctl(0x10), None);
+    }
+
+    #[test]
+    fn test_rect_corners_round_trip() {
+        let sorted = |points: &mut Vec<Point>| points.sort_by_key(|point| (point.x, point.y));
+        let center = Point::new(1, -2);
+        for width in 1..=4 {
+            for height in 1..=4 {
+                for (x, y) in [(-1, 0), (0, 0), (2, -3)] {
+                    let rect = Rectangle::new(Point::new(x, y), Size::new(width, height));
+                    let corners = rect_corners(&rect);
+                    assert_eq!(normalized_rect_from_corners(corners[1], corners[3]), rect);
+
+                    for rotation in ROTATIONS {
+                        let [a, b, c, d] =
+                            corners.map(|corner| rotate_point(corner, rotation, center));
+                        let rotated = normalized_rect_from_corners(a, c);
+                        assert_eq!(normalized_rect_from_corners(d, b), rotated);
+                        assert_eq!(rotate_rectangle(&rect, rotation, center), rotated);
+
+                        let mut expected: Vec<_> = rect
+                            .points()
+                            .map(|point| rotate_point(point, rotation, center))
+                            .collect();
+                        let mut actual: Vec<_> = rotated.points().collect();
+                        sorted(&mut expected);
+                        sorted(&mut actual);
+                        assert_eq!(actual, expected, "{rect:?} {rotation:?}");
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            rect_corners(&Rectangle::new(center, Size::zero())),
+            [center; 4]
+        );
+    }
+
+    #[test]
+    fn test_rotate_rect_in_place() {
+        for (width, height) in [(6, 4), (6, 3), (5, 4), (5, 3), (1, 8)] {
+            let rect = Rectangle::new(Point::new(-7, 12), Size::new(width, height));
+            for rotation in ROTATIONS {
+                let mut rotated = rect;
+                for _ in 0..4 {
+                    rotated = rotate_rect_in_place(&rotated, rotation);
+                    assert_eq!(rotated.center(), rect.center());
+                }
+                assert_eq!(rotated, rect, "{width}x{height} {rotation:?}");
+
+                let back = rotate_rect_in_place(
+                    &rotate_rect_in_place(&rect, rotation),
+                    rotation.inverse(),
+                );
+                assert_eq!(back, rect);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotated_draw_target() {
+        use embedded_graphics::mock_display::MockDisplay;
+
+        // Logical positions of an asymmetric pattern and where they end up on the 64x64 panel.
+        let expected = [
+            (
+                Rotation::Rotate0,
+                [(0, 0), (1, 0), (0, 2)],
+                Rectangle::new(Point::new(2, 1), Size::new(3, 2)),
+            ),
+            (
+                Rotation::Rotate90,
+                [(63, 0), (63, 1), (61, 0)],
+                Rectangle::new(Point::new(61, 2), Size::new(2, 3)),
+            ),
+            (
+                Rotation::Rotate180,
+                [(63, 63), (62, 63), (63, 61)],
+                Rectangle::new(Point::new(59, 61), Size::new(3, 2)),
+            ),
+            (
+                Rotation::Rotate270,
+                [(0, 63), (0, 62), (2, 63)],
+                Rectangle::new(Point::new(1, 59), Size::new(2, 3)),
+            ),
+        ];
+        let colors = [Rgb888::RED, Rgb888::GREEN, Rgb888::BLUE];
+
+        for (rotation, points, filled) in expected {
+            let mut display = MockDisplay::<Rgb888>::new();
+            let mut rotated = RotatedDrawTarget::new(&mut display, rotation);
+            assert_eq!(
+                rotated.bounding_box(),
+                Rectangle::new(Point::zero(), Size::new(64, 64))
+            );
+
+            let pattern = [Point::new(0, 0), Point::new(1, 0), Point::new(0, 2)];
+            rotated
+                .draw_iter(
+                    pattern
+                        .into_iter()
+                        .zip(colors)
+                        .map(|(point, color)| Pixel(point, color)),
+                )
+                .unwrap();
+            let area = Rectangle::new(Point::new(2, 1), Size::new(3, 2));
+            rotated.fill_solid(&area, Rgb888::WHITE).unwrap();
+
+            let mut reference = MockDisplay::<Rgb888>::new();
+            for ((x, y), color) in points.into_iter().zip(colors) {
+                reference.set_pixel(Point::new(x, y), Some(color));
+            }
+            reference.fill_solid(&filled, Rgb888::WHITE).unwrap();
+            display.assert_eq(&reference);
+
+            // Contiguous colors follow the logical rows.
+            let mut display = MockDisplay::<Rgb888>::new();
+            let mut rotated = RotatedDrawTarget::new(&mut display, rotation);
+            let shades = (0..6).map(|value| Rgb888::new(value * 40, 0, 0));
+            rotated.fill_contiguous(&area, shades.clone()).unwrap();
+            let rotated = RotatedDrawTarget::new(&mut reference, rotation);
+            for (point, color) in area.points().zip(shades) {
+                assert_eq!(display.get_pixel(rotated.to_physical(point)), Some(color));
+            }
+        }
+    }
+
+    // Counts the calls that reach the parent.
+    #[derive(Default)]
+    struct Counting {
+        display: embedded_graphics::mock_display::MockDisplay<Rgb888>,
+        calls: [usize; 4],
+    }
+
+    const DRAW_ITER: usize = 0;
+    const FILL_CONTIGUOUS: usize = 1;
+    const FILL_SOLID: usize = 2;
+    const CLEAR: usize = 3;
+
+    impl Dimensions for Counting {
+        fn bounding_box(&self) -> Rectangle {
+            self.display.bounding_box()
+        }
+    }
+
+    impl DrawTarget for Counting {
+        type Color = Rgb888;
+        type Error = core::convert::Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            self.calls[DRAW_ITER] += 1;
+            self.display.draw_iter(pixels)
+        }
+
+        fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Self::Color>,
+        {
+            self.calls[FILL_CONTIGUOUS] += 1;
+            let pixels = area
+                .points()
+                .zip(colors)
+                .map(|(point, color)| Pixel(point, color));
+            self.display.draw_iter(pixels)
+        }
+
+        fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+            self.calls[FILL_SOLID] += 1;
+            self.display.fill_solid(area, color)
+        }
+
+        fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+            self.calls[CLEAR] += 1;
+            self.display.clear(color)
+        }
+    }
+
+    #[test]
+    fn test_rotated_draw_target_calls() {
+        let area = Rectangle::new(Point::new(3, 5), Size::new(7, 4));
+        let shades = || (0..28).map(|value| Rgb888::new(value * 9, 0, 0));
+        let pixels = [Pixel(Point::new(1, 2), Rgb888::RED)];
+
+        for rotation in ROTATIONS {
+            let mut counting = Counting::default();
+            let mut rotated = RotatedDrawTarget::new(&mut counting, rotation);
+            rotated.fill_solid(&area, Rgb888::BLUE).unwrap();
+            assert_eq!(counting.calls, [0, 0, 1, 0], "{rotation:?}");
+
+            let mut counting = Counting::default();
+            RotatedDrawTarget::new(&mut counting, rotation)
+                .clear(Rgb888::BLUE)
+                .unwrap();
+            assert_eq!(counting.calls, [0, 0, 0, 1], "{rotation:?}");
+
+            let mut counting = Counting::default();
+            RotatedDrawTarget::new(&mut counting, rotation)
+                .draw_iter(pixels)
+                .unwrap();
+            assert_eq!(counting.calls, [1, 0, 0, 0], "{rotation:?}");
+
+            let mut counting = Counting::default();
+            let mut rotated = RotatedDrawTarget::new(&mut counting, rotation);
+            rotated.fill_contiguous(&area, shades()).unwrap();
+            let expected = match rotation {
+                Rotation::Rotate0 => [0, 1, 0, 0],
+                Rotation::Rotate180 => [0, 4, 0, 0],
+                _ => [1, 0, 0, 0],
+            };
+            assert_eq!(counting.calls, expected, "{rotation:?}");
+
+            let mut reference = Counting::default();
+            let mut rotated = RotatedDrawTarget::new(&mut reference, rotation);
+            let pixels = area
+                .points()
+                .zip(shades())
+                .map(|(point, color)| Pixel(point, color));
+            rotated.draw_iter(pixels).unwrap();
+            counting.display.assert_eq(&reference.display);
+
+            // A short iterator stops within the last row.
+            let mut counting = Counting::default();
+            let mut rotated = RotatedDrawTarget::new(&mut counting, rotation);
+            rotated.fill_contiguous(&area, shades().take(17)).unwrap();
+            let mut reference = Counting::default();
+            let mut rotated = RotatedDrawTarget::new(&mut reference, rotation);
+            let pixels = area
+                .points()
+                .zip(shades().take(17))
+                .map(|(point, color)| Pixel(point, color));
+            rotated.draw_iter(pixels).unwrap();
+            counting.display.assert_eq(&reference.display);
+        }
+    }
+
+    #[test]
+    fn test_rotated_draw_target_to_logical() {
+        use embedded_graphics::pixelcolor::BinaryColor;
+        use embedded_graphics_simulator::SimulatorDisplay;
+
+        for size in [Size::new(30, 20), Size::new(31, 20), Size::new(31, 21)] {
+            for rotation in ROTATIONS {
+                let mut display = SimulatorDisplay::<BinaryColor>::new(size);
+                let mut rotated = RotatedDrawTarget::new(&mut display, rotation);
+                let logical = Point::new(4, 17);
+                Pixel(logical, BinaryColor::On).draw(&mut rotated).unwrap();
+                let expected = rotated.to_physical(logical);
+
+                let physical = Rectangle::new(Point::zero(), size)
+                    .points()
+                    .find(|&point| display.get_pixel(point) == BinaryColor::On)
+                    .unwrap();
+                assert_eq!(physical, expected, "{size:?} {rotation:?}");
+                let rotated = RotatedDrawTarget::new(&mut display, rotation);
+                assert_eq!(
+                    rotated.to_logical(physical),
+                    logical,
+                    "{size:?} {rotation:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotated_draw_target_size() {
+        use embedded_graphics::pixelcolor::BinaryColor;
+        use embedded_graphics_simulator::SimulatorDisplay;
+
+        let mut display = SimulatorDisplay::<BinaryColor>::new(Size::new(30, 20));
+        for rotation in [Rotation::Rotate90, Rotation::Rotate270] {
+            let mut rotated = RotatedDrawTarget::new(&mut display, rotation);
+            assert_eq!(
+                rotated.bounding_box(),
+                Rectangle::new(Point::zero(), Size::new(20, 30))
+            );
+
+            // The logical bottom right corner is still on the panel.
+            let corner = Rectangle::new(Point::new(19, 29), Size::new(1, 1));
+            rotated.fill_solid(&corner, BinaryColor::On).unwrap();
+            let physical = rotated.to_physical(Point::new(19, 29));
+            assert_eq!(display.get_pixel(physical), BinaryColor::On);
+        }
+    }
+
+    #[test]
+    fn test_rotated_draw_target_window() {
+        use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+        // A 20x12 window in a 64x64 RAM, placed differently in every orientation.
+        let size = Size::new(20, 12);
+        let offsets = [
+            Point::new(5, 7),
+            Point::new(7, 5),
+            Point::new(30, 40),
+            Point::new(40, 30),
+        ];
+        let origins = [(5, 7), (26, 5), (49, 51), (40, 41)];
+
+        for (rotation, origin) in ROTATIONS.into_iter().zip(origins) {
+            let mut display = MockDisplay::new();
+            let mut rotated = RotatedDrawTarget::with_window(&mut display, rotation, size, offsets);
+            let bounds = rotated.bounding_box();
+            assert_eq!(bounds.size, rotation.rotate_size(size));
+            assert_eq!(
+                rotated.to_physical(Point::zero()),
+                Point::new(origin.0, origin.1)
+            );
+
+            rotated.clear(BinaryColor::On).unwrap();
+            let mut expected = MockDisplay::new();
+            let window = Rectangle::new(offsets[rotation.quadrants() as usize], size);
+            expected.fill_solid(&window, BinaryColor::On).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_rotated_faded_rectangle() {
+        use crate::{FadedRectangle, Fading};
+        use embedded_graphics::mock_display::MockDisplay;
+
+        let rect = Rectangle::new(Point::new(10, 20), Size::new(30, 12));
+        let faded = FadedRectangle::new(rect, Rgb888::CSS_TEAL, Fading::Left { steps: 7 });
+        let rotated = Rotated::new(faded, Rotation::Rotate90, Point::new(30, 30));
+
+        // Every pixel is drawn once, not just the faded ones. MockDisplay panics on overdraw.
+        let mut display = MockDisplay::<Rgb888>::new();
+        rotated.draw(&mut display).unwrap();
+        let drawn = display
+            .affected_area()
+            .points()
+            .filter(|point| display.get_pixel(*point).is_some())
+            .count();
+        assert_eq!(drawn, rect.points().count());
+
+        // The left edge ends up at the top.
+        let bounds = rotated.bounding_box();
+        assert_eq!(
+            bounds,
+            Rectangle::new(Point::new(29, 10), Size::new(12, 30))
+        );
+        let mut expected = MockDisplay::<Rgb888>::new();
+        FadedRectangle::new(bounds, Rgb888::CSS_TEAL, Fading::Top { steps: 7 })
+            .draw(&mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_rotate_iter_composition() {
+        use embedded_graphics::pixelcolor::BinaryColor;
+
+        let pixels = [
+            Pixel(Point::new(3, 1), BinaryColor::On),
+            Pixel(Point::new(-2, 5), BinaryColor::Off),
+            Pixel(Point::new(0, 0), BinaryColor::On),
+        ];
+        let center = Point::new(1, 2);
+
+        let twice = pixels
+            .into_iter()
+            .rotated(Rotation::Rotate90, center)
+            .rotated(Rotation::Rotate90, center);
+        assert_eq!(twice.size_hint(), (3, Some(3)));
+        assert!(twice.eq(pixels.into_iter().rotated(Rotation::Rotate180, center)));
+
+        let back = pixels
+            .into_iter()
+            .rotated(Rotation::Rotate270, center)
+            .rotated(Rotation::Rotate90, center);
+        assert!(back.eq(pixels));
+        assert_eq!(
+            pixels
+                .iter()
+                .copied()
+                .rotated(Rotation::Rotate0, center)
+                .len(),
+            3
+        );
+    }
+
+    #[cfg(feature = "libm")]
+    #[test]
+    fn test_rotate_point_f32_round_trip() {
+        let center = Point::new(3, -8);
+        for step in 0..720 {
+            let radians = step as f32 * core::f32::consts::PI / 360.0;
+            for point in [
+                Point::new(0, 0),
+                Point::new(12, 5),
+                Point::new(-40, 17),
+                Point::new(150, -90),
+            ] {
+                let rotated = rotate_point_f32(point, radians, center);
+                let back = rotate_point_f32(rotated, -radians, center);
+                let drift = back - point;
+                assert!(
+                    drift.x.abs() <= 1 && drift.y.abs() <= 1,
+                    "{radians} {point:?} -> {back:?}"
+                );
+            }
+        }
+
+        let quarter = core::f32::consts::FRAC_PI_2;
+        for point in Rectangle::new(Point::new(-20, -20), Size::new(40, 40)).points() {
+            assert_eq!(
+                rotate_point_f32(point, quarter, center),
+                rotate_point(point, Rotation::Rotate90, center)
+            );
+        }
+    }
+
+    #[cfg(feature = "libm")]
+    #[test]
+    fn test_angle_rotated() {
+        use embedded_graphics::{
+            mock_display::MockDisplay, pixelcolor::BinaryColor, primitives::PrimitiveStyle,
+        };
+
+        let rect = Rectangle::new(Point::new(20, 20), Size::new(10, 4));
+        let filled = rect.into_styled(PrimitiveStyle::with_fill(BinaryColor::On));
+        let rotated = AngleRotated::new(filled, core::f32::consts::PI, Point::new(30, 30));
+
+        let mut display = MockDisplay::new();
+        rotated.draw(&mut display).unwrap();
+        let expected = Rectangle::new(Point::new(31, 37), Size::new(10, 4));
+        assert_eq!(display.affected_area(), expected);
+        assert_eq!(rotated.bounding_box(), expected);
+    }
+}
@@ -0,0 +1,162 @@
+use embedded_graphics::{pixelcolor::PixelColor, prelude::*, primitives::Rectangle};
+
+/// A [`DrawTarget`] that draws every pixel as a `factor` x `factor` block on `parent`, e.g. to
+/// show a UI made for 160x120 on a 320x240 panel.
+///
+/// If the size of `parent` isn't a multiple of `factor`, the blocks of the last row and column
+/// are cut off.
+#[derive(Debug)]
+pub struct ScaledDrawTarget<'a, D> {
+    parent: &'a mut D,
+    factor: u32,
+}
+
+impl<'a, D> ScaledDrawTarget<'a, D>
+where
+    D: DrawTarget,
+{
+    /// # Panics
+    ///
+    /// Panics if `factor` is zero.
+    pub fn new(parent: &'a mut D, factor: u32) -> Self {
+        assert!(factor > 0, "scale factor must not be zero");
+        Self { parent, factor }
+    }
+
+    /// The block on the parent that the logical `area` covers, cut off at the parent's bounds.
+    pub fn to_physical(&self, area: &Rectangle) -> Rectangle {
+        let area = Rectangle::new(area.top_left * self.factor as i32, area.size * self.factor);
+        area.intersection(&self.parent.bounding_box())
+    }
+}
+
+impl<D> Dimensions for ScaledDrawTarget<'_, D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        let bounds = self.parent.bounding_box();
+        let Some(bottom_right) = bounds.bottom_right() else {
+            return Rectangle::new(logical(bounds.top_left, self.factor), Size::zero());
+        };
+
+        Rectangle::with_corners(
+            logical(bounds.top_left, self.factor),
+            logical(bottom_right, self.factor),
+        )
+    }
+}
+
+// The logical point that the physical `point` belongs to.
+fn logical(point: Point, factor: u32) -> Point {
+    let factor = factor as i32;
+    Point::new(point.x.div_euclid(factor), point.y.div_euclid(factor))
+}
+
+impl<D, C> DrawTarget for ScaledDrawTarget<'_, D>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let block = self.to_physical(&Rectangle::new(point, Size::new(1, 1)));
+            self.parent.fill_solid(&block, color)?;
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        // Repeating rows would need a buffer, so every color is filled as its own block.
+        let pixels = area
+            .points()
+            .zip(colors)
+            .map(|(point, color)| Pixel(point, color));
+        self.draw_iter(pixels)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.to_physical(area);
+        self.parent.fill_solid(&area, color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::faded_rectangle::{FadedRectangle, Fading};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888};
+
+    #[test]
+    fn test_scaled_faded_rectangle() {
+        let rect = Rectangle::new(Point::new(3, 2), Size::new(24, 10));
+        let faded = FadedRectangle::new(rect, Rgb888::CSS_TEAL, Fading::Right { steps: 6 })
+            .with_corner_radius(2);
+
+        let mut display = MockDisplay::new();
+        let mut scaled = ScaledDrawTarget::new(&mut display, 2);
+        assert_eq!(
+            scaled.bounding_box(),
+            Rectangle::new(Point::zero(), Size::new(32, 32))
+        );
+        faded.draw(&mut scaled).unwrap();
+
+        let mut logical = MockDisplay::new();
+        faded.draw(&mut logical).unwrap();
+        let mut expected = MockDisplay::new();
+        for point in Rectangle::new(Point::zero(), Size::new(32, 32)).points() {
+            if let Some(color) = logical.get_pixel(point) {
+                let block = Rectangle::new(point * 2, Size::new(2, 2));
+                expected.fill_solid(&block, color).unwrap();
+            }
+        }
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_scaled_partial_blocks() {
+        // 64 isn't a multiple of 3, so the last blocks are a single pixel wide.
+        let mut display = MockDisplay::new();
+        let mut scaled = ScaledDrawTarget::new(&mut display, 3);
+        let bounds = scaled.bounding_box();
+        assert_eq!(bounds.size, Size::new(22, 22));
+        scaled.fill_solid(&bounds, Rgb888::RED).unwrap();
+
+        let mut expected = MockDisplay::new();
+        expected.clear(Rgb888::RED).unwrap();
+        display.assert_eq(&expected);
+
+        let mut display = MockDisplay::new();
+        let mut scaled = ScaledDrawTarget::new(&mut display, 3);
+        let corner = Point::new(21, 21);
+        scaled
+            .draw_iter([
+                Pixel(corner, Rgb888::GREEN),
+                Pixel(corner + Point::new(1, 0), Rgb888::GREEN),
+            ])
+            .unwrap();
+        scaled
+            .fill_contiguous(
+                &Rectangle::new(Point::new(0, 21), Size::new(2, 1)),
+                [Rgb888::BLUE, Rgb888::RED],
+            )
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        expected.set_pixel(Point::new(63, 63), Some(Rgb888::GREEN));
+        let blue = Rectangle::new(Point::new(0, 63), Size::new(3, 1));
+        expected.fill_solid(&blue, Rgb888::BLUE).unwrap();
+        let red = Rectangle::new(Point::new(3, 63), Size::new(3, 1));
+        expected.fill_solid(&red, Rgb888::RED).unwrap();
+        display.assert_eq(&expected);
+    }
+}
@@ -0,0 +1,195 @@
+use embedded_graphics::pixelcolor::{raw::RawData, PixelColor};
+
+/// Colors that are serialized as their raw value, e.g. `0xRRGGBB` for `Rgb888`.
+pub trait RawColor: PixelColor {
+    fn to_u32(self) -> u32;
+    fn from_u32(value: u32) -> Self;
+}
+
+impl<C> RawColor for C
+where
+    C: PixelColor + Into<C::Raw> + From<C::Raw>,
+    <C::Raw as RawData>::Storage: Into<u32>,
+{
+    fn to_u32(self) -> u32 {
+        let raw: C::Raw = self.into();
+        raw.into_inner().into()
+    }
+
+    fn from_u32(value: u32) -> Self {
+        C::Raw::from_u32(value).into()
+    }
+}
+
+/// `#[serde(with = ...)]` helpers for colors implementing [`RawColor`].
+pub mod color {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::RawColor;
+
+    pub fn serialize<S, C>(color: &C, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        C: RawColor,
+    {
+        color.to_u32().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, C>(deserializer: D) -> Result<C, D::Error>
+    where
+        D: Deserializer<'de>,
+        C: RawColor,
+    {
+        u32::deserialize(deserializer).map(C::from_u32)
+    }
+}
+
+/// `#[serde(with = ...)]` helpers that serialize a
+/// [`Point`](embedded_graphics::prelude::Point) as `{ x, y }`.
+pub mod point {
+    use embedded_graphics::prelude::Point;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct PointData {
+        x: i32,
+        y: i32,
+    }
+
+    pub fn serialize<S>(point: &Point, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Point { x, y } = *point;
+        PointData { x, y }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Point, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let PointData { x, y } = PointData::deserialize(deserializer)?;
+        Ok(Point::new(x, y))
+    }
+}
+
+/// `#[serde(with = ...)]` helpers that serialize a
+/// [`Rectangle`](embedded_graphics::primitives::Rectangle) as `{ x, y, w, h }`.
+pub mod rectangle {
+    use embedded_graphics::{prelude::*, primitives::Rectangle};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct RectangleData {
+        x: i32,
+        y: i32,
+        w: u32,
+        h: u32,
+    }
+
+    pub fn serialize<S>(rect: &Rectangle, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        RectangleData {
+            x: rect.top_left.x,
+            y: rect.top_left.y,
+            w: rect.size.width,
+            h: rect.size.height,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Rectangle, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let RectangleData { x, y, w, h } = RectangleData::deserialize(deserializer)?;
+        Ok(Rectangle::new(Point::new(x, y), Size::new(w, h)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Debug;
+
+    use embedded_graphics::{
+        pixelcolor::{Rgb565, Rgb888},
+        prelude::*,
+        primitives::Rectangle,
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use crate::{
+        curve::{FadeCurve, FadeProfile},
+        faded_rectangle::{Corner, FadedRectangle, Fading},
+        flip::{Flip, Orientation},
+        gradient::{GradientDirection, GradientRectangle},
+        needle::Needle,
+        rotate::Rotation,
+        transform::Transform2D,
+    };
+
+    fn round_trip<T>(value: T)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let bytes = postcard::to_allocvec(&value).unwrap();
+        assert_eq!(postcard::from_bytes::<T>(&bytes).unwrap(), value);
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value, "{json}");
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let rect = Rectangle::new(Point::new(-3, 7), Size::new(40, 12));
+        let profile = FadeProfile::new(&[(0, 0), (100, 200), (255, 255)]).unwrap();
+        let faded = FadedRectangle::new(
+            rect,
+            Rgb888::CSS_ORANGE,
+            Fading::Diagonal {
+                steps: 9,
+                corner: Corner::BottomLeft,
+            },
+        )
+        .with_fade_target(Rgb888::new(1, 2, 3))
+        .with_curve(FadeCurve::Profile(profile))
+        .with_corner_radius(4);
+        round_trip(faded);
+        round_trip(faded.with_stroke(Rgb888::CSS_NAVY, 2));
+        round_trip(faded.frame(3));
+        round_trip(FadedRectangle::binary(rect, Fading::Top { steps: 3 }));
+        round_trip(GradientRectangle::new(
+            rect,
+            Rgb565::RED,
+            Rgb565::BLUE,
+            GradientDirection::Vertical,
+        ));
+        round_trip(Needle::new(Point::new(9, 8), 30, 3, -45, Rgb888::CSS_RED));
+        round_trip(Rotation::Rotate270);
+        round_trip(Orientation::new(Rotation::Rotate90, Flip::Horizontal));
+        round_trip(
+            Transform2D::rotation(Rotation::Rotate90, Point::new(5, 6))
+                .then(Transform2D::translation(Point::new(-1, 2))),
+        );
+    }
+
+    #[test]
+    fn test_json_layout() {
+        let rect = Rectangle::new(Point::new(1, 2), Size::new(3, 4));
+        let faded = FadedRectangle::new(rect, Rgb888::new(0x12, 0x34, 0x56), Fading::None);
+        let json = serde_json::to_value(faded).unwrap();
+        assert_eq!(
+            json["rect"],
+            serde_json::json!({ "x": 1, "y": 2, "w": 3, "h": 4 })
+        );
+        assert_eq!(json["base_color"], 0x12_3456);
+        assert_eq!(json["style"]["target"], 0);
+
+        let invalid = r#"{ "points": [[9, 0], [3, 0], [0, 0], [0, 0], [0, 0], [0, 0]], "len": 2 }"#;
+        assert!(serde_json::from_str::<FadeProfile>(invalid).is_err());
+        let too_long =
+            r#"{ "points": [[0, 0], [3, 0], [0, 0], [0, 0], [0, 0], [0, 0]], "len": 9 }"#;
+        assert!(serde_json::from_str::<FadeProfile>(too_long).is_err());
+    }
+}
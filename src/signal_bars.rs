@@ -0,0 +1,279 @@
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+
+use crate::{
+    color::FadeColor,
+    diff_drawable::DiffDrawable,
+    faded_rectangle::{union, FadedRectangle, Fading},
+    progress_bar::split,
+};
+
+/// Ascending signal strength bars, the first `active` of them in `active_color` and the rest
+/// in `inactive_color`.
+///
+/// The bars are bottom aligned, the highest one fills the height of `bounds`. The space above
+/// the bars and the gaps between them aren't drawn.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct SignalBars<C = Rgb888> {
+    pub bounds: Rectangle,
+    pub bars: u8,
+    /// Values above `bars` show all bars as active.
+    pub active: u8,
+    pub active_color: C,
+    pub inactive_color: C,
+    pub gap: u32,
+    /// Fading of the inactive bars, e.g. [`Fading::Top`] towards the fade target.
+    pub inactive_fading: Fading,
+}
+
+impl<C> SignalBars<C>
+where
+    C: FadeColor,
+{
+    pub fn new(bounds: Rectangle, bars: u8, active_color: C, inactive_color: C) -> Self {
+        Self {
+            bounds,
+            bars,
+            active: 0,
+            active_color,
+            inactive_color,
+            gap: 1,
+            inactive_fading: Fading::None,
+        }
+    }
+
+    pub fn with_active(mut self, active: u8) -> Self {
+        self.active = active;
+        self
+    }
+
+    pub fn with_gap(mut self, gap: u32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn with_inactive_fading(mut self, fading: Fading) -> Self {
+        self.inactive_fading = fading;
+        self
+    }
+
+    /// The areas of the bars from left to right.
+    pub fn bar_rects(&self) -> impl Iterator<Item = Rectangle> {
+        let count = u32::from(self.bars);
+        let bottom = self.bounds.top_left.y + self.bounds.size.height as i32;
+        let height = u64::from(self.bounds.size.height);
+
+        split(self.bounds, count, self.gap, true)
+            .zip(1..)
+            .map(move |(column, index)| {
+                let bar_height = (height * index / u64::from(count)) as u32;
+                Rectangle::new(
+                    Point::new(column.top_left.x, bottom - bar_height as i32),
+                    Size::new(column.size.width, bar_height),
+                )
+            })
+    }
+
+    fn draw_bar<D>(&self, target: &mut D, rect: Rectangle, active: bool) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if active {
+            target.fill_solid(&rect, self.active_color)
+        } else {
+            FadedRectangle::new(rect, self.inactive_color, self.inactive_fading).draw(target)
+        }
+    }
+
+    /// Sets `active` and redraws the bars that changed between active and inactive over an
+    /// earlier drawing.
+    ///
+    /// Returns the bounding box of all written pixels, `None` if nothing changed.
+    pub fn set_active<D>(
+        &mut self,
+        target: &mut D,
+        active: u8,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let previous = self.active.min(self.bars);
+        self.active = active;
+        let active = active.min(self.bars);
+
+        let (start, end) = (previous.min(active), previous.max(active));
+        let mut dirty = None;
+        for (rect, index) in self.bar_rects().zip(0..).skip(start.into()) {
+            if index >= end {
+                break;
+            }
+            self.draw_bar(target, rect, index < active)?;
+            dirty = union(dirty, &rect);
+        }
+        Ok(dirty)
+    }
+}
+
+impl<C> Dimensions for SignalBars<C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+impl<C> Drawable for SignalBars<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let active = self.active.min(self.bars);
+        self.bar_rects()
+            .zip(0..)
+            .try_for_each(|(rect, index)| self.draw_bar(target, rect, index < active))
+    }
+}
+
+/// The state is `active`, the other fields must be the same as in the earlier drawing.
+impl<C> DiffDrawable for SignalBars<C>
+where
+    C: FadeColor,
+{
+    type State = u8;
+
+    fn state(&self) -> Self::State {
+        self.active
+    }
+
+    fn draw_diff<D>(
+        &self,
+        target: &mut D,
+        previous: &Self::State,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let mut drawn = self.with_active(*previous);
+        drawn.set_active(target, self.active)
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+
+    fn signal(active: u8) -> SignalBars {
+        let bounds = Rectangle::new(Point::new(2, 3), Size::new(20, 12));
+        SignalBars::new(bounds, 4, Rgb888::WHITE, Rgb888::CSS_DIM_GRAY).with_active(active)
+    }
+
+    #[test]
+    fn test_signal_bars_layout() {
+        let rects = signal(0).bar_rects().collect::<Vec<_>>();
+        assert_eq!(
+            rects,
+            [
+                Rectangle::new(Point::new(2, 12), Size::new(4, 3)),
+                Rectangle::new(Point::new(7, 9), Size::new(4, 6)),
+                Rectangle::new(Point::new(12, 6), Size::new(4, 9)),
+                Rectangle::new(Point::new(17, 3), Size::new(5, 12)),
+            ]
+        );
+
+        let mut five = signal(0);
+        five.bars = 5;
+        let rects = five.bar_rects().collect::<Vec<_>>();
+        let columns = rects.iter().map(|r| (r.top_left.x, r.size.width));
+        assert_eq!(
+            columns.collect::<Vec<_>>(),
+            [(2, 3), (6, 3), (10, 3), (14, 3), (18, 4)]
+        );
+        let heights = rects.iter().map(|r| r.size.height);
+        assert_eq!(heights.collect::<Vec<_>>(), [2, 4, 7, 9, 12]);
+    }
+
+    #[test]
+    fn test_signal_bars_draw() {
+        let mut display = MockDisplay::new();
+        signal(2).draw(&mut display).unwrap();
+
+        let mut expected = MockDisplay::new();
+        for (rect, color) in signal(0).bar_rects().zip([
+            Rgb888::WHITE,
+            Rgb888::WHITE,
+            Rgb888::CSS_DIM_GRAY,
+            Rgb888::CSS_DIM_GRAY,
+        ]) {
+            expected.fill_solid(&rect, color).unwrap();
+        }
+        display.assert_eq(&expected);
+
+        // Too many active bars clamp, no bars draw nothing.
+        let mut all = MockDisplay::new();
+        signal(9).draw(&mut all).unwrap();
+        let mut four = MockDisplay::new();
+        signal(4).draw(&mut four).unwrap();
+        all.assert_eq(&four);
+
+        let mut none = signal(3);
+        none.bars = 0;
+        let mut display = MockDisplay::<Rgb888>::new();
+        none.draw(&mut display).unwrap();
+        assert_eq!(display.affected_area(), Rectangle::zero());
+        assert_eq!(none.set_active(&mut display, 1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_signal_bars_set_active() {
+        let mut signal = signal(1);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        signal.draw(&mut display).unwrap();
+        let rects = signal.bar_rects().collect::<Vec<_>>();
+
+        // Only the bars that flipped are redrawn.
+        let dirty = signal.set_active(&mut display, 3).unwrap();
+        assert_eq!(dirty, union(Some(rects[1]), &rects[2]));
+        assert_eq!(signal.set_active(&mut display, 3).unwrap(), None);
+        assert_eq!(signal.set_active(&mut display, 2).unwrap(), Some(rects[2]));
+        assert_eq!(
+            signal.set_active(&mut display, 200).unwrap(),
+            union(Some(rects[2]), &rects[3])
+        );
+        assert_eq!(signal.set_active(&mut display, 4).unwrap(), None);
+
+        for active in [0, 4, 2] {
+            signal.set_active(&mut display, active).unwrap();
+            let mut expected = MockDisplay::new();
+            signal.draw(&mut expected).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn test_signal_bars_draw_diff() {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        signal(1).draw(&mut display).unwrap();
+        let rects = signal(0).bar_rects().collect::<Vec<_>>();
+
+        let dirty = signal(3).draw_diff(&mut display, &1).unwrap();
+        assert_eq!(dirty, union(Some(rects[1]), &rects[2]));
+        assert_eq!(signal(9).draw_diff(&mut display, &4).unwrap(), None);
+
+        let mut previous = signal(3).state();
+        for active in [0, 200, 2, 4] {
+            signal(active).draw_diff(&mut display, &previous).unwrap();
+            previous = signal(active).state();
+
+            let mut expected = MockDisplay::new();
+            signal(active).draw(&mut expected).unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use embedded_graphics::{
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Line, PrimitiveStyle, Rectangle},
+};
+
+use crate::{
+    color::FadeColor,
+    faded_rectangle::{FadedRectangle, Fading},
+};
+
+/// A small trend chart of the last `N` samples, drawn as a line with the area below it filled.
+///
+/// The samples are scaled so that the lowest one touches the bottom and the highest one the
+/// top of `bounds`, constant samples give a flat line in the middle. The fill has the color of
+/// the line and fades towards `background` with `fill_fading` over the height of `bounds`,
+/// e.g. to the bottom for [`Fading::Bottom`]. The area above the line isn't drawn.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Sparkline<const N: usize, C = Rgb888> {
+    pub bounds: Rectangle,
+    /// The samples from oldest to newest, only the first `len` are shown.
+    pub samples: [i16; N],
+    pub len: usize,
+    pub line_color: C,
+    pub fill_fading: Fading,
+    pub background: C,
+}
+
+impl<const N: usize, C> Sparkline<N, C>
+where
+    C: FadeColor,
+{
+    pub fn new(bounds: Rectangle, line_color: C, fill_fading: Fading) -> Self {
+        Self {
+            bounds,
+            samples: [0; N],
+            len: 0,
+            line_color,
+            fill_fading,
+            background: C::DEFAULT_TARGET,
+        }
+    }
+
+    pub fn with_background(mut self, background: C) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Appends `sample`, dropping the oldest one once `N` samples are stored.
+    pub fn push(&mut self, sample: i16) {
+        if N == 0 {
+            return;
+        }
+        if self.len == N {
+            self.samples.copy_within(1.., 0);
+            self.len -= 1;
+        }
+        self.samples[self.len] = sample;
+        self.len += 1;
+    }
+
+    /// The shown samples from oldest to newest.
+    pub fn samples(&self) -> &[i16] {
+        &self.samples[..self.len.min(N)]
+    }
+
+    /// The position of each shown sample on the line, spread evenly over the width of
+    /// `bounds`.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        let samples = self.samples();
+        let min = samples.iter().copied().min().unwrap_or(0);
+        let max = samples.iter().copied().max().unwrap_or(0);
+
+        let Rectangle { top_left, size } = self.bounds;
+        let steps = samples.len().saturating_sub(1).max(1) as i64;
+        let columns = i64::from(size.width.saturating_sub(1));
+        let rows = i64::from(size.height.saturating_sub(1));
+        let range = i64::from(max) - i64::from(min);
+
+        samples.iter().zip(0..).map(move |(&sample, index)| {
+            let x = (index * columns + steps / 2) / steps;
+            let y = if range == 0 {
+                rows / 2
+            } else {
+                rows - ((i64::from(sample) - i64::from(min)) * rows + range / 2) / range
+            };
+            top_left + Point::new(x as i32, y as i32)
+        })
+    }
+}
+
+impl<const N: usize, C> Dimensions for Sparkline<N, C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+impl<const N: usize, C> Drawable for Sparkline<N, C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let bottom = self.bounds.top_left.y + self.bounds.size.height as i32;
+        let fill = FadedRectangle::new(self.bounds, self.line_color, self.fill_fading)
+            .with_fade_target(self.background);
+        let style = PrimitiveStyle::with_stroke(self.line_color, 1);
+
+        if self.bounds.is_zero_sized() {
+            return Ok(());
+        }
+
+        let mut points = self.points().peekable();
+        let mut previous: Option<Point> = None;
+        while let Some(first) = points.next() {
+            // Samples sharing a column are drawn as one vertical run, with the fill below it.
+            let (mut top, mut low, mut last) = (first.y, first.y, first);
+            while let Some(next) = points.next_if(|next| next.x == first.x) {
+                (top, low, last) = (top.min(next.y), low.max(next.y), next);
+            }
+
+            // The columns between the samples are filled below the interpolated line.
+            if let Some(start) = previous {
+                let width = first.x - start.x;
+                for x in start.x + 1..first.x {
+                    let offset = x - start.x;
+                    let y =
+                        start.y + ((first.y - start.y) * 2 * offset + width).div_euclid(2 * width);
+                    let strip =
+                        Rectangle::with_corners(Point::new(x, y), Point::new(x, bottom - 1));
+                    fill.draw_partial(target, &strip)?;
+                }
+                Line::new(start, first).into_styled(style).draw(target)?;
+            }
+
+            let below = (bottom - low - 1).max(0) as u32;
+            let strip = Rectangle::new(Point::new(first.x, low + 1), Size::new(1, below));
+            fill.draw_partial(target, &strip)?;
+            let run = Line::new(Point::new(first.x, top), Point::new(first.x, low));
+            run.into_styled(style).draw(target)?;
+            previous = Some(last);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    const BOUNDS: Rectangle = Rectangle::new(Point::new(2, 1), Size::new(10, 8));
+
+    fn sparkline(samples: &[i16]) -> Sparkline<10> {
+        let mut sparkline = Sparkline::new(BOUNDS, Rgb888::WHITE, Fading::None);
+        for &sample in samples {
+            sparkline.push(sample);
+        }
+        sparkline
+    }
+
+    // The topmost drawn row of every column in `bounds`, `None` for empty columns.
+    fn tops(display: &MockDisplay<Rgb888>) -> Vec<Option<i32>> {
+        BOUNDS
+            .columns()
+            .map(|x| {
+                BOUNDS
+                    .rows()
+                    .find(|&y| display.get_pixel(Point::new(x, y)).is_some())
+            })
+            .collect()
+    }
+
+    // Every drawn column is filled down to the bottom of the bounds.
+    fn assert_filled(display: &MockDisplay<Rgb888>) {
+        for x in BOUNDS.columns() {
+            let column = BOUNDS
+                .rows()
+                .map(|y| display.get_pixel(Point::new(x, y)).is_some());
+            let column = column.collect::<Vec<_>>();
+            let top = column
+                .iter()
+                .position(|&drawn| drawn)
+                .unwrap_or(column.len());
+            assert!(column[top..].iter().all(|&drawn| drawn), "{x}");
+        }
+    }
+
+    #[test]
+    fn test_sparkline_ramp() {
+        let sparkline = sparkline(&[-40, -30, -20, -10, 0, 10, 20, 30, 40, 50]);
+        let points = sparkline.points().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            [
+                (2, 8),
+                (3, 7),
+                (4, 6),
+                (5, 6),
+                (6, 5),
+                (7, 4),
+                (8, 3),
+                (9, 3),
+                (10, 2),
+                (11, 1)
+            ]
+        );
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        sparkline.draw(&mut display).unwrap();
+        assert_filled(&display);
+        assert_eq!(
+            tops(&display),
+            [8, 7, 6, 6, 5, 4, 3, 3, 2, 1].map(Some).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_sparkline_constant() {
+        let mut sparkline = sparkline(&[i16::MAX; 3]);
+        assert!(sparkline.points().all(|p| p.y == 4));
+        assert_eq!(sparkline.points().last(), Some(Point::new(11, 4)));
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        sparkline.draw(&mut display).unwrap();
+        assert_eq!(
+            display.affected_area(),
+            Rectangle::new(Point::new(2, 4), Size::new(10, 5))
+        );
+        assert_filled(&display);
+
+        // A single sample is a one pixel wide column, no samples draw nothing.
+        sparkline = self::sparkline(&[7]);
+        assert_eq!(sparkline.points().collect::<Vec<_>>(), [Point::new(2, 4)]);
+        let mut display = MockDisplay::new();
+        sparkline.draw(&mut display).unwrap();
+        assert_eq!(
+            display.affected_area(),
+            Rectangle::new(Point::new(2, 4), Size::new(1, 5))
+        );
+
+        let mut display = MockDisplay::new();
+        self::sparkline(&[]).draw(&mut display).unwrap();
+        assert_eq!(display.affected_area(), Rectangle::zero());
+    }
+
+    #[test]
+    fn test_sparkline_sawtooth() {
+        let mut sparkline = sparkline(&[]);
+        for sample in 0..25 {
+            sparkline.push(sample % 4 * 1000 - 1000);
+        }
+        // The ring buffer keeps the latest samples in order.
+        assert_eq!(
+            sparkline.samples(),
+            [2000, -1000, 0, 1000, 2000, -1000, 0, 1000, 2000, -1000]
+        );
+        let rows = sparkline.points().map(|p| p.y).collect::<Vec<_>>();
+        assert_eq!(rows, [1, 8, 6, 3, 1, 8, 6, 3, 1, 8]);
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        sparkline.draw(&mut display).unwrap();
+        assert_filled(&display);
+        assert_eq!(
+            tops(&display),
+            [1, 5, 5, 2, 1, 5, 5, 2, 1, 5].map(Some).to_vec()
+        );
+        assert_eq!(display.affected_area(), BOUNDS);
+    }
+
+    #[test]
+    fn test_sparkline_narrow_bounds() {
+        // Ten samples in five columns, two per column.
+        let bounds = Rectangle::new(Point::new(2, 1), Size::new(5, 8));
+        let mut sparkline = Sparkline::<10>::new(bounds, Rgb888::WHITE, Fading::None);
+        (0..10).for_each(|sample| sparkline.push(sample));
+        let columns = sparkline.points().map(|p| p.x - 2).collect::<Vec<_>>();
+        assert_eq!(columns, [0, 0, 1, 1, 2, 2, 3, 3, 4, 4]);
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        sparkline.draw(&mut display).unwrap();
+        let tops = bounds.columns().map(|x| {
+            let drawn = |y| display.get_pixel(Point::new(x, y)).is_some();
+            let top = bounds.rows().find(|&y| drawn(y)).unwrap();
+            assert!((top..9).all(drawn), "{x}");
+            top
+        });
+        assert_eq!(tops.collect::<Vec<_>>(), [7, 6, 4, 3, 1]);
+
+        // All samples in a single column are one vertical run above the fill.
+        let column = Rectangle::new(Point::new(2, 1), Size::new(1, 8));
+        let mut sparkline = Sparkline::<10>::new(column, Rgb888::WHITE, Fading::None);
+        for sample in [3, -3, 1] {
+            sparkline.push(sample);
+            let mut display = MockDisplay::new();
+            sparkline.draw(&mut display).unwrap();
+            let top = sparkline.points().map(|p| p.y).min().unwrap();
+            assert_eq!(
+                display.affected_area(),
+                Rectangle::with_corners(Point::new(2, top), Point::new(2, 8))
+            );
+        }
+
+        // Empty bounds draw nothing.
+        let empty = Rectangle::new(Point::new(2, 1), Size::new(0, 8));
+        let mut sparkline = Sparkline::<10>::new(empty, Rgb888::WHITE, Fading::None);
+        (0..10).for_each(|sample| sparkline.push(sample));
+        let mut display = MockDisplay::new();
+        sparkline.draw(&mut display).unwrap();
+        assert_eq!(display.affected_area(), Rectangle::zero());
+    }
+
+    #[test]
+    fn test_sparkline_fill_fading() {
+        let background = Rgb888::CSS_NAVY;
+        let bounds = Rectangle::new(Point::zero(), Size::new(40, 20));
+        let mut sparkline =
+            Sparkline::<40, Rgb888>::new(bounds, Rgb888::CSS_ORANGE, Fading::Bottom { steps: 10 })
+                .with_background(background);
+        for sample in 0..40 {
+            sparkline.push(sample * sample);
+        }
+
+        let mut display = SimulatorDisplay::<Rgb888>::new(bounds.size);
+        sparkline.draw(&mut display).unwrap();
+
+        // The fill shares one ramp over the height of the bounds.
+        let fill = FadedRectangle::new(bounds, Rgb888::CSS_ORANGE, Fading::Bottom { steps: 10 })
+            .with_fade_target(background);
+        for point in [Point::new(10, 19), Point::new(20, 19), Point::new(39, 12)] {
+            assert_eq!(Some(display.get_pixel(point)), fill.color_at(point));
+        }
+        assert_eq!(display.get_pixel(Point::new(39, 0)), Rgb888::CSS_ORANGE);
+        assert_eq!(display.get_pixel(Point::new(0, 19)), Rgb888::CSS_ORANGE);
+        assert_eq!(display.get_pixel(Point::new(0, 18)), Rgb888::BLACK);
+        assert_eq!(display.get_pixel(Point::new(20, 10)), Rgb888::BLACK);
+    }
+}
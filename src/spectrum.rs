@@ -0,0 +1,223 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::{
+    bar_group::BarGroup,
+    color::FadeColor,
+    faded_rectangle::{FadedRectangle, Fading},
+    progress_bar::split,
+};
+
+/// A spectrum analyzer with `N` bands, each shown as a bar that grows like
+/// [`FadedRectangle::resized`] with `fading`, e.g. upwards for [`Fading::Top`].
+///
+/// The bars are placed next to each other with `gap` pixels between them, spare pixels are
+/// spread evenly over the bands. The gaps aren't drawn.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Spectrum<C, const N: usize> {
+    pub bounds: Rectangle,
+    pub gap: u32,
+    pub fading: Fading,
+    pub color: C,
+    /// Colors of the individual bands, replacing `color`.
+    pub palette: Option<[C; N]>,
+    pub background: C,
+    /// The levels shown by the last update, `255` fills the whole band.
+    pub levels: [u8; N],
+}
+
+impl<C, const N: usize> Spectrum<C, N>
+where
+    C: FadeColor,
+{
+    pub fn new(bounds: Rectangle, gap: u32, fading: Fading, color: C) -> Self {
+        Self {
+            bounds,
+            gap,
+            fading,
+            color,
+            palette: None,
+            background: C::DEFAULT_TARGET,
+            levels: [0; N],
+        }
+    }
+
+    pub fn with_palette(mut self, palette: [C; N]) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    pub fn with_background(mut self, background: C) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// The areas the bars of the bands can fill.
+    pub fn band_rects(&self) -> [Rectangle; N] {
+        let (horizontal, _) = self.fading.resize_anchor();
+        let mut cells = split(self.bounds, N as u32, self.gap, !horizontal);
+        [(); N].map(|_| cells.next().unwrap_or(self.bounds))
+    }
+
+    /// The bars for the current levels.
+    pub fn bars(&self) -> BarGroup<C, N> {
+        let cells = self.band_rects();
+        let bars = core::array::from_fn(|band| {
+            let color = self.palette.map_or(self.color, |palette| palette[band]);
+            let bar = FadedRectangle::new(cells[band], color, self.fading)
+                .with_fade_target(self.background);
+            bar.resized(bar_len(&cells[band], self.fading, self.levels[band]))
+        });
+        BarGroup::from_bars(bars)
+    }
+
+    /// Shows `levels` and redraws the bars that changed over an earlier drawing of this
+    /// spectrum, see [`BarGroup::update`].
+    ///
+    /// Returns the bounding box of all written pixels, `None` if nothing changed.
+    pub fn update<D>(
+        &mut self,
+        target: &mut D,
+        levels: &[u8; N],
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let mut bars = self.bars();
+        let cells = self.band_rects();
+        let lengths: [u32; N] =
+            core::array::from_fn(|band| bar_len(&cells[band], self.fading, levels[band]));
+        self.levels = *levels;
+
+        bars.update(target, &lengths)
+    }
+}
+
+// Length of the bar for `level` in `cell`, rounded to the nearest pixel.
+fn bar_len(cell: &Rectangle, fading: Fading, level: u8) -> u32 {
+    let (horizontal, _) = fading.resize_anchor();
+    let full = if horizontal {
+        cell.size.width
+    } else {
+        cell.size.height
+    };
+    (u32::from(level) * full + 127) / 255
+}
+
+impl<C, const N: usize> Dimensions for Spectrum<C, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+impl<C, const N: usize> Drawable for Spectrum<C, N>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let cells = self.band_rects();
+        for (bar, cell) in self.bars().bars.iter().zip(cells) {
+            bar.draw(target)?;
+
+            let full = FadedRectangle { rect: cell, ..*bar };
+            for area in bar.vacated(&full) {
+                if !area.is_zero_sized() {
+                    target.fill_solid(&area, self.background)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888};
+    use embedded_graphics_simulator::SimulatorDisplay;
+
+    #[test]
+    fn test_spectrum_animation() {
+        let size = Size::new(100, 50);
+        let background = Rgb888::CSS_MIDNIGHT_BLUE;
+        let bounds = Rectangle::new(Point::new(0, 2), Size::new(100, 46));
+        let mut spectrum = Spectrum::<Rgb888, 16>::new(
+            bounds,
+            1,
+            Fading::Top { steps: 5 },
+            Rgb888::CSS_SPRING_GREEN,
+        )
+        .with_background(background);
+
+        // 85 pixels for 16 bands, the cells never overlap and fill the bounds.
+        let cells = spectrum.band_rects();
+        assert_eq!(cells[0], Rectangle::new(Point::new(0, 2), Size::new(5, 46)));
+        assert_eq!(cells[15].bottom_right(), bounds.bottom_right());
+        for pair in cells.windows(2) {
+            assert_eq!(
+                pair[0].top_left.x + pair[0].size.width as i32 + 1,
+                pair[1].top_left.x
+            );
+            assert!(pair[0].size.width.abs_diff(pair[1].size.width) <= 1);
+        }
+
+        let mut display = SimulatorDisplay::<Rgb888>::new(size);
+        spectrum.draw(&mut display).unwrap();
+
+        let mut seed = 0x1234_5678_u32;
+        for frame in 0..10 {
+            let mut levels = [0; 16];
+            for level in &mut levels {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                *level = seed as u8;
+            }
+            levels[frame] = 255;
+            spectrum.update(&mut display, &levels).unwrap();
+
+            let mut expected = SimulatorDisplay::<Rgb888>::new(size);
+            spectrum.draw(&mut expected).unwrap();
+            for point in Rectangle::new(Point::zero(), size).points() {
+                assert_eq!(
+                    display.get_pixel(point),
+                    expected.get_pixel(point),
+                    "{frame} {point:?}"
+                );
+            }
+            for (bar, cell) in spectrum.bars().bars.iter().zip(cells) {
+                assert_eq!(cell.intersection(&bar.rect), bar.rect);
+            }
+        }
+
+        let levels = spectrum.levels;
+        assert_eq!(spectrum.update(&mut display, &levels).unwrap(), None);
+    }
+
+    #[test]
+    fn test_single_band() {
+        let bounds = Rectangle::new(Point::new(4, 4), Size::new(50, 10));
+        let mut spectrum =
+            Spectrum::<Rgb888, 1>::new(bounds, 3, Fading::Right { steps: 3 }, Rgb888::RED)
+                .with_palette([Rgb888::CSS_GOLD]);
+        assert_eq!(spectrum.band_rects(), [bounds]);
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        spectrum.draw(&mut display).unwrap();
+        let dirty = spectrum.update(&mut display, &[128]).unwrap();
+        let bar = Rectangle::new(Point::new(4, 4), Size::new(25, 10));
+        assert_eq!(dirty, Some(bar));
+        assert_eq!(display.get_pixel(Point::new(4, 8)), Some(Rgb888::CSS_GOLD));
+
+        let mut expected = MockDisplay::new();
+        spectrum.draw(&mut expected).unwrap();
+        display.assert_eq(&expected);
+    }
+}
@@ -0,0 +1,88 @@
+use embedded_graphics::prelude::Point;
+
+use crate::{
+    color::{bayer_threshold, FadeColor, Interpolation},
+    curve::FadeCurve,
+};
+
+/// Describes how the fade band of a faded primitive blends into the fade target.
+///
+/// The default style fades linearly all the way to black (see [`FadeColor::DEFAULT_TARGET`]).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "C: crate::serde_helpers::RawColor"))]
+pub struct FadeStyle<C> {
+    /// The color the fade interpolates towards, e.g. the UI background.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::color"))]
+    pub target: C,
+    pub curve: FadeCurve,
+    /// Limits how far the fade goes, the outermost step becomes
+    /// `base_color * (1 - max_fade / 255)` plus the matching share of `target`.
+    pub max_fade: u8,
+    /// Flips the gradient within the fade band, so it darkens towards the solid region.
+    pub reversed: bool,
+    /// Keeps `offset` pixels at the fading edge in the base color before the gradient starts.
+    pub offset: u8,
+    pub interpolation: Interpolation,
+    /// Applies ordered dithering to RGB interpolated fades, hiding the banding of low bit
+    /// depth colors like `Rgb565`.
+    pub dithered: bool,
+}
+
+impl<C> FadeStyle<C>
+where
+    C: FadeColor,
+{
+    pub const fn new() -> Self {
+        Self::with_target(C::DEFAULT_TARGET)
+    }
+
+    pub const fn with_target(target: C) -> Self {
+        Self {
+            target,
+            curve: FadeCurve::Linear,
+            max_fade: u8::MAX,
+            reversed: false,
+            offset: 0,
+            interpolation: Interpolation::Rgb,
+            dithered: false,
+        }
+    }
+}
+
+impl<C: Default> Default for FadeStyle<C> {
+    fn default() -> Self {
+        Self {
+            target: C::default(),
+            curve: FadeCurve::Linear,
+            max_fade: u8::MAX,
+            reversed: false,
+            offset: 0,
+            interpolation: Interpolation::Rgb,
+            dithered: false,
+        }
+    }
+}
+
+impl<C> FadeStyle<C>
+where
+    C: FadeColor,
+{
+    // Blends `base_color` by a raw gradient factor, applying the curve, depth and interpolation.
+    // `point` is the display position, used for dithering.
+    pub(crate) fn blend(&self, base_color: C, fade_factor_256: u16, point: Point) -> C {
+        let fade_factor_256 = self.curve.apply(fade_factor_256) as u32;
+        let fade_factor_256 = (fade_factor_256 * self.max_fade as u32 / 255) as u16;
+
+        match self.interpolation {
+            Interpolation::Rgb if self.dithered => base_color.fade_towards_dithered(
+                self.target,
+                fade_factor_256,
+                bayer_threshold(point.x, point.y),
+            ),
+            Interpolation::Rgb => base_color.fade_towards(self.target, fade_factor_256),
+            Interpolation::HsvValue => base_color.fade_value_towards(self.target, fade_factor_256),
+        }
+    }
+}
@@ -0,0 +1,511 @@
+use embedded_graphics::{pixelcolor::PixelColor, prelude::*, primitives::Rectangle};
+
+use crate::{flip::Flip, rotate::Rotation};
+
+/// A combination of 90° rotations, flips and translations, mapping pixels to pixels.
+///
+/// The default is the identity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Transform2D {
+    // `[xx, xy, yx, yy]`, the transformed x is `xx * x + xy * y + offset.x`.
+    matrix: [i32; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::point"))]
+    offset: Point,
+}
+
+impl Transform2D {
+    pub const fn identity() -> Self {
+        Self {
+            matrix: [1, 0, 0, 1],
+            offset: Point::zero(),
+        }
+    }
+
+    /// Rotation around `center`, like [`rotate_point`](crate::rotate::rotate_point).
+    pub fn rotation(rotation: Rotation, center: Point) -> Self {
+        Self::around(rotation.matrix(), center)
+    }
+
+    /// Mirroring at the axes through `axis_center`, like [`flip_point`](crate::flip::flip_point).
+    pub fn flip(flip: Flip, axis_center: Point) -> Self {
+        let sign = |flipped| if flipped { -1 } else { 1 };
+        Self::around(
+            [sign(flip.flips_x()), 0, 0, sign(flip.flips_y())],
+            axis_center,
+        )
+    }
+
+    pub const fn translation(offset: Point) -> Self {
+        Self {
+            matrix: [1, 0, 0, 1],
+            offset,
+        }
+    }
+
+    // Applies `matrix` relative to `center`.
+    fn around(matrix: [i32; 4], center: Point) -> Self {
+        let linear = Self {
+            matrix,
+            offset: Point::zero(),
+        };
+        Self {
+            matrix,
+            offset: center - linear.transform_point(center),
+        }
+    }
+
+    pub fn transform_point(&self, point: Point) -> Point {
+        let [xx, xy, yx, yy] = self.matrix;
+        Point::new(xx * point.x + xy * point.y, yx * point.x + yy * point.y) + self.offset
+    }
+
+    /// Bounding box of the transformed pixels of `rect`.
+    pub fn transform_rectangle(&self, rect: &Rectangle) -> Rectangle {
+        let Some(bottom_right) = rect.bottom_right() else {
+            let [xx, xy, ..] = self.matrix;
+            let size = if xx == 0 && xy != 0 {
+                Size::new(rect.size.height, rect.size.width)
+            } else {
+                rect.size
+            };
+            return Rectangle::new(self.transform_point(rect.top_left), size);
+        };
+
+        Rectangle::with_corners(
+            self.transform_point(rect.top_left),
+            self.transform_point(bottom_right),
+        )
+    }
+
+    /// This transform followed by `other`.
+    pub fn then(self, other: Transform2D) -> Self {
+        let [a1, b1, c1, d1] = self.matrix;
+        let [a2, b2, c2, d2] = other.matrix;
+        let matrix = [
+            a2 * a1 + b2 * c1,
+            a2 * b1 + b2 * d1,
+            c2 * a1 + d2 * c1,
+            c2 * b1 + d2 * d1,
+        ];
+        Self {
+            matrix,
+            offset: other.transform_point(self.offset),
+        }
+    }
+
+    /// The transform that maps transformed points back, e.g. touch positions on a rotated
+    /// display.
+    pub fn inverse(self) -> Self {
+        // The matrix is orthogonal, so its inverse is its transpose.
+        let [xx, xy, yx, yy] = self.matrix;
+        let transposed = Self {
+            matrix: [xx, yx, xy, yy],
+            offset: Point::zero(),
+        };
+        Self {
+            offset: Point::zero() - transposed.transform_point(self.offset),
+            ..transposed
+        }
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Draws `inner` with `transform` applied to every pixel.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Transformed<T> {
+    pub inner: T,
+    pub transform: Transform2D,
+}
+
+impl<T> Transformed<T> {
+    pub const fn new(inner: T, transform: Transform2D) -> Self {
+        Self { inner, transform }
+    }
+}
+
+impl<T> Dimensions for Transformed<T>
+where
+    T: Dimensions,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.transform
+            .transform_rectangle(&self.inner.bounding_box())
+    }
+}
+
+impl<T> Drawable for Transformed<T>
+where
+    T: Drawable,
+{
+    type Color = T::Color;
+    type Output = T::Output;
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let mut transformed = TransformedTarget {
+            parent: target,
+            transform: self.transform,
+        };
+        self.inner.draw(&mut transformed)
+    }
+}
+
+struct TransformedTarget<'a, D> {
+    parent: &'a mut D,
+    transform: Transform2D,
+}
+
+impl<D> Dimensions for TransformedTarget<'_, D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.transform
+            .inverse()
+            .transform_rectangle(&self.parent.bounding_box())
+    }
+}
+
+impl<D, C> DrawTarget for TransformedTarget<'_, D>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let transform = self.transform;
+        self.parent.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(transform.transform_point(point), color)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        // Rows only stay rows in the same order if nothing is rotated or flipped.
+        if self.transform.matrix == Transform2D::identity().matrix {
+            let area = area.translate(self.transform.offset);
+            return self.parent.fill_contiguous(&area, colors);
+        }
+
+        let pixels = area
+            .points()
+            .zip(colors)
+            .map(|(point, color)| Pixel(point, color));
+        self.draw_iter(pixels)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.transform.transform_rectangle(area);
+        self.parent.fill_solid(&area, color)
+    }
+}
+
+/// Shifts `point` horizontally by `shear_x_num / shear_x_den` pixels for every row below
+/// `origin`, rounded down. Rows above `origin` are shifted the other way.
+///
+/// # Panics
+///
+/// Panics if `shear_x_den` is zero.
+pub fn shear_point(point: Point, shear_x_num: i32, shear_x_den: i32, origin: Point) -> Point {
+    point
+        + Point::new(
+            shear_offset(point.y - origin.y, shear_x_num, shear_x_den),
+            0,
+        )
+}
+
+fn shear_offset(rows: i32, num: i32, den: i32) -> i32 {
+    let (num, den) = if den < 0 { (-num, -den) } else { (num, den) };
+    (rows * num).div_euclid(den)
+}
+
+/// Draws `inner` skewed horizontally like [`shear_point`], e.g. for italics or speed lines.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Sheared<T> {
+    pub inner: T,
+    pub shear_x_num: i32,
+    pub shear_x_den: i32,
+    pub origin: Point,
+}
+
+impl<T> Sheared<T> {
+    pub const fn new(inner: T, shear_x_num: i32, shear_x_den: i32, origin: Point) -> Self {
+        Self {
+            inner,
+            shear_x_num,
+            shear_x_den,
+            origin,
+        }
+    }
+
+    fn offset(&self, y: i32) -> i32 {
+        shear_offset(y - self.origin.y, self.shear_x_num, self.shear_x_den)
+    }
+
+    // The smallest and largest offset of the rows of `rect`.
+    fn offsets(&self, rect: &Rectangle) -> (i32, i32) {
+        let top = self.offset(rect.top_left.y);
+        let bottom = self.offset(rect.top_left.y + rect.size.height.saturating_sub(1) as i32);
+        (top.min(bottom), top.max(bottom))
+    }
+}
+
+impl<T> Dimensions for Sheared<T>
+where
+    T: Dimensions,
+{
+    fn bounding_box(&self) -> Rectangle {
+        let bounds = self.inner.bounding_box();
+        let (min, max) = self.offsets(&bounds);
+        let Some(bottom_right) = bounds.bottom_right() else {
+            return bounds.translate(Point::new(min, 0));
+        };
+
+        Rectangle::with_corners(
+            bounds.top_left + Point::new(min, 0),
+            bottom_right + Point::new(max, 0),
+        )
+    }
+}
+
+impl<T> Drawable for Sheared<T>
+where
+    T: Drawable,
+{
+    type Color = T::Color;
+    type Output = T::Output;
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let mut sheared = ShearedTarget {
+            parent: target,
+            shear: Sheared::new((), self.shear_x_num, self.shear_x_den, self.origin),
+        };
+        self.inner.draw(&mut sheared)
+    }
+}
+
+struct ShearedTarget<'a, D> {
+    parent: &'a mut D,
+    shear: Sheared<()>,
+}
+
+impl<D> Dimensions for ShearedTarget<'_, D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        let bounds = self.parent.bounding_box();
+        let (min, max) = self.shear.offsets(&bounds);
+        let Some(bottom_right) = bounds.bottom_right() else {
+            return bounds;
+        };
+
+        Rectangle::with_corners(
+            bounds.top_left - Point::new(max, 0),
+            bottom_right - Point::new(min, 0),
+        )
+    }
+}
+
+impl<D, C> DrawTarget for ShearedTarget<'_, D>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let shear = self.shear;
+        self.parent.draw_iter(
+            pixels.into_iter().map(|Pixel(point, color)| {
+                Pixel(point + Point::new(shear.offset(point.y), 0), color)
+            }),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        // Every row is shifted as a whole.
+        for y in area.rows() {
+            let x = area.top_left.x + self.shear.offset(y);
+            let row = Rectangle::new(Point::new(x, y), Size::new(area.size.width, 1));
+            self.parent.fill_solid(&row, color)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        faded_rectangle::{FadedRectangle, Fading},
+        flip::flip_point,
+        rotate::rotate_point,
+    };
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888};
+
+    struct XorShift(u32);
+
+    impl XorShift {
+        fn below(&mut self, bound: u32) -> i32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            (self.0 % bound) as i32
+        }
+
+        fn point(&mut self) -> Point {
+            Point::new(self.below(200) - 100, self.below(200) - 100)
+        }
+
+        fn transform(&mut self) -> Transform2D {
+            let rotation = Rotation::from_quadrants(self.below(4));
+            let flip =
+                [Flip::None, Flip::Horizontal, Flip::Vertical, Flip::Both][self.below(4) as usize];
+            Transform2D::rotation(rotation, self.point())
+                .then(Transform2D::flip(flip, self.point()))
+                .then(Transform2D::translation(self.point()))
+        }
+    }
+
+    #[test]
+    fn test_transform_matches_point_functions() {
+        let center = Point::new(4, -9);
+        let point = Point::new(-13, 6);
+        for quadrants in 0..4 {
+            let rotation = Rotation::from_quadrants(quadrants);
+            assert_eq!(
+                Transform2D::rotation(rotation, center).transform_point(point),
+                rotate_point(point, rotation, center)
+            );
+        }
+        for flip in [Flip::None, Flip::Horizontal, Flip::Vertical, Flip::Both] {
+            assert_eq!(
+                Transform2D::flip(flip, center).transform_point(point),
+                flip_point(point, flip, center)
+            );
+        }
+        assert_eq!(
+            Transform2D::translation(center).transform_point(point),
+            point + center
+        );
+        assert_eq!(Transform2D::default().transform_point(point), point);
+    }
+
+    #[test]
+    fn test_transform_properties() {
+        let mut random = XorShift(0x9e37_79b9);
+        for _ in 0..500 {
+            let (a, b, c) = (random.transform(), random.transform(), random.transform());
+            let point = random.point();
+
+            assert_eq!(a.inverse().transform_point(a.transform_point(point)), point);
+            assert_eq!(a.transform_point(a.inverse().transform_point(point)), point);
+            assert_eq!(
+                a.then(b).transform_point(point),
+                b.transform_point(a.transform_point(point))
+            );
+            assert_eq!(a.then(b).then(c), a.then(b.then(c)));
+            assert_eq!(a.then(a.inverse()), Transform2D::identity());
+        }
+    }
+
+    #[test]
+    fn test_transformed_drawable() {
+        let rect = Rectangle::new(Point::new(4, 6), Size::new(20, 9));
+        let faded = FadedRectangle::new(rect, Rgb888::CSS_SALMON, Fading::Left { steps: 5 });
+        // Mirror left and right, then move down.
+        let transform = Transform2D::flip(Flip::Horizontal, Point::new(20, 0))
+            .then(Transform2D::translation(Point::new(0, 30)));
+        let transformed = Transformed::new(faded, transform);
+
+        let mut display = MockDisplay::new();
+        transformed.draw(&mut display).unwrap();
+
+        let bounds = Rectangle::new(Point::new(17, 36), Size::new(20, 9));
+        assert_eq!(transformed.bounding_box(), bounds);
+        let mut expected = MockDisplay::new();
+        FadedRectangle::new(bounds, Rgb888::CSS_SALMON, Fading::Right { steps: 5 })
+            .draw(&mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_sheared_rectangle() {
+        assert_eq!(
+            shear_point(Point::new(3, 10), 1, 2, Point::new(0, 4)),
+            Point::new(6, 10)
+        );
+        assert_eq!(
+            shear_point(Point::new(3, 1), 1, 2, Point::new(0, 4)),
+            Point::new(1, 1)
+        );
+        assert_eq!(
+            shear_point(Point::new(3, 1), -1, -2, Point::new(0, 4)),
+            Point::new(1, 1)
+        );
+
+        let rect = Rectangle::new(Point::new(4, 6), Size::new(20, 9));
+        let faded = FadedRectangle::new(rect, Rgb888::CSS_SALMON, Fading::Left { steps: 5 });
+        let sheared = Sheared::new(faded, 1, 2, rect.top_left);
+        assert_eq!(
+            sheared.bounding_box(),
+            Rectangle::new(Point::new(4, 6), Size::new(24, 9))
+        );
+
+        let mut display = MockDisplay::new();
+        sheared.draw(&mut display).unwrap();
+        let mut expected = MockDisplay::new();
+        faded.draw(&mut expected).unwrap();
+
+        // The top row stays in place, the bottom row moves 4 pixels to the right, and every row
+        // keeps its colors.
+        for y in rect.rows() {
+            let offset = (y - rect.top_left.y) / 2;
+            for x in 0..64 {
+                let original = expected.get_pixel(Point::new(x, y));
+                let shifted = Point::new(x + offset, y);
+                if (0..64).contains(&shifted.x) {
+                    assert_eq!(display.get_pixel(shifted), original, "{shifted:?}");
+                }
+            }
+        }
+        assert_eq!(
+            display.get_pixel(Point::new(4, 6)),
+            expected.get_pixel(Point::new(4, 6))
+        );
+        assert_eq!(display.get_pixel(Point::new(4, 14)), None);
+        assert_eq!(
+            display.get_pixel(Point::new(8, 14)),
+            expected.get_pixel(Point::new(4, 14))
+        );
+    }
+}
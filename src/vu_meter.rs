@@ -0,0 +1,580 @@
+use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+
+use crate::{
+    color::FadeColor,
+    faded_rectangle::{union, FadedRectangle, Fading},
+    progress_bar::split,
+    rotate::Rotation,
+};
+
+/// A level meter bar that jumps up to new levels at once and falls back by `decay_per_frame`
+/// on every [`VuMeter::tick`].
+///
+/// The bar grows away from the edge opposite of `fading` within `bounds`, like
+/// [`FadedRectangle::resized`], so the default [`Fading::Top`] gives a vertical bar with a
+/// glowing tip.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct VuMeter<C = Rgb888> {
+    pub bounds: Rectangle,
+    /// The level shown by the last tick, `u16::MAX` fills `bounds`.
+    pub level: u16,
+    pub decay_per_frame: u16,
+    pub color: C,
+    /// Colors of an empty, a half and a full bar, replacing `color` by the length of the bar.
+    pub color_ramp: Option<[C; 3]>,
+    pub background: C,
+    pub fading: Fading,
+    // Highest level set since the last tick.
+    input: u16,
+}
+
+impl<C> VuMeter<C>
+where
+    C: FadeColor,
+{
+    pub fn new(bounds: Rectangle, decay_per_frame: u16, color: C) -> Self {
+        Self {
+            bounds,
+            level: 0,
+            decay_per_frame,
+            color,
+            color_ramp: None,
+            background: C::DEFAULT_TARGET,
+            fading: Fading::Top { steps: 4 },
+            input: 0,
+        }
+    }
+
+    /// Ramps the color from `low` over `mid` to `high` as the bar gets longer, e.g. from
+    /// green over yellow to red.
+    pub fn with_color_ramp(mut self, low: C, mid: C, high: C) -> Self {
+        self.color_ramp = Some([low, mid, high]);
+        self
+    }
+
+    pub fn with_background(mut self, background: C) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn with_fading(mut self, fading: Fading) -> Self {
+        self.fading = fading;
+        self
+    }
+
+    /// Feeds a new reading, which is shown by the next tick if it is above the decayed level.
+    pub fn set_level(&mut self, raw: u16) {
+        self.input = self.input.max(raw);
+    }
+
+    // Length of `bounds` along the axis the bar grows in.
+    fn full_len(&self) -> u32 {
+        let (horizontal, _) = self.fading.resize_anchor();
+        if horizontal {
+            self.bounds.size.width
+        } else {
+            self.bounds.size.height
+        }
+    }
+
+    /// Length of the bar for `level`, rounded to the nearest pixel.
+    pub fn bar_len(&self, level: u16) -> u32 {
+        let max = u64::from(u16::MAX);
+        ((u64::from(level) * u64::from(self.full_len()) + max / 2) / max) as u32
+    }
+
+    /// The color of a bar that is `len` pixels long.
+    pub fn color_at(&self, len: u32) -> C {
+        let Some([low, mid, high]) = self.color_ramp else {
+            return self.color;
+        };
+
+        let full = self.full_len();
+        if full == 0 {
+            return low;
+        }
+        let position = (u64::from(len.min(full)) * 256 / u64::from(full)) as u16;
+        if position <= 128 {
+            low.fade_towards(mid, position * 2)
+        } else {
+            mid.fade_towards(high, (position - 128) * 2)
+        }
+    }
+
+    /// The bar for the current level.
+    pub fn bar(&self) -> FadedRectangle<C> {
+        let len = self.bar_len(self.level);
+        FadedRectangle::new(self.bounds, self.color_at(len), self.fading)
+            .with_fade_target(self.background)
+            .resized(len)
+    }
+
+    /// Lets the level fall by `decay_per_frame`, raises it to the highest level set since the
+    /// last tick, and redraws the difference over an earlier drawing of this meter.
+    ///
+    /// Returns the bounding box of all written pixels, `None` if nothing changed.
+    pub fn tick<D>(&mut self, target: &mut D) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let previous = self.bar();
+        self.level = self
+            .level
+            .saturating_sub(self.decay_per_frame)
+            .max(self.input);
+        self.input = 0;
+
+        self.bar().draw_diff_full(target, &previous)
+    }
+}
+
+impl<C> Dimensions for VuMeter<C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+impl<C> Drawable for VuMeter<C>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let bar = self.bar();
+        bar.draw(target)?;
+
+        let full = FadedRectangle {
+            rect: self.bounds,
+            ..bar
+        };
+        for area in bar.vacated(&full) {
+            if !area.is_zero_sized() {
+                target.fill_solid(&area, self.background)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The peak tick of a [`VuMeter`], which stays at the highest recent level for `hold_frames`
+/// frames and then falls by `fall_per_frame` per frame until it meets the bar again.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct PeakHold<C = Rgb888> {
+    pub hold_frames: u16,
+    pub fall_per_frame: u16,
+    pub color: C,
+    /// Length of the tick along the bar.
+    pub thickness: u32,
+    /// The level the tick is at.
+    pub peak: u16,
+    // Frames left until the tick starts to fall.
+    held: u16,
+}
+
+impl<C> PeakHold<C>
+where
+    C: FadeColor,
+{
+    pub fn new(hold_frames: u16, fall_per_frame: u16, color: C) -> Self {
+        Self {
+            hold_frames,
+            fall_per_frame,
+            color,
+            thickness: 2,
+            peak: 0,
+            held: 0,
+        }
+    }
+
+    pub fn with_thickness(mut self, thickness: u32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Advances the tick by one frame in which the meter showed `new_level`.
+    ///
+    /// Returns the old and the new level of the tick.
+    pub fn update(&mut self, new_level: u16) -> (u16, u16) {
+        let old = self.peak;
+        if new_level >= self.peak {
+            self.peak = new_level;
+            self.held = self.hold_frames;
+        } else if self.held > 0 {
+            self.held -= 1;
+        } else {
+            self.peak = self.peak.saturating_sub(self.fall_per_frame).max(new_level);
+        }
+        (old, self.peak)
+    }
+
+    /// The area of the tick at `level` on `meter`, `None` for an empty bar.
+    pub fn tick_rect(&self, meter: &VuMeter<C>, level: u16) -> Option<Rectangle> {
+        let len = meter.bar_len(level);
+        if len == 0 {
+            return None;
+        }
+
+        let full = FadedRectangle::new(meter.bounds, self.color, meter.fading);
+        let end = full.resized(len);
+        let start = full.resized(len.saturating_sub(self.thickness));
+        start
+            .vacated(&end)
+            .iter()
+            .filter(|area| !area.is_zero_sized())
+            .fold(None, union)
+    }
+
+    /// Moves the tick from `old_level` to [`PeakHold::peak`] over `meter`, which has to be
+    /// drawn already.
+    ///
+    /// The pixels the tick leaves are restored to the bar, including its fade, or to the
+    /// background. The tick itself is always drawn, as the bar may have been redrawn over it.
+    /// Returns the bounding box of all written pixels.
+    pub fn draw_tick<D>(
+        &self,
+        target: &mut D,
+        meter: &VuMeter<C>,
+        old_level: u16,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let bar = meter.bar();
+        let new = self.tick_rect(meter, self.peak);
+        let mut dirty = None;
+
+        if let Some(old) = self.tick_rect(meter, old_level) {
+            let kept = new.unwrap_or(Rectangle::new(old.top_left, Size::zero()));
+            let left =
+                FadedRectangle { rect: kept, ..bar }.vacated(&FadedRectangle { rect: old, ..bar });
+            for area in left.iter().filter(|area| !area.is_zero_sized()) {
+                bar.draw_partial(target, area)?;
+                for uncovered in bar.vacated(&FadedRectangle { rect: *area, ..bar }) {
+                    if !uncovered.is_zero_sized() {
+                        target.fill_solid(&uncovered, meter.background)?;
+                    }
+                }
+                dirty = union(dirty, area);
+            }
+        }
+
+        if let Some(new) = new {
+            target.fill_solid(&new, self.color)?;
+            dirty = union(dirty, &new);
+        }
+        Ok(dirty)
+    }
+
+    /// Advances the tick to the level of `meter` like [`PeakHold::update`] and moves it with
+    /// [`PeakHold::draw_tick`], after the meter was ticked.
+    pub fn tick<D>(
+        &mut self,
+        target: &mut D,
+        meter: &VuMeter<C>,
+    ) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let (old, _) = self.update(meter.level);
+        self.draw_tick(target, meter, old)
+    }
+}
+
+/// `N` level meters next to each other along the axis they grow in, e.g. a stereo pair.
+///
+/// Every second meter uses the mirrored fading, so the tips of each pair face each other. With
+/// [`Fading::Right`] the first meter grows from the left edge of `bounds` and the second one
+/// from the right edge towards it.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct MeterArray<C, const N: usize> {
+    pub meters: [VuMeter<C>; N],
+}
+
+impl<C, const N: usize> MeterArray<C, N>
+where
+    C: FadeColor,
+{
+    /// Splits `bounds` into `N` meters with `gap` pixels between them, the first one using
+    /// `fading` and a color of `colors` each.
+    pub fn new(
+        bounds: Rectangle,
+        gap: u32,
+        fading: Fading,
+        decay_per_frame: u16,
+        colors: [C; N],
+    ) -> Self {
+        let (horizontal, _) = fading.resize_anchor();
+        let mut cells = split(bounds, N as u32, gap, horizontal);
+        let meters = core::array::from_fn(|index| {
+            let cell = cells.next().unwrap_or(bounds);
+            let fading = if index % 2 == 0 {
+                fading
+            } else {
+                fading.rotated(Rotation::Rotate180)
+            };
+            VuMeter::new(cell, decay_per_frame, colors[index]).with_fading(fading)
+        });
+        Self { meters }
+    }
+
+    pub fn with_background(mut self, background: C) -> Self {
+        for meter in &mut self.meters {
+            meter.background = background;
+        }
+        self
+    }
+
+    /// Feeds a new reading to every meter, see [`VuMeter::set_level`].
+    pub fn set_levels(&mut self, levels: &[u16; N]) {
+        for (meter, level) in self.meters.iter_mut().zip(levels) {
+            meter.set_level(*level);
+        }
+    }
+
+    /// Ticks every meter, see [`VuMeter::tick`].
+    ///
+    /// Returns the bounding box of all written pixels, `None` if nothing changed.
+    pub fn tick<D>(&mut self, target: &mut D) -> Result<Option<Rectangle>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let mut dirty = None;
+        for meter in &mut self.meters {
+            let redrawn = meter.tick(target)?;
+            dirty = redrawn.iter().fold(dirty, union);
+        }
+        Ok(dirty)
+    }
+}
+
+impl<C, const N: usize> Dimensions for MeterArray<C, N> {
+    fn bounding_box(&self) -> Rectangle {
+        self.meters
+            .iter()
+            .fold(None, |bounds, meter| union(bounds, &meter.bounds))
+            .unwrap_or(Rectangle::zero())
+    }
+}
+
+impl<C, const N: usize> Drawable for MeterArray<C, N>
+where
+    C: FadeColor,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.meters.iter().try_for_each(|meter| meter.draw(target))
+    }
+}
+
+#[cfg(test)]
+mod simulator_tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+
+    fn meter() -> VuMeter {
+        let bounds = Rectangle::new(Point::new(10, 2), Size::new(6, 60));
+        VuMeter::new(bounds, 6554, Rgb888::CSS_LIME).with_background(Rgb888::CSS_DARK_BLUE)
+    }
+
+    #[test]
+    fn test_vu_meter_decay() {
+        let mut meter = meter();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        meter.draw(&mut display).unwrap();
+
+        meter.set_level(u16::MAX);
+        let mut lengths = Vec::new();
+        for frame in 0..12 {
+            // Readings below the decayed level don't hold the bar up.
+            if frame == 3 {
+                meter.set_level(20_000);
+            }
+            let dirty = meter.tick(&mut display).unwrap();
+            let len = meter.bar_len(meter.level);
+            lengths.push(len);
+
+            let bar = meter.bar().rect;
+            assert_eq!(bar.size.height, len);
+            assert_eq!(bar.bottom_right().map(|p| p.y), (len > 0).then_some(61));
+            assert_eq!(dirty.is_some(), frame < 11, "{frame}");
+
+            let mut expected = MockDisplay::new();
+            meter.draw(&mut expected).unwrap();
+            display.assert_eq(&expected);
+        }
+        assert_eq!(lengths, [60, 54, 48, 42, 36, 30, 24, 18, 12, 6, 0, 0]);
+
+        // A reading above the decayed level is shown at once.
+        meter.set_level(40_000);
+        meter.set_level(30_000);
+        meter.tick(&mut display).unwrap();
+        assert_eq!(meter.level, 40_000);
+    }
+
+    #[test]
+    fn test_vu_meter_color_ramp() {
+        let meter = meter().with_color_ramp(Rgb888::GREEN, Rgb888::YELLOW, Rgb888::RED);
+        assert_eq!(meter.color_at(0), Rgb888::GREEN);
+        assert_eq!(meter.color_at(30), Rgb888::YELLOW);
+        assert_eq!(meter.color_at(60), Rgb888::RED);
+        assert_eq!(meter.color_at(15), Rgb888::new(127, 255, 0));
+        assert_eq!(meter.color_at(45), Rgb888::new(255, 127, 0));
+
+        // The whole bar changes color as it grows.
+        let mut meter = meter;
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        meter.draw(&mut display).unwrap();
+        meter.set_level(u16::MAX);
+        meter.tick(&mut display).unwrap();
+        assert_eq!(display.get_pixel(Point::new(12, 61)), Some(Rgb888::RED));
+
+        let horizontal = meter.with_fading(Fading::Right { steps: 2 });
+        assert_eq!(horizontal.bar_len(u16::MAX), 6);
+        assert_eq!(horizontal.bar().rect.top_left, Point::new(10, 2));
+    }
+
+    #[test]
+    fn test_peak_hold() {
+        let mut meter = meter();
+        let mut peak = PeakHold::new(3, 3277, Rgb888::WHITE);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        meter.draw(&mut display).unwrap();
+
+        let burst = [50_000, u16::MAX, 30_000];
+        let mut trajectory = Vec::new();
+        for frame in 0..16 {
+            if let Some(&level) = burst.get(frame) {
+                meter.set_level(level);
+            }
+            meter.tick(&mut display).unwrap();
+            let (old, new) = peak.update(meter.level);
+            assert_eq!(old, trajectory.last().copied().unwrap_or(0));
+            peak.draw_tick(&mut display, &meter, old).unwrap();
+            trajectory.push(new);
+
+            let mut expected = MockDisplay::new();
+            expected.set_allow_overdraw(true);
+            meter.draw(&mut expected).unwrap();
+            peak.draw_tick(&mut expected, &meter, peak.peak).unwrap();
+            display.assert_eq(&expected);
+        }
+
+        // Held for three frames, then falling by half as much as the bar.
+        assert_eq!(
+            trajectory,
+            [
+                50_000, 65_535, 65_535, 65_535, 65_535, 62_258, 58_981, 55_704, 52_427, 49_150,
+                45_873, 42_596, 39_319, 36_042, 32_765, 29_488
+            ]
+        );
+        assert_eq!(
+            peak.tick_rect(&meter, u16::MAX),
+            Some(Rectangle::new(Point::new(10, 2), Size::new(6, 2)))
+        );
+        assert_eq!(peak.tick_rect(&meter, 0), None);
+    }
+
+    #[test]
+    fn test_peak_hold_restores_fade() {
+        // The tick leaves the faded tip of the bar, which has to get its fade colors back.
+        let mut meter = meter().with_fading(Fading::Top { steps: 8 });
+        let mut peak = PeakHold::new(0, 1000, Rgb888::WHITE).with_thickness(3);
+        meter.set_level(40_000);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        meter.tick(&mut display).unwrap();
+        meter.draw(&mut display).unwrap();
+        peak.tick(&mut display, &meter).unwrap();
+        assert_eq!(display.get_pixel(Point::new(12, 25)), Some(Rgb888::WHITE));
+        peak.update(u16::MAX);
+        let dirty = peak.draw_tick(&mut display, &meter, 40_000).unwrap();
+        assert_eq!(
+            dirty,
+            Some(Rectangle::new(Point::new(10, 2), Size::new(6, 26)))
+        );
+
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        meter.draw(&mut expected).unwrap();
+        peak.draw_tick(&mut expected, &meter, peak.peak).unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn test_meter_pair() {
+        let bounds = Rectangle::new(Point::new(2, 10), Size::new(60, 8));
+        let mut pair = MeterArray::new(
+            bounds,
+            2,
+            Fading::Right { steps: 4 },
+            1000,
+            [Rgb888::CSS_LIME, Rgb888::CSS_ORANGE],
+        );
+        let [left, right] = pair.meters;
+        assert_eq!(left.fading, Fading::Right { steps: 4 });
+        assert_eq!(right.fading, Fading::Left { steps: 4 });
+        assert_eq!(
+            left.bounds,
+            Rectangle::new(Point::new(2, 10), Size::new(29, 8))
+        );
+        assert_eq!(
+            right.bounds,
+            Rectangle::new(Point::new(33, 10), Size::new(29, 8))
+        );
+        assert_eq!(pair.bounding_box(), bounds);
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        pair.draw(&mut display).unwrap();
+        // 20 and 10 pixels.
+        pair.set_levels(&[45_198, 22_598]);
+        let dirty = pair.tick(&mut display).unwrap();
+        assert_eq!(
+            dirty,
+            Some(Rectangle::new(Point::new(2, 10), Size::new(60, 8)))
+        );
+
+        let [left, right] = pair.meters;
+        assert_eq!(
+            left.bar().rect,
+            Rectangle::new(Point::new(2, 10), Size::new(20, 8))
+        );
+        assert_eq!(
+            right.bar().rect,
+            Rectangle::new(Point::new(52, 10), Size::new(10, 8))
+        );
+        // The tips glow towards the gap in the middle.
+        let y = 13;
+        assert_eq!(display.get_pixel(Point::new(2, y)), Some(Rgb888::CSS_LIME));
+        assert_ne!(display.get_pixel(Point::new(21, y)), Some(Rgb888::CSS_LIME));
+        assert_eq!(
+            display.get_pixel(Point::new(61, y)),
+            Some(Rgb888::CSS_ORANGE)
+        );
+        assert_ne!(
+            display.get_pixel(Point::new(52, y)),
+            Some(Rgb888::CSS_ORANGE)
+        );
+        assert_eq!(display.get_pixel(Point::new(32, y)), None);
+
+        let mut expected = MockDisplay::new();
+        pair.draw(&mut expected).unwrap();
+        display.assert_eq(&expected);
+    }
+}